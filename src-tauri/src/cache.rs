@@ -0,0 +1,89 @@
+//! Content-addressed hashing for generation tasks, recording which ones ran
+//! with which inputs.
+//!
+//! Mirrors moon's task-runner hashing: a task's cache key folds its own
+//! declared inputs (`Task::cache_inputs`) together with the hashes of
+//! everything it depends on, so changing an upstream task invalidates every
+//! task downstream of it even when a dependent's own inputs didn't change.
+//! This only records a task's hash and a log message, not its real
+//! filesystem output -- every task here writes into a fresh `staging_dir`
+//! scoped to its own run (see `tasks::TaskContext::staging_dir`), so nothing
+//! here is replayed in place of actually re-executing a task.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::state::AppState;
+use crate::tasks::{Task, TaskContext};
+
+/// Bumped whenever `CachedOutput`'s shape or meaning changes, and folded
+/// into every hash, so entries written by an older build are never
+/// misread as a hit -- they simply miss and the task re-executes.
+const CACHE_FORMAT_VERSION: u32 = 1;
+
+/// Compute the cache key for `task`. `dependency_hashes` must already
+/// contain an entry for every task this one depends on -- callers process
+/// tasks in dependency order (see `scheduler::topological_order`) and feed
+/// each task's own computed hash back into the same map before hashing its
+/// dependents, so a hash mismatch anywhere upstream rolls all the way down.
+pub fn hash_task(task: &dyn Task, context: &TaskContext, dependency_hashes: &HashMap<String, String>) -> String {
+    let mut hasher = DefaultHasher::new();
+    CACHE_FORMAT_VERSION.hash(&mut hasher);
+    task.id().hash(&mut hasher);
+
+    for input in task.cache_inputs(context) {
+        input.hash(&mut hasher);
+    }
+
+    for dep in task.dependencies() {
+        if let Some(dep_hash) = dependency_hashes.get(dep) {
+            dep_hash.hash(&mut hasher);
+        }
+    }
+
+    format!("{:x}", hasher.finish())
+}
+
+/// A task's recorded outcome, written by `store_cached_output` for later
+/// inspection -- see the module docs for why nothing replays it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CachedOutput {
+    pub message: String,
+}
+
+fn cache_dir(app_state: &AppState) -> Result<PathBuf, String> {
+    let dir = app_state.get_app_data_dir()?.join("task-cache");
+    if !dir.exists() {
+        std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to create '{}': {}", dir.display(), e))?;
+    }
+    Ok(dir)
+}
+
+fn cache_entry_path(app_state: &AppState, hash: &str) -> Result<PathBuf, String> {
+    Ok(cache_dir(app_state)?.join(format!("{}.json", hash)))
+}
+
+/// Record a task's output under its content-addressed hash, keyed the same
+/// way `hash_task` computes it, so a hit means this exact task -- with this
+/// exact resolved config and these exact dependency outputs -- has run
+/// before for *any* project, not just this one. Written atomically (write
+/// to a `.tmp` file, then rename), matching `AppState::save_checkpoint`.
+///
+/// Nothing replays this today: every task here writes into a fresh
+/// `staging_dir` scoped to its own run (see `tasks::TaskContext::staging_dir`),
+/// so a recorded message alone can't stand in for re-executing a task --
+/// this is purely a record of what ran, for future tooling to read.
+pub fn store_cached_output(app_state: &AppState, hash: &str, message: &str) -> Result<(), String> {
+    let path = cache_entry_path(app_state, hash)?;
+    let tmp_path = path.with_extension("json.tmp");
+
+    let cached = CachedOutput { message: message.to_string() };
+    let content = serde_json::to_string_pretty(&cached).map_err(|e| format!("Failed to serialize cache entry: {}", e))?;
+
+    std::fs::write(&tmp_path, content).map_err(|e| format!("Failed to write '{}': {}", tmp_path.display(), e))?;
+    std::fs::rename(&tmp_path, &path).map_err(|e| format!("Failed to finalize '{}': {}", path.display(), e))
+}
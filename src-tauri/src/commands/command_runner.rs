@@ -1,18 +1,27 @@
 use std::process::{Command, Stdio};
 use std::path::{Path, PathBuf};
 use std::fs;
-use std::io::{Read, BufRead, BufReader};
+use std::fmt;
+use std::io::{BufRead, BufReader};
+use std::sync::Arc;
+use std::thread;
 use std::thread::sleep as thread_sleep;
 use std::time::Duration as StdDuration;
+use std::time::Instant as StdInstant;
+use notify::{RecursiveMode, Watcher};
 use regex::Regex;
 use tauri::AppHandle;
 use tauri::Emitter;
-use tokio::time::{sleep, Duration};
+use tokio::sync::{mpsc, Notify};
+use tokio::time::{sleep, sleep_until, Duration, Instant};
 
 use serde::{Deserialize, Serialize};
 use tauri::async_runtime::spawn_blocking;
 use tokio::time::{timeout};
 use log::{debug, info, warn};
+use once_cell::sync::Lazy;
+use std::sync::RwLock;
+use std::collections::HashMap;
 
 /// Options for command execution
 #[derive(Debug, Clone)]
@@ -21,6 +30,11 @@ pub struct CommandOptions {
     pub max_retries: u32,
     /// Delay between retries in seconds
     pub retry_delay: u32,
+    /// Delay before retrying a command that was force-killed for exceeding
+    /// its timeout, in seconds -- kept separate from `retry_delay` since a
+    /// hung install is a different failure mode than a clean non-zero exit
+    /// and often warrants a longer backoff.
+    pub timeout_retry_delay: u32,
     /// Whether to verify the command output
     pub verify_output: bool,
     /// Timeout for command execution in seconds
@@ -36,6 +50,7 @@ impl Default for CommandOptions {
         Self {
             max_retries: 1,
             retry_delay: 2,
+            timeout_retry_delay: 5,
             verify_output: true,
             timeout: 120,
             verify_project_dir: false,
@@ -55,6 +70,302 @@ pub struct CommandResult {
     pub stderr: String,
     /// Exit code from the command
     pub exit_code: i32,
+    /// Signal that killed the process, if it died from one instead of
+    /// exiting normally. Always `None` on Windows.
+    #[serde(default)]
+    pub signal: Option<i32>,
+    /// Wall-clock time the command took to run, in milliseconds.
+    #[serde(default)]
+    pub duration_ms: u64,
+    /// The spawned child's PID, if it got far enough to be assigned one.
+    #[serde(default)]
+    pub pid: Option<u32>,
+}
+
+/// The program, args, and working directory a command was run with --
+/// carried by `ProcessError` so a failure can be reported (and logged)
+/// with full context instead of a bare message.
+#[derive(Debug, Clone)]
+pub struct ProcessContext {
+    pub program: String,
+    pub args: Vec<String>,
+    pub current_dir: PathBuf,
+}
+
+impl ProcessContext {
+    fn new(program: impl Into<String>, args: &[String], current_dir: &Path) -> Self {
+        Self {
+            program: program.into(),
+            args: args.to_vec(),
+            current_dir: current_dir.to_path_buf(),
+        }
+    }
+
+    /// The command as a reader would type it, e.g. `` npx create-next-app foo ``.
+    pub fn command_line(&self) -> String {
+        if self.args.is_empty() {
+            self.program.clone()
+        } else {
+            format!("{} {}", self.program, self.args.join(" "))
+        }
+    }
+}
+
+/// Why a command run by `CommandBuilder` (or the free functions built on
+/// it) failed, carrying the program/args/working dir it was run with --
+/// à la cargo-util's `ProcessError` -- instead of collapsing everything to
+/// a bare `String`.
+#[derive(Debug, Clone)]
+pub enum ProcessError {
+    /// The command could not be spawned at all (bad program name,
+    /// permission denied, ...).
+    Spawn { context: ProcessContext, message: String },
+    /// The command ran and exited unsuccessfully. `signal` is `Some` when
+    /// the process was killed by a signal rather than exiting normally, in
+    /// which case `code` is `None`.
+    Exit { context: ProcessContext, code: Option<i32>, signal: Option<i32> },
+    /// The command exceeded its timeout and was force-killed.
+    TimedOut { context: ProcessContext, timeout_secs: u64 },
+    /// Waiting on the spawned child itself failed (an OS-level error
+    /// reaping it, not a failure of the command it ran).
+    Wait { context: ProcessContext, message: String },
+    /// The command reported success, but a post-exit check (e.g. the
+    /// project directory a generator is expected to create) didn't hold.
+    VerificationFailed { context: ProcessContext, message: String },
+}
+
+impl fmt::Display for ProcessError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProcessError::Spawn { context, message } => {
+                write!(f, "failed to spawn `{}`: {}", context.command_line(), message)
+            }
+            ProcessError::Exit { context, code: _, signal: Some(signal) } => {
+                write!(f, "`{}` terminated by signal {}", context.command_line(), signal)
+            }
+            ProcessError::Exit { context, code: Some(code), signal: None } => {
+                write!(f, "`{}` (exit code: {})", context.command_line(), code)
+            }
+            ProcessError::Exit { context, code: None, signal: None } => {
+                write!(f, "`{}` exited with an unknown status", context.command_line())
+            }
+            ProcessError::TimedOut { context, timeout_secs } => {
+                write!(f, "`{}` timed out after {} seconds and was force-killed", context.command_line(), timeout_secs)
+            }
+            ProcessError::Wait { context, message } => {
+                write!(f, "failed to wait for `{}`: {}", context.command_line(), message)
+            }
+            ProcessError::VerificationFailed { context, message } => {
+                write!(f, "`{}` succeeded but {}", context.command_line(), message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ProcessError {}
+
+/// The signal that terminated `status`, if any (Unix only -- always `None`
+/// on Windows, where `ExitStatusExt::signal` doesn't exist).
+fn exit_signal(status: &std::process::ExitStatus) -> Option<i32> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::ExitStatusExt;
+        status.signal()
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = status;
+        None
+    }
+}
+
+/// Which stream a `.stream_to` line came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StreamSource {
+    Stdout,
+    Stderr,
+}
+
+/// Where `.stream_to` forwards each decoded line as it's produced.
+#[derive(Clone)]
+struct StreamTarget {
+    app_handle: AppHandle,
+    step: String,
+}
+
+/// A caller-supplied hook set via `.on_line`, invoked with each decoded
+/// output line as it arrives rather than `.stream_to`'s fixed
+/// `command-output` Tauri event -- for blueprint authors that want to react
+/// to output in Rust (progress bars, log filtering) instead of the frontend.
+type LineCallback = Arc<dyn Fn(StreamSource, &str) + Send + Sync>;
+
+/// Emit one line of a `.stream_to` target's `command-output` event.
+fn emit_command_line(target: &StreamTarget, stream: StreamSource, line: &str) {
+    let payload = serde_json::json!({
+        "step": target.step,
+        "stream": stream,
+        "line": line,
+    });
+    if let Err(e) = target.app_handle.emit("command-output", payload) {
+        warn!("Failed to emit command-output event: {}", e);
+    }
+}
+
+/// Resolved executable paths, keyed by the command name they were looked up
+/// under -- so a generation run with many steps invoking `npm` doesn't
+/// re-scan `PATH` (and the well-known install dirs below) for every one.
+static EXECUTABLE_CACHE: Lazy<RwLock<HashMap<String, PathBuf>>> = Lazy::new(|| RwLock::new(HashMap::new()));
+
+/// Install locations `PATH` commonly misses for the Node toolchain this
+/// crate shells out to -- Homebrew on Apple Silicon, and nvm/volta's
+/// "current" shims on a shell that hasn't re-sourced its profile.
+fn well_known_install_dirs() -> Vec<PathBuf> {
+    let mut dirs = vec![
+        PathBuf::from("/opt/homebrew/bin"),
+        PathBuf::from("/usr/local/bin"),
+        PathBuf::from("/usr/bin"),
+    ];
+    let home_var = if cfg!(windows) { "USERPROFILE" } else { "HOME" };
+    if let Ok(home) = std::env::var(home_var) {
+        let home = PathBuf::from(home);
+        dirs.push(home.join(".volta/bin"));
+        dirs.push(home.join(".nvm/current/bin"));
+        dirs.push(home.join(".local/bin"));
+    }
+    dirs
+}
+
+/// Whether `path` is a file this process can actually execute -- on Unix,
+/// that means one of the executable permission bits is set; on Windows,
+/// reaching a candidate filename (`.exe`/`.cmd`/`.bat`) is enough.
+fn is_executable(path: &Path) -> bool {
+    if !path.is_file() {
+        return false;
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::metadata(path)
+            .map(|meta| meta.permissions().mode() & 0o111 != 0)
+            .unwrap_or(false)
+    }
+    #[cfg(not(unix))]
+    {
+        true
+    }
+}
+
+/// Search `PATH` (plus `well_known_install_dirs`) for `command` up front,
+/// so a missing `npm`/`npx`/`pnpm` fails immediately with a message naming
+/// the tool, instead of surfacing only as "No such file or directory" after
+/// every retry has already been spent spawning something that was never
+/// going to succeed. Resolved paths are cached in `EXECUTABLE_CACHE` for the
+/// life of the process.
+pub fn resolve_executable(command: &str) -> Result<PathBuf, String> {
+    if let Some(cached) = EXECUTABLE_CACHE.read().unwrap().get(command) {
+        return Ok(cached.clone());
+    }
+
+    let candidates: Vec<String> = if cfg!(windows) {
+        vec![
+            format!("{}.exe", command),
+            format!("{}.cmd", command),
+            format!("{}.bat", command),
+            command.to_string(),
+        ]
+    } else {
+        vec![command.to_string()]
+    };
+
+    let search_dirs: Vec<PathBuf> = std::env::var_os("PATH")
+        .map(|path| std::env::split_paths(&path).collect())
+        .unwrap_or_default()
+        .into_iter()
+        .chain(well_known_install_dirs())
+        .collect();
+
+    for dir in &search_dirs {
+        for candidate in &candidates {
+            let full_path = dir.join(candidate);
+            if is_executable(&full_path) {
+                EXECUTABLE_CACHE.write().unwrap().insert(command.to_string(), full_path.clone());
+                return Ok(full_path);
+            }
+        }
+    }
+
+    Err(format!(
+        "'{command}' was not found on PATH. Install it (e.g. via nvm, Volta, or your system's package manager) and make sure it's on PATH before retrying."
+    ))
+}
+
+/// Files whose presence marks a directory as a project root, in the order
+/// this crate prefers when more than one generator-style output is possible.
+const PROJECT_MARKERS: &[&str] = &["package.json", "Cargo.toml", "pnpm-workspace.yaml", ".git"];
+
+/// The set of subdirectories directly under `dir`, for diffing against the
+/// same snapshot taken after a generator command runs.
+fn snapshot_dirs(dir: &Path) -> std::collections::HashSet<PathBuf> {
+    fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|entry| entry.path())
+                .filter(|path| path.is_dir())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Find the project a generator command just created by scanning
+/// `working_dir` for a subdirectory that wasn't in `before` and contains one
+/// of `PROJECT_MARKERS`, instead of assuming the generator named its output
+/// after `project_name` -- `create-vite`, `npm create`, and similar
+/// scaffolders don't always honor that.
+fn detect_project_origin(working_dir: &Path, before: &std::collections::HashSet<PathBuf>) -> Option<PathBuf> {
+    let entries = fs::read_dir(working_dir).ok()?;
+    entries
+        .flatten()
+        .map(|entry| entry.path())
+        .find(|path| {
+            path.is_dir()
+                && !before.contains(path)
+                && PROJECT_MARKERS.iter().any(|marker| path.join(marker).exists())
+        })
+}
+
+/// Kill a timed-out child and everything it spawned, by PID. On Unix this
+/// targets the negative PID (the process group `process_group(0)` put the
+/// child in at spawn) with `SIGTERM` then `SIGKILL`; on Windows it asks
+/// `taskkill` to tear down the PID's whole process tree. Shells out to the
+/// platform's own process-management tool rather than linking a
+/// job-object/signal FFI crate for it.
+pub(crate) fn kill_process_tree(pid: u32) {
+    #[cfg(unix)]
+    {
+        let pgid = format!("-{}", pid);
+        let _ = Command::new("kill").args(["-TERM", &pgid]).status();
+        thread::sleep(StdDuration::from_millis(300));
+        let _ = Command::new("kill").args(["-KILL", &pgid]).status();
+    }
+    #[cfg(windows)]
+    {
+        let _ = Command::new("taskkill")
+            .args(["/PID", &pid.to_string(), "/T", "/F"])
+            .status();
+    }
+}
+
+/// The largest char boundary in `s` at or before `index` -- `str`'s own
+/// `floor_char_boundary` is still unstable, and slicing at an arbitrary
+/// byte offset panics if it lands mid-character.
+fn floor_char_boundary(s: &str, index: usize) -> usize {
+    let mut idx = index.min(s.len());
+    while idx > 0 && !s.is_char_boundary(idx) {
+        idx -= 1;
+    }
+    idx
 }
 
 /// Builder for creating and executing commands
@@ -68,6 +379,20 @@ pub struct CommandBuilder {
     working_dir: PathBuf,
     /// Options for command execution
     options: CommandOptions,
+    /// Opt-in live line streaming target, set via `.stream_to`.
+    stream_target: Option<StreamTarget>,
+    /// Opt-in per-line callback, set via `.on_line`.
+    line_callback: Option<LineCallback>,
+    /// Canned prompt/reply pairs set via `.interactive_answer`. Non-empty
+    /// switches `execute` to the PTY-backed path so the replies can
+    /// actually reach the child's controlling terminal.
+    interactive_answers: Vec<(Regex, String)>,
+    /// Opt-in mirror of each attempt's child PID, set via
+    /// `.report_pid_to` -- lets an external caller (`WatchMode`'s
+    /// restart-on-change loop) kill the process group via
+    /// `kill_process_tree` without `execute` itself knowing about watch
+    /// mode.
+    pid_reporter: Option<Arc<std::sync::Mutex<Option<u32>>>>,
 }
 
 impl CommandBuilder {
@@ -78,9 +403,55 @@ impl CommandBuilder {
             args: Vec::new(),
             working_dir: PathBuf::from("."),
             options: CommandOptions::default(),
+            stream_target: None,
+            line_callback: None,
+            interactive_answers: Vec::new(),
+            pid_reporter: None,
         }
     }
-    
+
+    /// Forward each decoded output line to the frontend as it's produced,
+    /// as a `command-output` event carrying `step`, `stream`
+    /// ("stdout"/"stderr"), and `line` -- instead of only returning the
+    /// combined buffers in `CommandResult` once the process exits. Mirrors
+    /// how `ProcessRunner` streams PTY output live.
+    pub fn stream_to<S: Into<String>>(mut self, app_handle: AppHandle, step: S) -> Self {
+        self.stream_target = Some(StreamTarget { app_handle, step: step.into() });
+        self
+    }
+
+    /// Invoke `callback` with each decoded stdout/stderr line as it's
+    /// produced, instead of waiting for the final `CommandResult` to read
+    /// it out of the aggregated buffers -- lets a long-running command like
+    /// `npm install` report live progress to its caller directly.
+    pub fn on_line<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(StreamSource, &str) + Send + Sync + 'static,
+    {
+        self.line_callback = Some(Arc::new(callback));
+        self
+    }
+
+    /// Answer an interactive prompt automatically: once PTY output matches
+    /// `pattern`, `reply` (plus a trailing newline) is written back to the
+    /// child's controlling terminal. Setting any of these switches
+    /// `execute` to a PTY-backed path instead of plain pipes, since plain
+    /// pipes can't carry a reply to a program reading from its controlling
+    /// terminal. Order matters: prompts are matched in the order added.
+    pub fn interactive_answer(mut self, pattern: Regex, reply: impl Into<String>) -> Self {
+        self.interactive_answers.push((pattern, reply.into()));
+        self
+    }
+
+    /// Mirror each attempt's child PID into `reporter` as soon as it's
+    /// known (cleared back to `None` once that attempt's process exits).
+    /// Used by `WatchMode` so it can kill a still-running command via
+    /// `kill_process_tree` when a new change arrives.
+    pub fn report_pid_to(mut self, reporter: Arc<std::sync::Mutex<Option<u32>>>) -> Self {
+        self.pid_reporter = Some(reporter);
+        self
+    }
+
     /// Add an argument to the command
     pub fn arg<S: Into<String>>(mut self, arg: S) -> Self {
         self.args.push(arg.into());
@@ -116,7 +487,13 @@ impl CommandBuilder {
         self.options.retry_delay = delay;
         self
     }
-    
+
+    /// Set the delay before retrying a command that timed out, in seconds
+    pub fn timeout_retry_delay(mut self, delay: u32) -> Self {
+        self.options.timeout_retry_delay = delay;
+        self
+    }
+
     /// Set the timeout for the command in seconds
     pub fn timeout(mut self, timeout: u64) -> Self {
         self.options.timeout = timeout;
@@ -151,17 +528,20 @@ impl CommandBuilder {
     }
     
     /// Execute the command
-    pub async fn execute(self) -> Result<CommandResult, String> {
+    pub async fn execute(self) -> Result<CommandResult, ProcessError> {
         // Check if this is a create-next-app command or similar
-        let is_project_generator = 
+        let is_project_generator =
             (self.command == "npx" && !self.args.is_empty() && self.args[0].contains("create-")) ||
             (self.command == "npm" && self.args.len() > 1 && self.args[0] == "init");
-            
-        // Check if this is a project directory that we need to verify gets created
-        let project_name = if is_project_generator && self.options.verify_output && !self.args.is_empty() {
-            self.args.last().map(|s| s.to_string())
+
+        // Snapshot of working_dir's subdirectories before the generator runs,
+        // so `detect_project_origin` afterward can tell which one it created
+        // rather than assuming it's named after `project_name` -- generators
+        // like `create-vite`/`npm create` don't always honor that.
+        let pre_existing_dirs = if is_project_generator && self.options.verify_project_dir {
+            snapshot_dirs(&self.working_dir)
         } else {
-            None
+            Default::default()
         };
         
         // Adjust command for platform if needed
@@ -171,13 +551,35 @@ impl CommandBuilder {
             self.command.clone()
         };
         
-        info!("Executing command: {} {} in {}", 
+        info!("Executing command: {} {} in {}",
             platform_cmd,
             self.args.join(" "),
             self.working_dir.display()
         );
-        
+
+        let context = ProcessContext::new(platform_cmd.clone(), &self.args, &self.working_dir);
+
+        // Fail fast if the executable itself isn't installed, instead of
+        // spending every retry attempt spawning something that was never
+        // going to succeed.
+        if let Err(message) = resolve_executable(&platform_cmd) {
+            return Err(ProcessError::Spawn { context, message });
+        }
+
         for attempt in 1..=self.options.max_retries {
+            // A command with canned prompt answers needs a real
+            // controlling terminal to deliver them to -- plain pipes can't.
+            if !self.interactive_answers.is_empty() {
+                return timeout(
+                    Duration::from_secs(self.options.timeout),
+                    self.run_interactive(&platform_cmd),
+                )
+                .await
+                .unwrap_or_else(|_| {
+                    Err(ProcessError::TimedOut { context: context.clone(), timeout_secs: self.options.timeout })
+                });
+            }
+
             // Create command
             let mut cmd = Command::new(&platform_cmd);
             cmd.args(&self.args)
@@ -200,80 +602,159 @@ impl CommandBuilder {
                 cmd.env("CI", "false");
                 cmd.env("NODE_ENV", "development");
             }
-            
+
+            // Put the child in its own process group (Unix) so a timeout
+            // can kill the whole tree it spawned, not just the immediate
+            // child -- npm/npx fan out to further installer processes that
+            // would otherwise be orphaned.
+            #[cfg(unix)]
+            {
+                use std::os::unix::process::CommandExt;
+                cmd.process_group(0);
+            }
+
             let options = self.options.clone();
             let working_dir = self.working_dir.clone();
-            
+            let stream_target = self.stream_target.clone();
+            let line_callback = self.line_callback.clone();
+            let pid_reporter = self.pid_reporter.clone();
+            let attempt_context = context.clone();
+
+            // Reports the child's PID as soon as it's spawned, so the
+            // timeout branch below can kill it even though the `Child`
+            // itself stays owned by the blocking closure for the rest of
+            // its lifetime.
+            let (pid_tx, pid_rx) = std::sync::mpsc::channel::<Option<u32>>();
+
             // Execute with a timeout
             let cmd_future = spawn_blocking(move || {
+                let start = StdInstant::now();
                 match cmd.spawn() {
                     Ok(mut child) => {
-                        let mut stdout_lines = Vec::new();
-                        let mut stderr_lines = Vec::new();
-                        
-                        // Read stdout lines
-                        if let Some(stdout) = child.stdout.take() {
-                            let stdout_reader = BufReader::new(stdout);
-                            for line in stdout_reader.lines() {
-                                if let Ok(line) = line {
+                        let pid = child.id();
+                        let _ = pid_tx.send(Some(pid));
+                        if let Some(reporter) = &pid_reporter {
+                            *reporter.lock().unwrap() = Some(pid);
+                        }
+                        let stdout = child.stdout.take();
+                        let stderr = child.stderr.take();
+
+                        // Drain stdout and stderr on separate threads instead
+                        // of reading one to EOF before starting the other --
+                        // a command that fills one pipe's buffer before the
+                        // other closes (npm/npx logging warnings to stderr
+                        // while still writing to stdout is the common case)
+                        // would otherwise deadlock us blocked on the first
+                        // while the child blocks writing to the second.
+                        let stdout_target = stream_target.clone();
+                        let stdout_callback = line_callback.clone();
+                        let stdout_thread = thread::spawn(move || {
+                            let mut lines = Vec::new();
+                            if let Some(stdout) = stdout {
+                                for line in BufReader::new(stdout).lines().flatten() {
                                     debug!("[STDOUT] {}", line);
-                                    stdout_lines.push(line);
+                                    if let Some(target) = &stdout_target {
+                                        emit_command_line(target, StreamSource::Stdout, &line);
+                                    }
+                                    if let Some(callback) = &stdout_callback {
+                                        callback(StreamSource::Stdout, &line);
+                                    }
+                                    lines.push(line);
                                 }
                             }
-                        }
-                        
-                        // Read stderr lines
-                        if let Some(stderr) = child.stderr.take() {
-                            let stderr_reader = BufReader::new(stderr);
-                            for line in stderr_reader.lines() {
-                                if let Ok(line) = line {
+                            lines
+                        });
+                        let stderr_target = stream_target.clone();
+                        let stderr_callback = line_callback.clone();
+                        let stderr_thread = thread::spawn(move || {
+                            let mut lines = Vec::new();
+                            if let Some(stderr) = stderr {
+                                for line in BufReader::new(stderr).lines().flatten() {
                                     debug!("[STDERR] {}", line);
-                                    stderr_lines.push(line);
+                                    if let Some(target) = &stderr_target {
+                                        emit_command_line(target, StreamSource::Stderr, &line);
+                                    }
+                                    if let Some(callback) = &stderr_callback {
+                                        callback(StreamSource::Stderr, &line);
+                                    }
+                                    lines.push(line);
                                 }
                             }
-                        }
-                        
+                            lines
+                        });
+
+                        let stdout_lines = stdout_thread.join().unwrap_or_default();
+                        let stderr_lines = stderr_thread.join().unwrap_or_default();
+
                         // Wait for process to complete
-                        match child.wait() {
+                        let outcome = match child.wait() {
                             Ok(status) => {
                                 let exit_code = status.code().unwrap_or(-1);
+                                let signal = exit_signal(&status);
                                 let success = status.success();
-                                
-                                CommandResult {
+
+                                Ok(CommandResult {
                                     success,
                                     stdout: stdout_lines.join("\n"),
                                     stderr: stderr_lines.join("\n"),
                                     exit_code,
-                                }
+                                    signal,
+                                    duration_ms: start.elapsed().as_millis() as u64,
+                                    pid: Some(pid),
+                                })
                             },
-                            Err(e) => {
-                                CommandResult {
-                                    success: false,
-                                    stdout: stdout_lines.join("\n"),
-                                    stderr: format!("Failed to wait for command: {}", e),
-                                    exit_code: -1,
-                                }
-                            }
+                            Err(e) => Err(ProcessError::Wait {
+                                context: attempt_context,
+                                message: e.to_string(),
+                            }),
+                        };
+                        if let Some(reporter) = &pid_reporter {
+                            *reporter.lock().unwrap() = None;
                         }
+                        outcome
                     },
                     Err(e) => {
-                        CommandResult {
-                            success: false,
-                            stdout: String::new(),
-                            stderr: format!("Failed to execute command: {}", e),
-                            exit_code: -1,
-                        }
+                        let _ = pid_tx.send(None);
+                        Err(ProcessError::Spawn { context: attempt_context, message: e.to_string() })
                     }
                 }
             });
-            
-            let result = match timeout(Duration::from_secs(options.timeout), cmd_future).await {
-                Ok(Ok(result)) => result,
-                Ok(Err(e)) => {
-                    return Err(format!("Failed to execute command: {}", e));
-                },
+
+            let spawn_result = match timeout(Duration::from_secs(options.timeout), cmd_future).await {
+                Ok(Ok(inner)) => inner,
+                Ok(Err(e)) => Err(ProcessError::Spawn {
+                    context: context.clone(),
+                    message: format!("blocking task panicked: {}", e),
+                }),
                 Err(_) => {
-                    return Err(format!("Command timed out after {} seconds", options.timeout));
+                    // The blocking task keeps running even though we're
+                    // abandoning this future -- kill the child (and
+                    // everything it spawned) by PID instead of leaving it
+                    // and its subprocesses running in the background.
+                    if let Ok(Some(pid)) = pid_rx.try_recv() {
+                        kill_process_tree(pid);
+                    }
+                    Err(ProcessError::TimedOut { context: context.clone(), timeout_secs: options.timeout })
+                }
+            };
+
+            // A spawn or wait failure is retried the same way a failed
+            // exit is, below -- only the final attempt's error is surfaced.
+            // A timeout gets its own backoff (`timeout_retry_delay`) rather
+            // than `retry_delay`, since a hung command is a different
+            // failure mode than a clean non-zero exit.
+            let result = match spawn_result {
+                Ok(result) => result,
+                Err(e) if attempt == self.options.max_retries => return Err(e),
+                Err(e @ ProcessError::TimedOut { .. }) => {
+                    warn!("Command timed out ({}), retrying (attempt {}/{})", e, attempt, self.options.max_retries);
+                    sleep(Duration::from_secs(self.options.timeout_retry_delay.into())).await;
+                    continue;
+                }
+                Err(e) => {
+                    warn!("Command failed ({}), retrying (attempt {}/{})", e, attempt, self.options.max_retries);
+                    sleep(Duration::from_secs(self.options.retry_delay.into())).await;
+                    continue;
                 }
             };
             
@@ -285,44 +766,39 @@ impl CommandBuilder {
                     info!("Project generator command completed, waiting for filesystem to settle...");
                     sleep(Duration::from_secs(3)).await;
                     
-                    // If we have a project name to verify, check that it exists
-                    if let Some(project_name) = &project_name {
-                        let project_dir = working_dir.join(project_name);
-                        info!("Verifying project directory exists: {}", project_dir.display());
-                        
-                        // Try multiple times with increasing delays
-                        let mut dir_exists = false;
-                        for i in 0..5 {
-                            if project_dir.exists() && project_dir.is_dir() {
-                                dir_exists = true;
-                                info!("Project directory verified!");
-                                break;
-                            }
-                            warn!("Directory not found, waiting (attempt {}/5)...", i+1);
-                            thread_sleep(StdDuration::from_millis(500 * (i+1)));
-                        }
-                        
-                        if !dir_exists {
-                            // If we've done max retries, fail, otherwise retry the command
-                            if attempt == self.options.max_retries {
-                                return Err(format!("Project directory {} was not created even though command reported success", project_dir.display()));
-                            } else {
-                                warn!("Retrying command due to missing project directory (attempt {}/{})", attempt, self.options.max_retries);
-                                sleep(Duration::from_secs(1)).await;
-                                continue;
-                            }
+                    // Detect whichever newly-created subdirectory looks like
+                    // the generator's output, rather than assuming it's
+                    // named after project_name -- create-vite/npm create and
+                    // similar scaffolders don't always honor that.
+                    info!("Verifying project was created under {}", working_dir.display());
+
+                    let mut detected_origin = None;
+                    for i in 0..5 {
+                        if let Some(origin) = detect_project_origin(&working_dir, &pre_existing_dirs) {
+                            info!("Project origin detected: {}", origin.display());
+                            detected_origin = Some(origin);
+                            break;
                         }
-                        
-                        // If project exists, check for package.json
-                        let package_json = project_dir.join("package.json");
-                        if !package_json.exists() {
-                            warn!("Warning: package.json not found in project directory");
+                        warn!("No project origin found yet, waiting (attempt {}/5)...", i + 1);
+                        thread_sleep(StdDuration::from_millis(500 * (i + 1)));
+                    }
+
+                    if detected_origin.is_none() {
+                        // If we've done max retries, fail, otherwise retry the command
+                        if attempt == self.options.max_retries {
+                            return Err(ProcessError::VerificationFailed {
+                                context: context.clone(),
+                                message: format!(
+                                    "no project directory (marked by one of {:?}) was created under {}",
+                                    PROJECT_MARKERS,
+                                    working_dir.display()
+                                ),
+                            });
                         } else {
-                            info!("package.json verified!");
+                            warn!("Retrying command due to missing project origin (attempt {}/{})", attempt, self.options.max_retries);
+                            sleep(Duration::from_secs(1)).await;
+                            continue;
                         }
-                    } else {
-                        // No project name to verify, use a standard delay
-                        sleep(Duration::from_secs(2)).await;
                     }
                 } else {
                     // Standard delay for other npm/npx commands
@@ -330,21 +806,313 @@ impl CommandBuilder {
                 }
             }
             
-            // If successful or final attempt, return the result
-            if result.success || attempt == self.options.max_retries {
+            // If successful, return the result; if this was the final
+            // attempt, surface the exit status/signal instead.
+            if result.success {
                 return Ok(result);
+            } else if attempt == self.options.max_retries {
+                return Err(ProcessError::Exit {
+                    context,
+                    code: if result.signal.is_some() { None } else { Some(result.exit_code) },
+                    signal: result.signal,
+                });
             } else {
                 // If failed but we have retries left
                 warn!("Command failed, retrying (attempt {}/{})", attempt, self.options.max_retries);
                 sleep(Duration::from_secs(self.options.retry_delay.into())).await;
             }
         }
-        
+
         // We should never reach here (loop always returns), but satisfy the compiler
-        Err("Command execution failed after all retries".to_string())
+        Err(ProcessError::Exit { context, code: None, signal: None })
+    }
+
+    /// PTY-backed execution path used when `.interactive_answer` has been
+    /// set: allocates a real pseudo-terminal the way `ProcessRunner` does
+    /// for framework scaffolders, then drives it with a loop that scans
+    /// incoming output for each configured prompt regex and writes the
+    /// matching canned reply back to the master as soon as it appears --
+    /// the way coreutils' test harness answers TTY-dependent programs via
+    /// `openpty`.
+    async fn run_interactive(&self, platform_cmd: &str) -> Result<CommandResult, ProcessError> {
+        use pty_process::{Command as PtyCommand, Pty, Size};
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let context = ProcessContext::new(platform_cmd, &self.args, &self.working_dir);
+        let start = StdInstant::now();
+
+        let pty = Pty::new().map_err(|e| ProcessError::Spawn {
+            context: context.clone(),
+            message: format!("failed to allocate pseudo-terminal: {}", e),
+        })?;
+        pty.resize(Size::new(24, 80)).map_err(|e| ProcessError::Spawn {
+            context: context.clone(),
+            message: format!("failed to size pseudo-terminal: {}", e),
+        })?;
+        let pts = pty.pts().map_err(|e| ProcessError::Spawn {
+            context: context.clone(),
+            message: format!("failed to open pseudo-terminal slave: {}", e),
+        })?;
+
+        let mut cmd = PtyCommand::new(platform_cmd);
+        cmd.args(&self.args).current_dir(&self.working_dir).kill_on_drop(true);
+        if let Ok(path) = std::env::var("PATH") {
+            cmd.env("PATH", path);
+        }
+        for (key, value) in &self.options.env_vars {
+            cmd.env(key, value);
+        }
+        if self.command == "npm" || self.command == "npx" {
+            cmd.env("CI", "false");
+            cmd.env("NODE_ENV", "development");
+        }
+
+        let mut child = cmd.spawn(&pts).map_err(|e| ProcessError::Spawn {
+            context: context.clone(),
+            message: e.to_string(),
+        })?;
+        let pid = child.id();
+        let (mut reader, mut writer) = tokio::io::split(pty);
+
+        let mut output = String::new();
+        let mut scanned_up_to = 0usize;
+        let mut buf = [0u8; 4096];
+
+        loop {
+            match reader.read(&mut buf).await {
+                Ok(0) | Err(_) => break,
+                Ok(n) => {
+                    output.push_str(&String::from_utf8_lossy(&buf[..n]));
+
+                    // Rescan from a little before the last scan point, so a
+                    // prompt split across two reads still matches once its
+                    // second half arrives. Rounded down to a char boundary,
+                    // since the lossy UTF-8 decode above can otherwise land
+                    // this mid-character.
+                    let scan_from = floor_char_boundary(&output, scanned_up_to.saturating_sub(256));
+                    for (pattern, reply) in &self.interactive_answers {
+                        if let Some(m) = pattern.find(&output[scan_from..]) {
+                            if scan_from + m.end() > scanned_up_to {
+                                debug!("Interactive prompt matched {:?}, replying {:?}", pattern.as_str(), reply);
+                                let line = format!("{}\n", reply);
+                                if let Err(e) = writer.write_all(line.as_bytes()).await {
+                                    warn!("Failed to write interactive reply: {}", e);
+                                }
+                            }
+                        }
+                    }
+                    scanned_up_to = output.len();
+                }
+            }
+        }
+
+        let status = child.wait().await.map_err(|e| ProcessError::Wait {
+            context: context.clone(),
+            message: e.to_string(),
+        })?;
+        Ok(CommandResult {
+            success: status.success(),
+            stdout: output,
+            stderr: String::new(),
+            exit_code: status.code().unwrap_or(-1),
+            signal: exit_signal(&status),
+            duration_ms: start.elapsed().as_millis() as u64,
+            pid,
+        })
+    }
+}
+
+/// A boxed, already-invoked future returning the same shape `execute`
+/// does -- lets `WatchMode` treat a `CommandBuilder` and an arbitrary
+/// generation closure uniformly.
+type WatchFuture = std::pin::Pin<Box<dyn std::future::Future<Output = Result<CommandResult, ProcessError>> + Send>>;
+
+/// What `WatchMode` re-runs on every detected change.
+enum WatchAction {
+    Command(CommandBuilder),
+    Closure(Arc<dyn Fn() -> WatchFuture + Send + Sync>),
+}
+
+impl WatchAction {
+    /// Attach `reporter` to the command case so the run loop can kill it
+    /// on the next change; a closure reports no PID, so nothing to kill.
+    fn with_pid_reporter(&self, reporter: Arc<std::sync::Mutex<Option<u32>>>) -> Self {
+        match self {
+            WatchAction::Command(builder) => WatchAction::Command(builder.clone().report_pid_to(reporter)),
+            WatchAction::Closure(f) => WatchAction::Closure(f.clone()),
+        }
+    }
+
+    fn run(self) -> WatchFuture {
+        match self {
+            WatchAction::Command(builder) => Box::pin(builder.execute()),
+            WatchAction::Closure(f) => f(),
+        }
     }
 }
 
+/// How long to wait after the last observed change before re-running --
+/// see `watcher::DEBOUNCE_MS` for the same tradeoff (bulk edits collapsing
+/// into one run vs. still feeling live).
+const WATCH_DEBOUNCE_MS: u64 = 300;
+
+/// Re-runs a command (or an arbitrary generation closure) whenever one of
+/// a fixed set of watched paths changes, modeled on Deno's `--watch` loop:
+/// run once immediately, then on every debounced batch of filesystem
+/// changes, kill whatever's still running and start over. Watched paths
+/// are resolved against `root` -- captured once up front rather than
+/// re-read from the environment on every iteration -- so a step that does
+/// its own `chdir` doesn't shift what later change events are measured
+/// against (the bug Deno fixed by threading its initial cwd through
+/// explicitly instead of re-querying it).
+pub struct WatchMode {
+    root: PathBuf,
+    watch_paths: Vec<PathBuf>,
+    action: WatchAction,
+    app_handle: AppHandle,
+    step_name: String,
+}
+
+impl WatchMode {
+    /// Re-run `command` (cloned fresh each run) on every change.
+    pub fn for_command(
+        root: impl Into<PathBuf>,
+        command: CommandBuilder,
+        app_handle: AppHandle,
+        step_name: impl Into<String>,
+    ) -> Self {
+        Self {
+            root: root.into(),
+            watch_paths: Vec::new(),
+            action: WatchAction::Command(command),
+            app_handle,
+            step_name: step_name.into(),
+        }
+    }
+
+    /// Re-run an arbitrary generation closure (e.g. a task's own step
+    /// logic) on every change, instead of a plain command.
+    pub fn for_closure<F, Fut>(
+        root: impl Into<PathBuf>,
+        app_handle: AppHandle,
+        step_name: impl Into<String>,
+        closure: F,
+    ) -> Self
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: std::future::Future<Output = Result<CommandResult, ProcessError>> + Send + 'static,
+    {
+        Self {
+            root: root.into(),
+            watch_paths: Vec::new(),
+            action: WatchAction::Closure(Arc::new(move || Box::pin(closure()) as WatchFuture)),
+            app_handle,
+            step_name: step_name.into(),
+        }
+    }
+
+    /// Paths to watch, resolved against `root`. Each is watched
+    /// non-recursively -- pass the specific template/config files or
+    /// directories that should trigger a re-run, not the whole project.
+    pub fn watch_paths<I, P>(mut self, paths: I) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: Into<PathBuf>,
+    {
+        self.watch_paths = paths.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Run the watch loop until `stop` is notified, emitting
+    /// `generation-progress` events ("watching", "change detected",
+    /// "re-running") so the frontend reflects the live loop.
+    pub async fn run(self, stop: Arc<Notify>) -> Result<(), String> {
+        let WatchMode { root, watch_paths, action, app_handle, step_name } = self;
+
+        let (raw_tx, mut raw_rx) = mpsc::unbounded_channel::<notify::Event>();
+        let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| match res {
+            Ok(event) => {
+                let _ = raw_tx.send(event);
+            }
+            Err(e) => warn!("Watch mode filesystem watch error: {}", e),
+        })
+        .map_err(|e| format!("Failed to create watch-mode filesystem watcher: {}", e))?;
+
+        let mut watched_any = false;
+        for relative in &watch_paths {
+            let absolute = root.join(relative);
+            match watcher.watch(&absolute, RecursiveMode::NonRecursive) {
+                Ok(()) => watched_any = true,
+                Err(e) => warn!("Watch mode could not watch {}: {}", absolute.display(), e),
+            }
+        }
+        if !watched_any {
+            return Err("Watch mode has no watchable paths".to_string());
+        }
+
+        emit_progress(&app_handle, &step_name, "Watching for changes...", 0.0);
+
+        let pid_reporter = Arc::new(std::sync::Mutex::new(None::<u32>));
+        let mut current_run = spawn_watch_action(&action, &pid_reporter, app_handle.clone(), step_name.clone());
+        let mut deadline: Option<Instant> = None;
+
+        loop {
+            let debounce = async {
+                match deadline {
+                    Some(d) => sleep_until(d).await,
+                    None => std::future::pending::<()>().await,
+                }
+            };
+
+            tokio::select! {
+                _ = stop.notified() => {
+                    current_run.abort();
+                    if let Some(pid) = pid_reporter.lock().unwrap().take() {
+                        kill_process_tree(pid);
+                    }
+                    break;
+                }
+                event = raw_rx.recv() => {
+                    match event {
+                        Some(_) => deadline = Some(Instant::now() + Duration::from_millis(WATCH_DEBOUNCE_MS)),
+                        // The watcher (and its sender) dropped.
+                        None => break,
+                    }
+                }
+                _ = debounce => {
+                    deadline = None;
+                    emit_progress(&app_handle, &step_name, "Change detected, re-running...", 0.0);
+                    current_run.abort();
+                    if let Some(pid) = pid_reporter.lock().unwrap().take() {
+                        kill_process_tree(pid);
+                    }
+                    current_run = spawn_watch_action(&action, &pid_reporter, app_handle.clone(), step_name.clone());
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn spawn_watch_action(
+    action: &WatchAction,
+    pid_reporter: &Arc<std::sync::Mutex<Option<u32>>>,
+    app_handle: AppHandle,
+    step_name: String,
+) -> tauri::async_runtime::JoinHandle<()> {
+    let action = action.with_pid_reporter(pid_reporter.clone());
+    tauri::async_runtime::spawn(async move {
+        match action.run().await {
+            Ok(_) => info!("Watch mode step '{}' completed", step_name),
+            Err(e) => {
+                warn!("Watch mode step '{}' failed: {}", step_name, e);
+                emit_progress(&app_handle, &step_name, &format!("Failed: {}", e), 0.0);
+            }
+        }
+    })
+}
+
 /// Verify that a file exists, with retries
 pub async fn verify_file_exists(path: &Path, retries: u32, delay_ms: u64) -> bool {
     for i in 0..retries {
@@ -367,7 +1135,7 @@ pub async fn run_command(
     command: &str,
     args: &[&str],
     working_dir: &Path,
-) -> Result<CommandResult, String> {
+) -> Result<CommandResult, ProcessError> {
     let args_owned: Vec<String> = args.iter().map(|s| s.to_string()).collect();
     
     CommandBuilder::new(command)
@@ -383,13 +1151,14 @@ pub async fn run_command_with_options(
     args: &[&str],
     working_dir: &Path,
     options: CommandOptions,
-) -> Result<CommandResult, String> {
+) -> Result<CommandResult, ProcessError> {
     let args_owned: Vec<String> = args.iter().map(|s| s.to_string()).collect();
     let mut builder = CommandBuilder::new(command)
         .args(args_owned)
         .working_dir(working_dir)
         .retries(options.max_retries)
         .retry_delay(options.retry_delay)
+        .timeout_retry_delay(options.timeout_retry_delay)
         .timeout(options.timeout)
         .verify_output(options.verify_output)
         .verify_project_dir(options.verify_project_dir);
@@ -401,103 +1170,109 @@ pub async fn run_command_with_options(
     builder.execute().await
 }
 
-/// Runs an interactive command asynchronously with the given arguments and working directory
+/// Runs an interactive command asynchronously, printing each stdout/stderr
+/// line live as it's produced, instead of polling each pipe in turn with a
+/// blocking `read()` -- a command that fills one pipe's buffer while this
+/// loop sits blocked waiting for data on the other would otherwise deadlock,
+/// the same hazard `CommandBuilder::execute` drains on separate threads to
+/// avoid.
+///
+/// `on_line`, if given, is called with every `(StreamSource, line)` pair as
+/// it arrives, so a caller can subscribe to the live stream instead of only
+/// seeing output printed after the fact.
 pub async fn run_interactive_command(
-    command: &str, 
-    args: &[&str], 
+    command: &str,
+    args: &[&str],
     working_dir: &Path,
-    env_vars: Option<Vec<(String, String)>>
-) -> Result<(), String> {
+    env_vars: Option<Vec<(String, String)>>,
+    on_line: Option<impl Fn(StreamSource, &str) + Send + 'static>,
+) -> Result<(), ProcessError> {
     println!("Running interactive command: {} {:?} in {}", command, args, working_dir.display());
-    
+
+    let context = ProcessContext::new(
+        command,
+        &args.iter().map(|s| s.to_string()).collect::<Vec<_>>(),
+        working_dir,
+    );
+
     // Create the command
     let mut cmd = Command::new(command);
     cmd.args(args)
        .current_dir(working_dir)
        .stdout(Stdio::piped())
        .stderr(Stdio::piped());
-    
+
     // Add environment variables if provided
     if let Some(vars) = env_vars {
         for (key, value) in vars {
             cmd.env(key, value);
         }
     }
-    
-    match cmd.spawn() {
-        Ok(mut child) => {
-            let stdout = child.stdout.take().expect("Failed to capture stdout");
-            let stderr = child.stderr.take().expect("Failed to capture stderr");
-            
-            let mut reader = std::io::BufReader::new(stdout);
-            let mut err_reader = std::io::BufReader::new(stderr);
-            
-            let mut buffer = [0; 1024];
-            let mut err_buffer = [0; 1024];
-            
-            loop {
-                // Check if child process has exited
-                match child.try_wait() {
-                    Ok(Some(status)) => {
-                        if !status.success() {
-                            let error_msg = format!("Command exited with non-zero status: {}", status);
-                            println!("{}", error_msg);
-                            return Err(error_msg);
-                        }
-                        break;
-                    },
-                    Ok(None) => {}, // Child still running
-                    Err(e) => {
-                        let error_msg = format!("Error checking child process status: {}", e);
-                        println!("{}", error_msg);
-                        return Err(error_msg);
-                    }
-                }
-                
-                // Read from stdout
-                if let Ok(n) = reader.read(&mut buffer) {
-                    if n > 0 {
-                        let output = String::from_utf8_lossy(&buffer[..n]).to_string();
-                        println!("{}", output);
-                    }
-                }
-                
-                // Read from stderr
-                if let Ok(n) = err_reader.read(&mut err_buffer) {
-                    if n > 0 {
-                        let output = String::from_utf8_lossy(&err_buffer[..n]).to_string();
-                        println!("{}", output);
-                    }
-                }
-                
-                // Small sleep to prevent tight CPU loops
-                std::thread::sleep(std::time::Duration::from_millis(10));
+
+    let mut child = match cmd.spawn() {
+        Ok(child) => child,
+        Err(e) => {
+            println!("Failed to spawn command: {}", e);
+            return Err(ProcessError::Spawn { context, message: e.to_string() });
+        }
+    };
+
+    let stdout = child.stdout.take().expect("Failed to capture stdout");
+    let stderr = child.stderr.take().expect("Failed to capture stderr");
+
+    // Read stdout and stderr on their own threads, both feeding the same
+    // channel, instead of reading one pipe to completion before the other
+    // is ever touched.
+    let (tx, rx) = std::sync::mpsc::channel::<(StreamSource, String)>();
+
+    let stdout_tx = tx.clone();
+    let stdout_thread = thread::spawn(move || {
+        for line in BufReader::new(stdout).lines().flatten() {
+            if stdout_tx.send((StreamSource::Stdout, line)).is_err() {
+                break;
             }
-            
-            // Wait for the child process to finish if it hasn't already
-            match child.wait() {
-                Ok(status) => {
-                    if status.success() {
-                        let success_msg = "Command completed successfully";
-                        println!("{}", success_msg);
-                        Ok(())
-                    } else {
-                        let error_msg = format!("Command exited with non-zero status: {}", status);
-                        println!("{}", error_msg);
-                        Err(error_msg)
-                    }
-                },
-                Err(e) => {
-                    let error_msg = format!("Failed to wait for command: {}", e);
-                    println!("{}", error_msg);
-                    Err(error_msg)
-                },
+        }
+    });
+    let stderr_thread = thread::spawn(move || {
+        for line in BufReader::new(stderr).lines().flatten() {
+            if tx.send((StreamSource::Stderr, line)).is_err() {
+                break;
+            }
+        }
+    });
+
+    // The channel closes once both reader threads have dropped their
+    // senders, which happens when both pipes hit EOF -- draining it here
+    // reports each line the moment it's produced rather than after the
+    // whole command finishes.
+    let reporter = crate::progress::default_line_reporter(None);
+    for (source, line) in rx.iter() {
+        reporter.report_line(source, &line);
+        if let Some(callback) = &on_line {
+            callback(source, &line);
+        }
+    }
+
+    stdout_thread.join().ok();
+    stderr_thread.join().ok();
+
+    match child.wait() {
+        Ok(status) => {
+            if status.success() {
+                println!("Command completed successfully");
+                Ok(())
+            } else {
+                println!("Command exited with non-zero status: {}", status);
+                Err(ProcessError::Exit {
+                    context,
+                    code: status.code(),
+                    signal: exit_signal(&status),
+                })
             }
         },
         Err(e) => {
-            let error_msg = format!("Failed to spawn command: {}", e);
-            println!("{}", error_msg);
-            Err(error_msg)
+            println!("Failed to wait for command: {}", e);
+            Err(ProcessError::Wait { context, message: e.to_string() })
         },
     }
 }
@@ -583,7 +1358,22 @@ pub fn modify_import(path: &Path, action: &str, import: &str) -> Result<(), Stri
     if !path.exists() {
         return Err(format!("File not found: {}", path.display()));
     }
-    
+
+    // For TS/JS files, prefer editing the real import declarations over
+    // the line-regex approach below, which breaks on multi-line imports,
+    // imports inside comments/strings, `import type`, and can't merge a
+    // specifier into an existing declaration from the same module.
+    if crate::commands::ast_import::supports(path) {
+        if let Some((module, specifiers)) = parse_import_clause(import) {
+            return crate::commands::ast_import::modify_import_ast(path, action, &module, &specifiers);
+        }
+        warn!(
+            "Could not parse import clause '{}' for AST-based editing of '{}', falling back to text editing",
+            import,
+            path.display()
+        );
+    }
+
     let content = fs::read_to_string(path)
         .map_err(|e| format!("Failed to read file '{}': {}", path.display(), e))?;
     
@@ -615,11 +1405,34 @@ pub fn modify_import(path: &Path, action: &str, import: &str) -> Result<(), Stri
         },
         _ => return Err(format!("Unknown import action: {}", action)),
     }
-    
+
     fs::write(path, new_content)
         .map_err(|e| format!("Failed to write to file '{}': {}", path.display(), e))
 }
 
+/// Splits a raw import clause like `"{ useState, useEffect } from 'react'"`
+/// into its module specifier and named specifiers, for handing off to
+/// [`crate::commands::ast_import::modify_import_ast`]. Returns `None` if
+/// `import` doesn't contain a `from '...'`/`from "..."` clause.
+fn parse_import_clause(import: &str) -> Option<(String, Vec<String>)> {
+    let from_idx = import.rfind("from")?;
+    let module = import[from_idx + "from".len()..]
+        .trim()
+        .trim_matches(|c| c == '\'' || c == '"' || c == ';')
+        .to_string();
+
+    let specifiers = match (import.find('{'), import.find('}')) {
+        (Some(start), Some(end)) if start < end => import[start + 1..end]
+            .split(',')
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+            .collect(),
+        _ => Vec::new(),
+    };
+
+    Some((module, specifiers))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
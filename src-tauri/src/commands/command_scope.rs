@@ -0,0 +1,128 @@
+//! Configurable command scope
+//!
+//! Replaces the blunt allow/deny substring checks in `node_commands` with a
+//! positive allowlist modeled on Tauri's shell scope: each program maps to an
+//! ordered list of argument specifications, where each entry is either a
+//! literal token or a named pattern backed by a compiled regex. A command is
+//! only permitted if every one of its tokens matches an entry in its
+//! program's scope, so there is no shell metacharacter blocklist to bypass —
+//! nothing unmatched is ever allowed through, and no shell is ever invoked.
+
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+/// A single permitted argument shape for a program
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ArgSpec {
+    /// Matches this exact token
+    Literal(String),
+    /// Matches any token satisfying this regex (e.g. a script name pattern)
+    Pattern(String),
+}
+
+impl ArgSpec {
+    fn matches(&self, token: &str) -> bool {
+        match self {
+            ArgSpec::Literal(expected) => token == expected,
+            ArgSpec::Pattern(pattern) => Regex::new(pattern)
+                .map(|re| re.is_match(token))
+                .unwrap_or(false),
+        }
+    }
+}
+
+/// The set of argument shapes permitted for one program
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProgramScope {
+    pub program: String,
+    pub allowed_args: Vec<ArgSpec>,
+}
+
+/// Maps program name -> its allowed argument specifications
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CommandScope {
+    programs: HashMap<String, ProgramScope>,
+}
+
+impl CommandScope {
+    /// The built-in scope matching the previous npm/npx/yarn/pnpm/node allowlist
+    pub fn default_scope() -> Self {
+        let mut scope = Self::default();
+
+        for program in ["npm", "npx", "yarn", "pnpm", "node"] {
+            scope.register(ProgramScope {
+                program: program.to_string(),
+                allowed_args: vec![
+                    // Any subcommand/script/flag token is permitted by default;
+                    // callers tighten this with `register` for specific projects.
+                    ArgSpec::Pattern(r"^[A-Za-z0-9_.@:/=\-]+$".to_string()),
+                ],
+            });
+        }
+
+        scope
+    }
+
+    /// Register (or replace) the allowed arguments for a program
+    pub fn register(&mut self, program_scope: ProgramScope) {
+        self.programs.insert(program_scope.program.clone(), program_scope);
+    }
+
+    /// Tokenize and validate a full command line (e.g. "npm run build") against
+    /// the scope. Never invokes a shell, so shell metacharacters are moot —
+    /// they simply won't match any token pattern and the command is rejected.
+    pub fn validate(&self, command: &str) -> Result<(), String> {
+        let tokens: Vec<&str> = command.split_whitespace().collect();
+        let program = tokens
+            .first()
+            .ok_or_else(|| "Command cannot be empty".to_string())?;
+
+        let program_scope = self.programs.get(*program).ok_or_else(|| {
+            format!(
+                "Command not allowed: {}. Program '{}' is not in the allowed command scope.",
+                command, program
+            )
+        })?;
+
+        for token in &tokens[1..] {
+            let allowed = program_scope
+                .allowed_args
+                .iter()
+                .any(|spec| spec.matches(token));
+            if !allowed {
+                return Err(format!(
+                    "Argument '{}' is not permitted for program '{}' by the active command scope",
+                    token, program
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Process-wide active command scope, mutable at runtime via `register_scope_rule`
+static ACTIVE_SCOPE: Lazy<RwLock<CommandScope>> = Lazy::new(|| RwLock::new(CommandScope::default_scope()));
+
+/// Validate a command against the active scope
+pub fn validate_command_security(command: &str) -> Result<(), String> {
+    ACTIVE_SCOPE.read().unwrap().validate(command)
+}
+
+/// Query the currently active command scope
+#[tauri::command]
+pub async fn get_command_scope() -> Result<CommandScope, String> {
+    Ok(ACTIVE_SCOPE.read().unwrap().clone())
+}
+
+/// Register (or replace) the allowed arguments for a program at runtime. A
+/// program already in the scope has its argument list replaced, not extended.
+#[tauri::command]
+pub async fn register_scope_rule(program_scope: ProgramScope) -> Result<(), String> {
+    ACTIVE_SCOPE.write().unwrap().register(program_scope);
+    Ok(())
+}
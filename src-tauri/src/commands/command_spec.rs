@@ -0,0 +1,225 @@
+//! Structured command specification for module installation steps.
+//!
+//! Replaces raw shell strings in `ModuleInstallation::commands` (and the
+//! substring sniffing that used to guess criticality, e.g.
+//! `cmd.contains("npm install")`) with an explicit, typed description of what
+//! a command is, whether it's allowed to fail, and where it should run.
+
+use std::collections::HashMap;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Package manager a `CommandSpec` is invoked through.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum PackageManager {
+    Npm,
+    Npx,
+    Pnpm,
+    Yarn,
+    Bun,
+    /// Not a package-manager invocation at all (e.g. a raw shell command).
+    None,
+}
+
+impl PackageManager {
+    fn program(&self) -> &'static str {
+        match self {
+            PackageManager::Npm => "npm",
+            PackageManager::Npx => "npx",
+            PackageManager::Pnpm => "pnpm",
+            PackageManager::Yarn => "yarn",
+            PackageManager::Bun => "bun",
+            PackageManager::None => "",
+        }
+    }
+}
+
+/// A single, structured install/setup command.
+///
+/// Build one with `CommandSpec::builder(...)` rather than constructing the
+/// struct directly, mirroring the fluent builders already used elsewhere in
+/// this crate (e.g. `CommandBuilder` in `command_runner`).
+#[derive(Serialize, Deserialize, Debug, Clone, JsonSchema)]
+pub struct CommandSpec {
+    pub package_manager: PackageManager,
+    pub subcommand: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// Directory the command runs in, relative to the project root. `None`
+    /// means the project root itself.
+    #[serde(default)]
+    pub cwd_relative: Option<String>,
+    /// Extra environment variables for this command only.
+    #[serde(default)]
+    pub env: HashMap<String, String>,
+    /// Whether a failure here should abort the module install instead of
+    /// just being logged and skipped.
+    #[serde(default)]
+    pub critical: bool,
+    /// Whether a failure here is expected/acceptable (e.g. an idempotent
+    /// check that may legitimately return non-zero).
+    #[serde(default)]
+    pub allow_failure: bool,
+    /// Per-OS override of `subcommand`/`args`, keyed by `std::env::consts::OS`
+    /// (`"windows"`, `"macos"`, `"linux"`). Use this when a command needs a
+    /// different invocation on a given platform (e.g. a different shell
+    /// script name) instead of breaking there.
+    #[serde(default)]
+    pub platform_commands: HashMap<String, Vec<String>>,
+}
+
+impl CommandSpec {
+    /// Start building a command for the given package manager and subcommand.
+    pub fn builder(package_manager: PackageManager, subcommand: impl Into<String>) -> CommandSpecBuilder {
+        CommandSpecBuilder::new(package_manager, subcommand)
+    }
+
+    /// Render this spec as the shell command string `execute_node_command` expects.
+    pub fn to_command_string(&self) -> String {
+        let mut parts = Vec::new();
+        let program = self.package_manager.program();
+        if !program.is_empty() {
+            parts.push(program.to_string());
+        }
+        parts.extend(self.resolved_args());
+        parts.join(" ")
+    }
+
+    /// `subcommand` + `args` to actually run on this OS: the
+    /// `platform_commands` entry for `std::env::consts::OS`, if present,
+    /// otherwise the flat `subcommand`/`args`.
+    fn resolved_args(&self) -> Vec<String> {
+        match self.platform_commands.get(std::env::consts::OS) {
+            Some(platform_args) => platform_args.clone(),
+            None => {
+                let mut args = vec![self.subcommand.clone()];
+                args.extend(self.args.iter().cloned());
+                args
+            }
+        }
+    }
+
+    /// Package names this command would install, if it's an install-style
+    /// subcommand (`npm install`, `yarn add`, `pnpm add`, ...). Empty for
+    /// non-install commands. Used to skip already-satisfied installs.
+    pub fn requested_packages(&self) -> Vec<String> {
+        let is_install_subcommand = matches!(self.subcommand.as_str(), "install" | "i" | "add");
+        if !is_install_subcommand || matches!(self.package_manager, PackageManager::None) {
+            return Vec::new();
+        }
+
+        self.args
+            .iter()
+            .filter(|arg| !arg.starts_with('-'))
+            .map(|spec| Self::package_name(spec))
+            .collect()
+    }
+
+    /// True if `args` contains a recognized "save as dev dependency" flag
+    /// for any package manager. Used to carry that intent across backends
+    /// when `to_command_string_for` translates the command.
+    fn wants_dev_dependency(&self) -> bool {
+        self.args.iter().any(|a| matches!(a.as_str(), "-D" | "--save-dev" | "--dev" | "-d"))
+    }
+
+    /// Render this command for a specific package-manager backend,
+    /// translating install/add intent (requested packages + dev flag) into
+    /// that backend's own syntax so a module written against one manager
+    /// still works when the project uses another. Non-install commands
+    /// (e.g. `npx <tool>`) render unchanged.
+    pub fn to_command_string_for(&self, backend: &dyn crate::commands::package_manager::PackageManagerBackend) -> String {
+        let packages = self.requested_packages();
+        if packages.is_empty() {
+            return self.to_command_string();
+        }
+
+        let (program, args) = backend.add_cmd(&packages, self.wants_dev_dependency());
+        let mut parts = vec![program];
+        parts.extend(args);
+        parts.join(" ")
+    }
+
+    /// Strip a version specifier (`pkg@1.2.3`) from a package arg, keeping
+    /// the leading `@` of a scoped package name (`@scope/pkg@1.2.3`).
+    fn package_name(spec: &str) -> String {
+        if let Some(rest) = spec.strip_prefix('@') {
+            match rest.find('@') {
+                Some(at) => format!("@{}", &rest[..at]),
+                None => spec.to_string(),
+            }
+        } else {
+            match spec.find('@') {
+                Some(at) => spec[..at].to_string(),
+                None => spec.to_string(),
+            }
+        }
+    }
+}
+
+/// Fluent builder for `CommandSpec`.
+pub struct CommandSpecBuilder {
+    spec: CommandSpec,
+}
+
+impl CommandSpecBuilder {
+    fn new(package_manager: PackageManager, subcommand: impl Into<String>) -> Self {
+        Self {
+            spec: CommandSpec {
+                package_manager,
+                subcommand: subcommand.into(),
+                args: Vec::new(),
+                cwd_relative: None,
+                env: HashMap::new(),
+                critical: false,
+                allow_failure: true,
+                platform_commands: HashMap::new(),
+            },
+        }
+    }
+
+    pub fn args<I, S>(mut self, args: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.spec.args = args.into_iter().map(Into::into).collect();
+        self
+    }
+
+    pub fn cwd_relative(mut self, dir: impl Into<String>) -> Self {
+        self.spec.cwd_relative = Some(dir.into());
+        self
+    }
+
+    pub fn env(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.spec.env.insert(key.into(), value.into());
+        self
+    }
+
+    pub fn critical(mut self, critical: bool) -> Self {
+        self.spec.critical = critical;
+        self
+    }
+
+    pub fn allow_failure(mut self, allow_failure: bool) -> Self {
+        self.spec.allow_failure = allow_failure;
+        self
+    }
+
+    /// Override `subcommand`/`args` for a specific OS (`"windows"`,
+    /// `"macos"`, `"linux"`), taking priority over the flat form there.
+    pub fn platform_command<I, S>(mut self, os: impl Into<String>, args: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.spec.platform_commands.insert(os.into(), args.into_iter().map(Into::into).collect());
+        self
+    }
+
+    pub fn build(self) -> CommandSpec {
+        self.spec
+    }
+}
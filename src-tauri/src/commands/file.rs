@@ -4,6 +4,7 @@ use tauri::Runtime;
 use tauri::AppHandle;
 use std::process::Command;
 use std::fs;
+use serde_json::Value;
 
 #[command]
 pub async fn open_in_folder<R: Runtime>(path: String, _app_handle: AppHandle<R>) -> Result<(), String> {
@@ -46,4 +47,143 @@ pub fn modify_file(path: &Path, pattern: &str, replacement: &str) -> Result<(),
         Ok(_) => Ok(()),
         Err(e) => Err(format!("Failed to write to file: {}", e)),
     }
-} 
\ No newline at end of file
+}
+
+/// Deep-merges a JSON fragment into an existing (or not-yet-existing) JSON
+/// file, so two modules can both contribute to files like `package.json`,
+/// `tsconfig.json`, or `.eslintrc` without one overwriting the other's edits.
+///
+/// Objects are merged key by key (recursively); arrays are unioned by value,
+/// preserving the base file's existing entries first; scalar conflicts are
+/// resolved by `on_conflict`, either `"last-write-wins"` (the fragment's
+/// value replaces the base's) or `"error"` (the merge fails instead of
+/// silently picking a winner).
+pub fn json_merge_file(path: &Path, fragment_json: &str, on_conflict: &str) -> Result<(), String> {
+    let fragment: Value = serde_json::from_str(fragment_json)
+        .map_err(|e| format!("Invalid JSON fragment: {}", e))?;
+
+    let mut base: Value = if path.exists() {
+        let content = fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read file: {}", e))?;
+        if content.trim().is_empty() {
+            Value::Object(serde_json::Map::new())
+        } else {
+            serde_json::from_str(&content)
+                .map_err(|e| format!("Failed to parse existing JSON: {}", e))?
+        }
+    } else {
+        Value::Object(serde_json::Map::new())
+    };
+
+    deep_merge(&mut base, &fragment, on_conflict)?;
+
+    let rendered = serde_json::to_string_pretty(&base)
+        .map_err(|e| format!("Failed to serialize merged JSON: {}", e))?;
+
+    fs::write(path, rendered)
+        .map_err(|e| format!("Failed to write to file: {}", e))
+}
+
+/// Merges `incoming` into `base` in place. Objects merge key by key, arrays
+/// are unioned by value (base entries first, new incoming entries appended),
+/// and anything else is a scalar conflict handled per `on_conflict`.
+fn deep_merge(base: &mut Value, incoming: &Value, on_conflict: &str) -> Result<(), String> {
+    match (base, incoming) {
+        (Value::Object(base_map), Value::Object(incoming_map)) => {
+            for (key, incoming_value) in incoming_map {
+                match base_map.get_mut(key) {
+                    Some(base_value) => deep_merge(base_value, incoming_value, on_conflict)?,
+                    None => {
+                        base_map.insert(key.clone(), incoming_value.clone());
+                    }
+                }
+            }
+            Ok(())
+        }
+        (Value::Array(base_items), Value::Array(incoming_items)) => {
+            for item in incoming_items {
+                if !base_items.contains(item) {
+                    base_items.push(item.clone());
+                }
+            }
+            Ok(())
+        }
+        (base_value, incoming_value) => {
+            if base_value == incoming_value {
+                return Ok(());
+            }
+            match on_conflict {
+                "error" => Err(format!(
+                    "JSON merge conflict: {} cannot be merged with {}",
+                    base_value, incoming_value
+                )),
+                _ => {
+                    *base_value = incoming_value.clone();
+                    Ok(())
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn merges_objects_key_by_key_recursively() {
+        let mut base = json!({"name": "app", "scripts": {"dev": "vite"}});
+        let incoming = json!({"version": "1.0.0", "scripts": {"build": "vite build"}});
+
+        deep_merge(&mut base, &incoming, "last-write-wins").unwrap();
+
+        assert_eq!(base, json!({
+            "name": "app",
+            "version": "1.0.0",
+            "scripts": {"dev": "vite", "build": "vite build"},
+        }));
+    }
+
+    #[test]
+    fn unions_arrays_by_value_without_duplicating_existing_entries() {
+        let mut base = json!({"plugins": ["a", "b"]});
+        let incoming = json!({"plugins": ["b", "c"]});
+
+        deep_merge(&mut base, &incoming, "last-write-wins").unwrap();
+
+        assert_eq!(base, json!({"plugins": ["a", "b", "c"]}));
+    }
+
+    #[test]
+    fn scalar_conflict_last_write_wins_takes_incoming_value() {
+        let mut base = json!({"private": false});
+        let incoming = json!({"private": true});
+
+        deep_merge(&mut base, &incoming, "last-write-wins").unwrap();
+
+        assert_eq!(base, json!({"private": true}));
+    }
+
+    #[test]
+    fn scalar_conflict_error_strategy_fails_instead_of_picking_a_winner() {
+        let mut base = json!({"private": false});
+        let incoming = json!({"private": true});
+
+        let result = deep_merge(&mut base, &incoming, "error");
+
+        assert!(result.is_err());
+        // The base value is untouched when the merge is rejected.
+        assert_eq!(base, json!({"private": false}));
+    }
+
+    #[test]
+    fn identical_scalars_never_count_as_a_conflict() {
+        let mut base = json!({"private": true});
+        let incoming = json!({"private": true});
+
+        deep_merge(&mut base, &incoming, "error").unwrap();
+
+        assert_eq!(base, json!({"private": true}));
+    }
+}
\ No newline at end of file
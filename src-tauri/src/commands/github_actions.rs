@@ -0,0 +1,70 @@
+//! Bridges the command-execution layer onto the GitHub Actions runner
+//! contract, so a scaffolding run can be embedded as a step in a workflow:
+//! declared inputs arrive as `INPUT_*` environment variables, and a
+//! finished `CommandResult` translates into the outputs and annotations a
+//! later step (or the job log) reads back.
+//!
+//! Entirely optional and self-contained -- nothing else in the crate
+//! depends on this running under a real GitHub-hosted runner, and calling
+//! these functions outside one is harmless (`read_input` just returns
+//! `None`, `set_output`/`report_result` print workflow commands nobody is
+//! parsing).
+
+use std::io::Write;
+
+use uuid::Uuid;
+
+use super::command_runner::CommandResult;
+
+/// Read a declared action input the way `@actions/core`'s `getInput` does:
+/// `id` is upper-cased and spaces become underscores to get the `INPUT_*`
+/// variable name. Returns `None` for an unset or empty input.
+pub fn read_input(id: &str) -> Option<String> {
+    let var_name = format!("INPUT_{}", id.to_uppercase().replace(' ', "_"));
+    std::env::var(var_name).ok().filter(|v| !v.is_empty())
+}
+
+/// Workflow-command percent-encoding for a command's `data` (its message
+/// text), per the GitHub Actions toolkit's `escapeData`.
+fn escape_data(value: &str) -> String {
+    value.replace('%', "%25").replace('\r', "%0D").replace('\n', "%0A")
+}
+
+/// Set a step output readable by `${{ steps.<id>.outputs.<name> }}` in
+/// later steps. Prefers appending to the file at `$GITHUB_OUTPUT` (the
+/// mechanism that replaced `::set-output::`, which GitHub deprecated for
+/// logging the value in plaintext); falls back to the deprecated workflow
+/// command when that env var isn't set, e.g. when this isn't actually
+/// running on a GitHub-hosted runner.
+///
+/// `value` may be multi-line (real command stdout routinely is), so the
+/// file-based path always uses GitHub's heredoc form --
+/// `name<<DELIMITER` / `value` / `DELIMITER` -- with a random delimiter per
+/// call instead of the plain `name=value` form, which a single embedded
+/// newline would corrupt.
+pub fn set_output(name: &str, value: &str) {
+    if let Ok(path) = std::env::var("GITHUB_OUTPUT") {
+        if let Ok(mut file) = std::fs::OpenOptions::new().create(true).append(true).open(&path) {
+            let delimiter = format!("ghadelimiter_{}", Uuid::new_v4());
+            if writeln!(file, "{}<<{}\n{}\n{}", name, delimiter, value, delimiter).is_ok() {
+                return;
+            }
+        }
+    }
+    println!("::set-output name={}::{}", name, escape_data(value));
+}
+
+/// Translate a finished `CommandResult` into step outputs (`success`,
+/// `exit_code`, `stdout`) and, for a non-zero exit code, an `::error::`
+/// annotation (or `::warning::` when `allow_failure` is set) so the run
+/// shows up in the job's Annotations panel instead of only its raw log.
+pub fn report_result(result: &CommandResult, allow_failure: bool) {
+    set_output("success", &result.success.to_string());
+    set_output("exit_code", &result.exit_code.to_string());
+    set_output("stdout", result.stdout.trim());
+
+    if result.exit_code != 0 {
+        let level = if allow_failure { "warning" } else { "error" };
+        println!("::{}::command exited with code {}", level, result.exit_code);
+    }
+}
@@ -1,9 +1,28 @@
 pub mod project;
 pub mod system;
 pub mod framework;
+pub mod template_registry;
+pub mod template_schema;
+pub mod module_resolver;
+pub mod module_lockfile;
+pub mod project_lock;
+pub mod module_add;
+pub mod module_apply;
+pub mod project_info;
 pub mod command_runner;
+pub mod ast_import;
+pub mod github_actions;
 pub mod file;
 pub mod node_commands;
+pub mod plugin;
+pub mod command_scope;
+pub mod command_spec;
+pub mod route;
+pub mod transform;
+pub mod package_inventory;
+pub mod package_manager;
+pub mod worker;
+pub mod webhook;
 
 pub use project::*;
 pub use framework::*;
@@ -19,5 +38,6 @@ pub use file::open_in_folder;
 pub use node_commands::{
     run_node_command,
     run_node_command_streaming,
+    run_supervised_node_command,
     cleanup_command_resources
 };
@@ -0,0 +1,206 @@
+//! Incrementally add a single module to an already-scaffolded project,
+//! mirroring the "add a plugin to an existing app" pattern common in other
+//! scaffolding ecosystems.
+//!
+//! Generation installs every selected module up front via `ModuleTask`; this
+//! instead loads the project's `.architech/modules.json` lockfile, resolves
+//! `module_id` against the project's *current* module set (so a new
+//! dependency is pulled in and conflicts are still caught), and applies only
+//! the resulting delta -- the same commands/file-operations machinery
+//! `ModuleTask` uses, just against a live project directory instead of a
+//! generation run's staging directory.
+//!
+//! `install_module` below is also used by `module_apply::apply_modules`,
+//! which generalizes this to both adding and removing modules from a live
+//! project.
+
+use std::path::Path;
+use std::path::PathBuf;
+
+use log::{info, warn};
+use tauri::{command, AppHandle, Emitter};
+
+use super::framework::{get_frameworks, get_modules, Module};
+use super::module_lockfile::{AppliedOperation, ModulesLockfile};
+use super::module_resolver::resolve_modules;
+use super::node_commands::{execute_node_command, NodeCommandOptions};
+use super::package_manager::PackageManagerBackend;
+use super::transform::apply_transform;
+
+/// Run `module`'s install commands and file operations against
+/// `project_dir`, returning the file operations it applied so the caller
+/// can record them for later reversal (see `ModulesLockfile::take_installed`).
+/// Does not touch the modules lockfile itself -- callers that install more
+/// than one module in a batch record each as it finishes.
+pub(crate) async fn install_module(
+    app_handle: &AppHandle,
+    project_dir: &Path,
+    backend: &dyn PackageManagerBackend,
+    module: &Module,
+) -> Result<Vec<AppliedOperation>, String> {
+    info!("Installing module '{}' into existing project at {}", module.id, project_dir.display());
+    app_handle.emit("log-message", format!("Installing module: {}", module.name)).ok();
+
+    for cmd in &module.installation.commands {
+        let command_str = cmd.to_command_string_for(backend);
+        let command_dir = match &cmd.cwd_relative {
+            Some(relative) => project_dir.join(relative),
+            None => project_dir.to_path_buf(),
+        };
+
+        // Skip installs whose requested packages are already satisfied, so
+        // re-adding a module whose dependency is already present is fast
+        // and idempotent, same as initial generation.
+        let requested_packages = cmd.requested_packages();
+        if !requested_packages.is_empty() {
+            let inventory = super::package_inventory::PackageInventory::read(&command_dir);
+            if requested_packages.iter().all(|pkg| inventory.is_satisfied(pkg)) {
+                info!("Skipping '{}': already satisfied", command_str);
+                continue;
+            }
+        }
+
+        let cmd_options = if cmd.env.is_empty() {
+            None
+        } else {
+            Some(NodeCommandOptions {
+                env_vars: Some(cmd.env.clone()),
+                ..Default::default()
+            })
+        };
+
+        let result = execute_node_command(app_handle, &command_dir, &command_str, cmd_options).await;
+
+        let failure = match result {
+            Ok(r) if r.success => None,
+            Ok(r) => Some(r.stderr),
+            Err(e) => Some(e),
+        };
+
+        if let Some(stderr) = failure {
+            let msg = format!("Command '{}' failed while installing module '{}': {}", command_str, module.id, stderr);
+            if cmd.critical && !cmd.allow_failure {
+                return Err(msg);
+            }
+            warn!("{}", msg);
+        }
+    }
+
+    let mut applied_operations = Vec::new();
+
+    for op in &module.installation.file_operations {
+        let file_path = project_dir.join(&op.path);
+        if let Some(parent) = file_path.parent() {
+            if !parent.exists() {
+                std::fs::create_dir_all(parent)
+                    .map_err(|e| format!("Failed to create directory '{}': {}", parent.display(), e))?;
+            }
+        }
+
+        match op.operation.as_str() {
+            "create" => {
+                applied_operations.push(AppliedOperation {
+                    path: op.path.clone(),
+                    prior_content: std::fs::read_to_string(&file_path).ok(),
+                });
+                std::fs::write(&file_path, &op.content)
+                    .map_err(|e| format!("Failed to create file '{}': {}", op.path, e))?;
+            }
+            "modify" | "regex" | "literal" | "insert_after" | "insert_before" | "modify_import" => {
+                if !file_path.exists() {
+                    warn!("Cannot apply '{}' to non-existent file: {}", op.operation, op.path);
+                    continue;
+                }
+                applied_operations.push(AppliedOperation {
+                    path: op.path.clone(),
+                    prior_content: std::fs::read_to_string(&file_path).ok(),
+                });
+                apply_transform(&file_path, op)
+                    .map_err(|e| format!("Failed to apply '{}' to '{}': {}", op.operation, op.path, e))?;
+            }
+            "json-merge" | "json_merge" => {
+                applied_operations.push(AppliedOperation {
+                    path: op.path.clone(),
+                    prior_content: std::fs::read_to_string(&file_path).ok(),
+                });
+                apply_transform(&file_path, op)
+                    .map_err(|e| format!("Failed to merge JSON into '{}': {}", op.path, e))?;
+            }
+            other => warn!("Unknown file operation '{}' for module '{}'", other, module.id),
+        }
+    }
+
+    app_handle.emit("log-message", format!("Module '{}' installed", module.id)).ok();
+    Ok(applied_operations)
+}
+
+/// Add `module_id` (and any of its unresolved dependencies) to the project
+/// at `project_path`. Idempotent: re-adding an already-installed module
+/// returns immediately with a message instead of re-running its commands.
+///
+/// `options` mirrors `ModuleConfiguration`'s declared options, but nothing
+/// in this codebase substitutes those into an install yet (see
+/// `ModuleTask`) -- accepted here for API parity, not yet wired deeper.
+#[command]
+pub async fn add_module_to_project(
+    project_path: String,
+    module_id: String,
+    options: Option<serde_json::Value>,
+    app_handle: AppHandle,
+) -> Result<String, String> {
+    let _ = options;
+
+    let project_dir = PathBuf::from(&project_path);
+    if !project_dir.is_dir() {
+        return Err(format!("Project directory does not exist: {}", project_path));
+    }
+
+    let mut lockfile = ModulesLockfile::load(&project_dir)?.ok_or_else(|| {
+        format!(
+            "No modules lockfile found for '{}'; this project wasn't generated with module tracking enabled",
+            project_dir.display()
+        )
+    })?;
+
+    if let Some(installed_version) = lockfile.version_of(&module_id) {
+        let msg = format!(
+            "Module '{}' is already installed (version {}); nothing to do",
+            module_id, installed_version
+        );
+        info!("{}", msg);
+        return Ok(msg);
+    }
+
+    let frameworks = get_frameworks().await?;
+    let framework = frameworks
+        .into_iter()
+        .find(|f| f.id == lockfile.framework)
+        .ok_or_else(|| format!("Framework '{}' not found", lockfile.framework))?;
+
+    let all_modules = get_modules().await?;
+
+    let mut selected = lockfile.installed_ids();
+    selected.push(module_id.clone());
+
+    let resolved = resolve_modules(&framework, &selected, &all_modules).map_err(|e| e.to_string())?;
+
+    let already = lockfile.installed_ids();
+    let delta: Vec<Module> = resolved.into_iter().filter(|m| !already.contains(&m.id)).collect();
+
+    if delta.is_empty() {
+        return Ok(format!("Module '{}' is already installed; nothing to do", module_id));
+    }
+
+    let backend = super::package_manager::resolve(&project_dir, None);
+
+    for module in &delta {
+        let applied_operations = install_module(&app_handle, &project_dir, backend.as_ref(), module).await?;
+        lockfile.record_installed(&project_dir, &module.id, &module.version, applied_operations)?;
+    }
+
+    Ok(format!(
+        "Added module '{}' ({} module(s) installed for this add, including dependencies)",
+        module_id,
+        delta.len()
+    ))
+}
@@ -0,0 +1,144 @@
+//! Diff an already-scaffolded project's installed modules against a newly
+//! requested set and apply the delta in both directions -- add whatever's
+//! missing, remove whatever's no longer wanted -- so a user can evolve a
+//! project's feature set instead of regenerating it from scratch.
+//!
+//! Additions reuse `module_add::install_module`. Removals reverse the
+//! `AppliedOperation`s that install recorded (deleting files a `create`
+//! produced, restoring the content a `modify`/`json-merge` overwrote) and
+//! run the package manager's remove command for whatever packages the
+//! module's install commands had requested.
+
+use std::path::PathBuf;
+
+use log::{info, warn};
+use serde::Serialize;
+use tauri::{command, AppHandle};
+
+use super::framework::{get_frameworks, get_modules, Module};
+use super::module_add::install_module;
+use super::module_lockfile::ModulesLockfile;
+use super::module_resolver::resolve_modules;
+use super::node_commands::execute_node_command;
+
+/// What `apply_modules` actually did, for the frontend to render.
+#[derive(Serialize, Debug, Clone, Default)]
+pub struct ApplyModulesReport {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+}
+
+/// Reconcile the project at `project_path` with `modules` (the desired full
+/// selection, not a delta): resolve it the same way generation does, then
+/// install whatever's newly required and remove whatever's no longer in
+/// the closed set. Idempotent -- requesting the project's current module
+/// set is a no-op.
+#[command]
+pub async fn apply_modules(
+    project_path: String,
+    modules: Vec<String>,
+    app_handle: AppHandle,
+) -> Result<ApplyModulesReport, String> {
+    let project_dir = PathBuf::from(&project_path);
+    if !project_dir.is_dir() {
+        return Err(format!("Project directory does not exist: {}", project_path));
+    }
+
+    let mut lockfile = ModulesLockfile::load(&project_dir)?.ok_or_else(|| {
+        format!(
+            "No modules lockfile found for '{}'; this project wasn't generated with module tracking enabled",
+            project_dir.display()
+        )
+    })?;
+
+    let frameworks = get_frameworks().await?;
+    let framework = frameworks
+        .into_iter()
+        .find(|f| f.id == lockfile.framework)
+        .ok_or_else(|| format!("Framework '{}' not found", lockfile.framework))?;
+
+    let all_modules = get_modules().await?;
+    let resolved = resolve_modules(&framework, &modules, &all_modules).map_err(|e| e.to_string())?;
+
+    let desired_ids: Vec<String> = resolved.iter().map(|m| m.id.clone()).collect();
+    let installed_ids = lockfile.installed_ids();
+
+    let to_add: Vec<&Module> = resolved.iter().filter(|m| !installed_ids.contains(&m.id)).collect();
+    let to_remove: Vec<String> = installed_ids.into_iter().filter(|id| !desired_ids.contains(id)).collect();
+
+    let mut report = ApplyModulesReport::default();
+
+    let backend = super::package_manager::resolve(&project_dir, None);
+
+    for module in &to_add {
+        let applied_operations = install_module(&app_handle, &project_dir, backend.as_ref(), module).await?;
+        lockfile.record_installed(&project_dir, &module.id, &module.version, applied_operations)?;
+        report.added.push(module.id.clone());
+    }
+
+    for module_id in &to_remove {
+        remove_module(&app_handle, &project_dir, &mut lockfile, &all_modules, module_id).await?;
+        report.removed.push(module_id.clone());
+    }
+
+    Ok(report)
+}
+
+/// Reverse one module's install: run its package manager's remove command
+/// for whatever it had requested, undo its `AppliedOperation`s (newest
+/// first, mirroring a stack unwind), then drop it from the lockfile. Also
+/// used by `ModuleTask::rollback` to undo a module whose own task succeeded
+/// before a later task in the same run failed.
+pub(crate) async fn remove_module(
+    app_handle: &AppHandle,
+    project_dir: &std::path::Path,
+    lockfile: &mut ModulesLockfile,
+    all_modules: &[Module],
+    module_id: &str,
+) -> Result<(), String> {
+    info!("Removing module '{}' from project at {}", module_id, project_dir.display());
+
+    if let Some(module) = all_modules.iter().find(|m| m.id == module_id) {
+        let backend = super::package_manager::resolve(project_dir, None);
+        let packages: Vec<String> = module
+            .installation
+            .commands
+            .iter()
+            .flat_map(|cmd| cmd.requested_packages())
+            .collect();
+
+        if !packages.is_empty() {
+            let (program, args) = backend.remove_cmd(&packages);
+            let command_str = super::package_manager::command_string((program, args));
+            match execute_node_command(app_handle, project_dir, &command_str, None).await {
+                Ok(result) if !result.success => {
+                    warn!("Failed to uninstall packages for module '{}': {}", module_id, result.stderr);
+                }
+                Err(e) => warn!("Failed to run uninstall command for module '{}': {}", module_id, e),
+                _ => {}
+            }
+        }
+    } else {
+        warn!("Module '{}' is not in the known catalog; removing its files but skipping package uninstall", module_id);
+    }
+
+    let removed = lockfile.take_installed(project_dir, module_id)?;
+    if let Some(removed) = removed {
+        for applied in removed.applied_operations.iter().rev() {
+            let file_path = project_dir.join(&applied.path);
+            let result = match &applied.prior_content {
+                Some(content) => std::fs::write(&file_path, content),
+                None => match std::fs::remove_file(&file_path) {
+                    Ok(()) => Ok(()),
+                    Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+                    Err(e) => Err(e),
+                },
+            };
+            if let Err(e) = result {
+                warn!("Failed to reverse file operation on '{}' while removing module '{}': {}", applied.path, module_id, e);
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,133 @@
+//! Persisted record of which modules are actually installed in a generated
+//! project, at `<project>/.architech/modules.json`.
+//!
+//! Generation (`ModuleTask`) and the incremental `add_module_to_project`
+//! command both read and write this file, so an incremental add can diff
+//! against the project's real state instead of trusting whatever selection
+//! the caller happens to pass in.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+const LOCKFILE_RELATIVE_PATH: &str = ".architech/modules.json";
+
+/// What a single `FileOperation` did to a file, recorded at install time so
+/// removing the module later can reverse it instead of just deleting
+/// whatever the module happens to own.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AppliedOperation {
+    /// Path the operation touched, relative to the project root.
+    pub path: String,
+    /// The file's content immediately before the operation ran, or `None`
+    /// if the operation created the file (so reversing it deletes the
+    /// file instead of restoring content).
+    pub prior_content: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct InstalledModule {
+    pub id: String,
+    pub version: String,
+    /// File operations this module applied, oldest first, so removal can
+    /// reverse them in the opposite order. Empty for modules installed
+    /// before this was tracked.
+    #[serde(default)]
+    pub applied_operations: Vec<AppliedOperation>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ModulesLockfile {
+    pub framework: String,
+    #[serde(default)]
+    pub modules: Vec<InstalledModule>,
+}
+
+impl ModulesLockfile {
+    fn path(project_dir: &Path) -> PathBuf {
+        project_dir.join(LOCKFILE_RELATIVE_PATH)
+    }
+
+    /// Load the lockfile at `project_dir`, or `None` if the project has
+    /// never had one written (e.g. generated before module tracking
+    /// existed).
+    pub fn load(project_dir: &Path) -> Result<Option<Self>, String> {
+        let path = Self::path(project_dir);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read modules lockfile '{}': {}", path.display(), e))?;
+        serde_json::from_str(&content)
+            .map(Some)
+            .map_err(|e| format!("Failed to parse modules lockfile '{}': {}", path.display(), e))
+    }
+
+    /// Load the lockfile at `project_dir`, creating an empty one scoped to
+    /// `framework` if none exists yet.
+    pub fn load_or_init(project_dir: &Path, framework: &str) -> Result<Self, String> {
+        Ok(Self::load(project_dir)?.unwrap_or_else(|| Self {
+            framework: framework.to_string(),
+            modules: Vec::new(),
+        }))
+    }
+
+    pub fn is_installed(&self, module_id: &str) -> bool {
+        self.modules.iter().any(|m| m.id == module_id)
+    }
+
+    pub fn installed_ids(&self) -> Vec<String> {
+        self.modules.iter().map(|m| m.id.clone()).collect()
+    }
+
+    pub fn version_of(&self, module_id: &str) -> Option<&str> {
+        self.modules.iter().find(|m| m.id == module_id).map(|m| m.version.as_str())
+    }
+
+    /// Record `module_id`/`version` as installed (replacing any prior entry
+    /// for the same id) along with the file operations it applied, and
+    /// persist the lockfile to disk.
+    pub fn record_installed(
+        &mut self,
+        project_dir: &Path,
+        module_id: &str,
+        version: &str,
+        applied_operations: Vec<AppliedOperation>,
+    ) -> Result<(), String> {
+        self.modules.retain(|m| m.id != module_id);
+        self.modules.push(InstalledModule {
+            id: module_id.to_string(),
+            version: version.to_string(),
+            applied_operations,
+        });
+        self.save(project_dir)
+    }
+
+    /// Remove `module_id`'s entry and persist the lockfile, returning the
+    /// removed entry (with the file operations it applied) so the caller
+    /// can reverse them. `None` if the module wasn't tracked as installed.
+    pub fn take_installed(&mut self, project_dir: &Path, module_id: &str) -> Result<Option<InstalledModule>, String> {
+        let index = self.modules.iter().position(|m| m.id == module_id);
+        let removed = match index {
+            Some(i) => Some(self.modules.remove(i)),
+            None => None,
+        };
+        if removed.is_some() {
+            self.save(project_dir)?;
+        }
+        Ok(removed)
+    }
+
+    fn save(&self, project_dir: &Path) -> Result<(), String> {
+        let path = Self::path(project_dir);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create '{}': {}", parent.display(), e))?;
+        }
+        let rendered = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize modules lockfile: {}", e))?;
+        fs::write(&path, rendered)
+            .map_err(|e| format!("Failed to write modules lockfile '{}': {}", path.display(), e))
+    }
+}
@@ -0,0 +1,321 @@
+//! Dependency resolution and conflict detection for a user's module
+//! selection.
+//!
+//! `Module` already carries `dependencies`/`incompatible_with`, and
+//! `Framework` carries `compatible_modules`, but nothing resolved them --
+//! task creation in `generation.rs` did its own ad hoc bookkeeping that only
+//! caught a direct `A <-> B` pair, never pulled in a dependency the user
+//! didn't explicitly select, and never checked `compatible_modules` or
+//! `incompatible_with` at all. `resolve_modules` replaces that with one
+//! pass: transitively close dependencies, reject anything outside the
+//! framework's compatible set, detect conflicts across the closed set, and
+//! topologically sort the result into a deterministic install order.
+
+use std::collections::{BTreeSet, HashMap};
+
+use thiserror::Error;
+
+use super::framework::{Framework, Module};
+
+#[derive(Debug, Error)]
+pub enum ResolutionError {
+    #[error("module '{0}' is not in the known module catalog")]
+    Missing(String),
+
+    #[error("module '{module}' is not compatible with framework '{framework}'")]
+    Incompatible { module: String, framework: String },
+
+    #[error("module '{a}' is incompatible with selected module '{b}'")]
+    Conflict { a: String, b: String },
+
+    #[error("circular module dependency: {}", .0.join(" -> "))]
+    Cycle(Vec<String>),
+}
+
+/// Resolve `selected` module ids against `framework` and the full `catalog`
+/// of known modules:
+/// 1. Transitively close `dependencies`, pulling in required modules the
+///    caller didn't select directly.
+/// 2. Reject anything not in `framework.compatible_modules`.
+/// 3. Detect `incompatible_with` conflicts across the closed set.
+/// 4. Topologically sort by `dependencies` so a dependency always installs
+///    before whatever depends on it, breaking ties by module id for a
+///    deterministic order given the same selection.
+pub fn resolve_modules(
+    framework: &Framework,
+    selected: &[String],
+    catalog: &[Module],
+) -> Result<Vec<Module>, ResolutionError> {
+    let by_id: HashMap<&str, &Module> = catalog.iter().map(|m| (m.id.as_str(), m)).collect();
+
+    let mut closed: HashMap<String, Module> = HashMap::new();
+    let mut stack: Vec<String> = selected.to_vec();
+    while let Some(id) = stack.pop() {
+        if closed.contains_key(&id) {
+            continue;
+        }
+        let module = by_id.get(id.as_str()).ok_or_else(|| ResolutionError::Missing(id.clone()))?;
+        stack.extend(module.dependencies.clone());
+        closed.insert(id, (*module).clone());
+    }
+
+    for id in closed.keys() {
+        if !framework.compatible_modules.iter().any(|compatible| compatible == id) {
+            return Err(ResolutionError::Incompatible { module: id.clone(), framework: framework.id.clone() });
+        }
+    }
+
+    for module in closed.values() {
+        for conflict in &module.incompatible_with {
+            if closed.contains_key(conflict) {
+                return Err(ResolutionError::Conflict { a: module.id.clone(), b: conflict.clone() });
+            }
+        }
+    }
+
+    topological_sort(closed)
+}
+
+/// Kahn's algorithm: repeatedly take the lexicographically-smallest module
+/// with no unresolved dependency left in the set, until none remain. Any
+/// module still owing a dependency when the queue empties is part of a
+/// cycle.
+fn topological_sort(closed: HashMap<String, Module>) -> Result<Vec<Module>, ResolutionError> {
+    let mut remaining: HashMap<String, usize> = closed
+        .values()
+        .map(|module| {
+            let unresolved = module.dependencies.iter().filter(|dep| closed.contains_key(*dep)).count();
+            (module.id.clone(), unresolved)
+        })
+        .collect();
+
+    let mut dependents: HashMap<String, Vec<String>> = HashMap::new();
+    for module in closed.values() {
+        for dep in &module.dependencies {
+            if closed.contains_key(dep) {
+                dependents.entry(dep.clone()).or_default().push(module.id.clone());
+            }
+        }
+    }
+
+    let mut ready: BTreeSet<String> =
+        remaining.iter().filter(|(_, &count)| count == 0).map(|(id, _)| id.clone()).collect();
+
+    let mut order = Vec::with_capacity(closed.len());
+    while let Some(id) = ready.pop_first() {
+        order.push(id.clone());
+        for dependent in dependents.get(&id).into_iter().flatten() {
+            let count = remaining.get_mut(dependent).expect("dependent is tracked in remaining");
+            *count -= 1;
+            if *count == 0 {
+                ready.insert(dependent.clone());
+            }
+        }
+    }
+
+    if order.len() != closed.len() {
+        let cyclic: BTreeSet<String> = remaining.into_iter().filter(|(_, count)| *count > 0).map(|(id, _)| id).collect();
+        return Err(ResolutionError::Cycle(trace_cycle(&closed, &cyclic)));
+    }
+
+    let mut closed = closed;
+    Ok(order.into_iter().map(|id| closed.remove(&id).expect("id came from closed")).collect())
+}
+
+/// Walk dependency edges restricted to `cyclic` from an arbitrary starting
+/// node until a module is revisited, returning the path including the
+/// repeated id at both ends (e.g. `["a", "b", "a"]`) -- the concrete chain
+/// behind a `ResolutionError::Cycle`, rather than just the set of module ids
+/// it touches.
+fn trace_cycle(closed: &HashMap<String, Module>, cyclic: &BTreeSet<String>) -> Vec<String> {
+    let Some(start) = cyclic.iter().next() else {
+        return Vec::new();
+    };
+
+    let mut path = vec![start.clone()];
+    let mut current = start.clone();
+    loop {
+        let next = closed
+            .get(&current)
+            .and_then(|module| module.dependencies.iter().find(|dep| cyclic.contains(*dep)))
+            .expect("a module in a detected cycle has an unresolved dependency also in the cycle");
+
+        if let Some(repeat_at) = path.iter().position(|id| id == next) {
+            path.push(next.clone());
+            return path[repeat_at..].to_vec();
+        }
+        path.push(next.clone());
+        current = next.clone();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::framework::{DirectoryStructure, FrameworkCli, ModuleConfiguration, ModuleInstallation};
+
+    fn module(id: &str, dependencies: &[&str], incompatible_with: &[&str]) -> Module {
+        Module {
+            id: id.to_string(),
+            name: id.to_string(),
+            description: String::new(),
+            version: "1.0.0".to_string(),
+            category: "test".to_string(),
+            dependencies: dependencies.iter().map(|d| d.to_string()).collect(),
+            incompatible_with: incompatible_with.iter().map(|d| d.to_string()).collect(),
+            installation: ModuleInstallation { commands: Vec::new(), file_operations: Vec::new() },
+            configuration: ModuleConfiguration { options: Vec::new() },
+            fetch: Vec::new(),
+        }
+    }
+
+    fn framework(compatible_modules: &[&str]) -> Framework {
+        Framework {
+            id: "test-framework".to_string(),
+            name: "Test Framework".to_string(),
+            description: String::new(),
+            version: "1.0.0".to_string(),
+            framework_type: "test".to_string(),
+            tags: Vec::new(),
+            cli: FrameworkCli {
+                base_command: "test".to_string(),
+                arguments: serde_json::Map::new(),
+                interactive: false,
+                responses: Vec::new(),
+                platform_commands: HashMap::new(),
+            },
+            compatible_modules: compatible_modules.iter().map(|m| m.to_string()).collect(),
+            directory_structure: DirectoryStructure { enforced: false, directories: Vec::new() },
+            fetch: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn pulls_in_transitive_dependencies_not_directly_selected() {
+        let framework = framework(&["a", "b", "c"]);
+        let catalog = vec![module("a", &["b"], &[]), module("b", &["c"], &[]), module("c", &[], &[])];
+
+        let resolved = resolve_modules(&framework, &["a".to_string()], &catalog).unwrap();
+
+        assert_eq!(
+            resolved.iter().map(|m| m.id.as_str()).collect::<Vec<_>>(),
+            vec!["c", "b", "a"]
+        );
+    }
+
+    #[test]
+    fn rejects_a_module_outside_the_framework_compatible_set() {
+        let framework = framework(&["a"]);
+        let catalog = vec![module("a", &[], &[]), module("b", &[], &[])];
+
+        let err = resolve_modules(&framework, &["a".to_string(), "b".to_string()], &catalog).unwrap_err();
+
+        assert!(matches!(err, ResolutionError::Incompatible { module, .. } if module == "b"));
+    }
+
+    #[test]
+    fn rejects_a_module_not_in_the_catalog_at_all() {
+        let framework = framework(&["ghost"]);
+        let catalog = vec![];
+
+        let err = resolve_modules(&framework, &["ghost".to_string()], &catalog).unwrap_err();
+
+        assert!(matches!(err, ResolutionError::Missing(id) if id == "ghost"));
+    }
+
+    #[test]
+    fn detects_a_direct_incompatibility_between_two_selected_modules() {
+        let framework = framework(&["a", "b"]);
+        let catalog = vec![module("a", &[], &["b"]), module("b", &[], &[])];
+
+        let err = resolve_modules(&framework, &["a".to_string(), "b".to_string()], &catalog).unwrap_err();
+
+        assert!(matches!(err, ResolutionError::Conflict { a, b } if a == "a" && b == "b"));
+    }
+
+    #[test]
+    fn detects_an_incompatibility_pulled_in_transitively() {
+        // "a" depends on "c", and "b" is incompatible with "c" -- the
+        // conflict only shows up once dependency closure pulls "c" in,
+        // not from the two directly-selected ids alone.
+        let framework = framework(&["a", "b", "c"]);
+        let catalog = vec![module("a", &["c"], &[]), module("b", &[], &["c"]), module("c", &[], &[])];
+
+        let err = resolve_modules(&framework, &["a".to_string(), "b".to_string()], &catalog).unwrap_err();
+
+        assert!(matches!(err, ResolutionError::Conflict { .. }));
+    }
+
+    #[test]
+    fn detects_a_direct_dependency_cycle() {
+        let framework = framework(&["a", "b"]);
+        let catalog = vec![module("a", &["b"], &[]), module("b", &["a"], &[])];
+
+        let err = resolve_modules(&framework, &["a".to_string()], &catalog).unwrap_err();
+
+        match err {
+            ResolutionError::Cycle(chain) => {
+                assert_eq!(chain.first(), chain.last());
+                assert!(chain.contains(&"a".to_string()));
+                assert!(chain.contains(&"b".to_string()));
+            }
+            other => panic!("expected Cycle, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn traces_a_cycle_that_does_not_include_every_resolved_module() {
+        // "standalone" has no dependencies and isn't part of the cycle --
+        // the traced chain should only ever contain "a" and "b", not every
+        // module in the resolved set.
+        let framework = framework(&["standalone", "a", "b"]);
+        let catalog =
+            vec![module("standalone", &[], &[]), module("a", &["b"], &[]), module("b", &["a"], &[])];
+
+        let err =
+            resolve_modules(&framework, &["standalone".to_string(), "a".to_string()], &catalog).unwrap_err();
+
+        match err {
+            ResolutionError::Cycle(chain) => {
+                assert!(!chain.contains(&"standalone".to_string()));
+                assert_eq!(chain.first(), chain.last());
+            }
+            other => panic!("expected Cycle, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn traces_a_cycle_longer_than_two_modules_as_a_single_concrete_chain() {
+        // a -> b -> c -> a: the traced chain should walk the whole ring
+        // rather than stopping at the first pair it finds.
+        let framework = framework(&["a", "b", "c"]);
+        let catalog = vec![module("a", &["b"], &[]), module("b", &["c"], &[]), module("c", &["a"], &[])];
+
+        let err = resolve_modules(&framework, &["a".to_string()], &catalog).unwrap_err();
+
+        match err {
+            ResolutionError::Cycle(chain) => {
+                assert_eq!(chain.first(), chain.last());
+                assert_eq!(chain.len(), 4, "expected a, b, c, and the repeated start: {chain:?}");
+                for id in ["a", "b", "c"] {
+                    assert!(chain.contains(&id.to_string()), "chain {chain:?} missing {id}");
+                }
+            }
+            other => panic!("expected Cycle, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn breaks_ties_lexicographically_for_a_deterministic_install_order() {
+        let framework = framework(&["z", "a", "m"]);
+        let catalog = vec![module("z", &[], &[]), module("a", &[], &[]), module("m", &[], &[])];
+
+        let resolved =
+            resolve_modules(&framework, &["z".to_string(), "a".to_string(), "m".to_string()], &catalog).unwrap();
+
+        assert_eq!(
+            resolved.iter().map(|m| m.id.as_str()).collect::<Vec<_>>(),
+            vec!["a", "m", "z"]
+        );
+    }
+}
@@ -41,6 +41,13 @@ pub enum NodeCommandEvent {
     },
     /// Command encountered an error
     Error(String),
+    /// Supervisor is about to restart the process after an unexpected exit
+    Restarting {
+        /// Restart attempt number (1-indexed)
+        attempt: u32,
+        /// Delay before the restart, in milliseconds
+        delay_ms: u64,
+    },
 }
 
 // Track active command processes
@@ -78,6 +85,88 @@ fn clear_commands() {
     }
 }
 
+/// Backoff parameters for the long-running process supervisor
+const SUPERVISOR_INITIAL_DELAY_MS: u64 = 200;
+const SUPERVISOR_MAX_DELAY_MS: u64 = 30_000;
+const SUPERVISOR_STABILITY_WINDOW: std::time::Duration = std::time::Duration::from_secs(5);
+const SUPERVISOR_MAX_RESTARTS: u32 = 10;
+
+/// Handle used to cancel a supervised restart loop
+static SUPERVISORS: Lazy<Mutex<HashMap<CommandId, std::sync::Arc<std::sync::atomic::AtomicBool>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+fn register_supervisor(id: String) -> std::sync::Arc<std::sync::atomic::AtomicBool> {
+    let stop_flag = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+    SUPERVISORS.lock().unwrap().insert(id, stop_flag.clone());
+    stop_flag
+}
+
+fn cancel_supervisor(id: &str) {
+    if let Some(flag) = SUPERVISORS.lock().unwrap().remove(id) {
+        flag.store(true, std::sync::atomic::Ordering::SeqCst);
+    }
+}
+
+/// Run a long-lived command (e.g. a dev server) under supervision: if it exits
+/// unexpectedly it is restarted with exponential backoff, resetting the delay
+/// once the process has stayed up past the stability window.
+pub async fn run_supervised_command<R: Runtime>(
+    app_handle: &AppHandle<R>,
+    working_dir: &Path,
+    command: &str,
+    command_id: &str,
+) -> Result<(), String> {
+    let stop_flag = register_supervisor(command_id.to_string());
+    let event_name = format!("node-command-{}", command_id);
+
+    let mut delay_ms = SUPERVISOR_INITIAL_DELAY_MS;
+    let mut attempt: u32 = 0;
+
+    loop {
+        if stop_flag.load(std::sync::atomic::Ordering::SeqCst) {
+            debug!("Supervisor for '{}' cancelled, stopping", command_id);
+            break;
+        }
+
+        let started_at = std::time::Instant::now();
+        let result = execute_node_command_streaming(app_handle, working_dir, command, &event_name).await;
+
+        if stop_flag.load(std::sync::atomic::Ordering::SeqCst) {
+            break;
+        }
+
+        let exited_cleanly = matches!(&result, Ok(r) if r.success);
+        if exited_cleanly {
+            debug!("Supervised command '{}' exited cleanly, stopping supervisor", command_id);
+            break;
+        }
+
+        // A long, stable run resets the backoff delay
+        if started_at.elapsed() >= SUPERVISOR_STABILITY_WINDOW {
+            delay_ms = SUPERVISOR_INITIAL_DELAY_MS;
+            attempt = 0;
+        }
+
+        attempt += 1;
+        if attempt > SUPERVISOR_MAX_RESTARTS {
+            warn!("Supervised command '{}' exceeded max restarts ({}), giving up", command_id, SUPERVISOR_MAX_RESTARTS);
+            let _ = app_handle.emit(&event_name, NodeCommandEvent::Error(
+                format!("Process crashed repeatedly and was not restarted after {} attempts", SUPERVISOR_MAX_RESTARTS)
+            ));
+            break;
+        }
+
+        warn!("Supervised command '{}' terminated unexpectedly, restarting in {}ms (attempt {})", command_id, delay_ms, attempt);
+        let _ = app_handle.emit(&event_name, NodeCommandEvent::Restarting { attempt, delay_ms });
+
+        tokio::time::sleep(std::time::Duration::from_millis(delay_ms)).await;
+        delay_ms = (delay_ms * 2).min(SUPERVISOR_MAX_DELAY_MS);
+    }
+
+    SUPERVISORS.lock().unwrap().remove(command_id);
+    Ok(())
+}
+
 /// Options for executing a Node.js command
 #[derive(Debug, Clone, Default)]
 pub struct NodeCommandOptions {
@@ -89,6 +178,92 @@ pub struct NodeCommandOptions {
     pub event_name: Option<String>,
 }
 
+/// How many times, and how fast, to retry a network-sensitive command
+/// (install, build) before giving up. Read from `ProjectConfig::retry_policy`
+/// so scaffolding over a flaky network doesn't abort the whole cleanup task
+/// on the first hiccup.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RetryPolicy {
+    /// Total attempts, including the first -- 1 means "never retry".
+    pub max_attempts: u32,
+    /// Delay before the first retry, in milliseconds; doubled after each
+    /// further attempt.
+    pub base_delay_ms: u64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay_ms: 2_000,
+        }
+    }
+}
+
+/// Substrings that show up in stderr/error messages for failures worth
+/// retrying -- DNS hiccups, dropped connections, registry timeouts -- as
+/// opposed to failures a retry can't fix (bad syntax, missing script).
+const TRANSIENT_ERROR_MARKERS: &[&str] = &[
+    "econnreset",
+    "econnrefused",
+    "etimedout",
+    "enotfound",
+    "eai_again",
+    "network error",
+    "socket hang up",
+    "timed out",
+    "fetch failed",
+    "getaddrinfo",
+];
+
+fn is_transient_failure(result: &Result<CommandResult, String>) -> bool {
+    let text = match result {
+        Ok(r) if !r.success => r.stderr.to_lowercase(),
+        Err(e) => e.to_lowercase(),
+        _ => return false,
+    };
+    TRANSIENT_ERROR_MARKERS.iter().any(|marker| text.contains(marker))
+}
+
+/// Run `command` via `execute_node_command`, retrying on transient
+/// failures (network errors recognizable in stderr, or a timed-out
+/// execution) per `policy`, with exponential backoff. Emits a
+/// `log-message` event before each retry. Non-transient failures and the
+/// final attempt's result are returned as-is.
+pub async fn execute_node_command_with_retry<R: Runtime>(
+    app_handle: &AppHandle<R>,
+    working_dir: &Path,
+    command: &str,
+    options: Option<NodeCommandOptions>,
+    policy: &RetryPolicy,
+) -> Result<CommandResult, String> {
+    let mut delay_ms = policy.base_delay_ms;
+    let mut attempt: u32 = 1;
+
+    loop {
+        let result = execute_node_command(app_handle, working_dir, command, options.clone()).await;
+
+        if attempt >= policy.max_attempts || !is_transient_failure(&result) {
+            return result;
+        }
+
+        let delay = std::time::Duration::from_millis(delay_ms);
+        let message = format!(
+            "'{}' failed, retrying ({}/{}) in {}s...",
+            command,
+            attempt + 1,
+            policy.max_attempts,
+            delay.as_secs()
+        );
+        warn!("{}", message);
+        app_handle.emit("log-message", message).ok();
+
+        tokio::time::sleep(delay).await;
+        attempt += 1;
+        delay_ms *= 2;
+    }
+}
+
 /// Validate command inputs
 /// 
 /// This is a helper function to validate the working directory and command
@@ -115,31 +290,12 @@ fn validate_command_inputs(working_dir: &Path, command: &str) -> Result<(), Stri
 }
 
 /// Validate command for security
-/// 
-/// This checks if the command starts with allowed prefixes and doesn't contain
-/// potentially dangerous patterns.
+///
+/// Delegates to the active `CommandScope` (see `command_scope`), which tokenizes
+/// the command and checks each token against a per-program allowlist instead of
+/// a blunt prefix/substring check.
 fn validate_command_security(command: &str) -> Result<(), String> {
-    // Check if the command starts with an allowed prefix
-    let allowed_prefixes = ["npm ", "npx ", "yarn ", "pnpm ", "node "];
-    let is_allowed = allowed_prefixes.iter().any(|prefix| command.starts_with(prefix));
-    
-    if !is_allowed {
-        return Err(format!("Command not allowed: {}. Only npm, npx, yarn, pnpm, and node commands are permitted.", command));
-    }
-    
-    // Check for potentially dangerous patterns
-    let dangerous_patterns = [
-        "&&", "||", ";", "|", ">", "<", "`", "$(",
-        "eval", "exec", "system", "spawn"
-    ];
-    
-    for pattern in dangerous_patterns {
-        if command.contains(pattern) {
-            return Err(format!("Command contains forbidden pattern '{}': {}", pattern, command));
-        }
-    }
-    
-    Ok(())
+    crate::commands::command_scope::validate_command_security(command)
 }
 
 /// Prepare a command for execution
@@ -373,9 +529,24 @@ pub async fn run_node_command_streaming(
 #[tauri::command]
 pub fn cleanup_command_resources(command_id: Option<String>) -> Result<(), String> {
     if let Some(id) = command_id {
+        cancel_supervisor(&id);
         remove_command(&id)
     } else {
+        for id in SUPERVISORS.lock().unwrap().keys().cloned().collect::<Vec<_>>() {
+            cancel_supervisor(&id);
+        }
         clear_commands();
         Ok(())
     }
+}
+
+/// Start a supervised long-lived command (e.g. a dev server) that auto-restarts on crash
+#[tauri::command]
+pub async fn run_supervised_node_command(
+    app_handle: AppHandle,
+    working_dir: String,
+    command: String,
+    command_id: String,
+) -> Result<(), String> {
+    run_supervised_command(&app_handle, Path::new(&working_dir), &command, &command_id).await
 } 
\ No newline at end of file
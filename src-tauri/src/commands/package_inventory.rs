@@ -0,0 +1,71 @@
+//! Inspects a project's `package.json` (and `package-lock.json`, when
+//! present) to avoid re-running installs for dependencies that are already
+//! satisfied.
+//!
+//! Mirrors the way framework/module metadata elsewhere in this crate is read
+//! from JSON on disk: best-effort parsing that falls back to "nothing
+//! installed yet" rather than failing the task if a file is missing or
+//! malformed.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use serde::Deserialize;
+
+#[derive(Debug, Default)]
+pub struct PackageInventory {
+    /// Installed package name -> version string, merged from
+    /// dependencies/devDependencies and, when present, package-lock.json.
+    installed: HashMap<String, String>,
+}
+
+#[derive(Deserialize, Default)]
+struct PackageJson {
+    #[serde(default)]
+    dependencies: HashMap<String, String>,
+    #[serde(default, rename = "devDependencies")]
+    dev_dependencies: HashMap<String, String>,
+}
+
+impl PackageInventory {
+    /// Read `package.json` and `package-lock.json` from `project_dir`.
+    /// Returns an empty inventory if neither file exists or parses.
+    pub fn read(project_dir: &Path) -> Self {
+        let mut installed = HashMap::new();
+
+        if let Ok(content) = std::fs::read_to_string(project_dir.join("package.json")) {
+            if let Ok(pkg) = serde_json::from_str::<PackageJson>(&content) {
+                installed.extend(pkg.dependencies);
+                installed.extend(pkg.dev_dependencies);
+            }
+        }
+
+        if let Ok(content) = std::fs::read_to_string(project_dir.join("package-lock.json")) {
+            if let Ok(value) = serde_json::from_str::<serde_json::Value>(&content) {
+                if let Some(packages) = value.get("packages").and_then(|p| p.as_object()) {
+                    for (path, entry) in packages {
+                        if let Some(name) = path.strip_prefix("node_modules/") {
+                            if let Some(version) = entry.get("version").and_then(|v| v.as_str()) {
+                                installed.insert(name.to_string(), version.to_string());
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        Self { installed }
+    }
+
+    /// Whether `name` is already present in the inventory, ignoring the
+    /// exact requested version range (a best-effort idempotency check, not
+    /// a full semver resolver).
+    pub fn is_satisfied(&self, name: &str) -> bool {
+        self.installed.contains_key(name)
+    }
+
+    /// The resolved version actually installed for `name`, if present.
+    pub fn version_of(&self, name: &str) -> Option<&str> {
+        self.installed.get(name).map(|v| v.as_str())
+    }
+}
@@ -0,0 +1,232 @@
+//! Pluggable package-manager backend.
+//!
+//! `ModuleTask` used to hardcode npm semantics end to end: an npm-flavored
+//! default `package.json`, and commands passed straight through to
+//! `execute_node_command`. This lets the generator pick npm, pnpm, yarn, or
+//! bun -- from `ProjectConfig::package_manager`, or auto-detected from the
+//! project's lockfile -- and translates a module's declared install intent
+//! into that backend's own syntax.
+
+use std::path::Path;
+
+use crate::commands::command_spec::PackageManager;
+
+/// Behavior that differs between package managers.
+pub trait PackageManagerBackend: Send + Sync {
+    /// Which `PackageManager` this backend implements.
+    fn kind(&self) -> PackageManager;
+
+    /// Program + args for installing everything already in `package.json`.
+    fn install_cmd(&self) -> (String, Vec<String>);
+
+    /// Program + args for adding the given packages, as dev dependencies
+    /// when `dev` is set.
+    fn add_cmd(&self, packages: &[String], dev: bool) -> (String, Vec<String>);
+
+    /// Program + args for removing the given packages -- the inverse of
+    /// `add_cmd`, used to uninstall a module's dependencies when it's
+    /// removed from an already-scaffolded project.
+    fn remove_cmd(&self, packages: &[String]) -> (String, Vec<String>);
+
+    /// Program + args for running a one-off binary/script (the `npx tool`
+    /// equivalent for this backend).
+    fn exec_cmd(&self, command: &str, args: &[String]) -> (String, Vec<String>);
+
+    /// Program + args for running a `package.json` script (e.g. `format`,
+    /// `build`) in this backend's idiomatic form.
+    fn run_script_cmd(&self, script: &str) -> (String, Vec<String>);
+
+    /// Program + args for running the project's `test` script. Defaults to
+    /// `run_script_cmd("test")`; overridden where a backend has a more
+    /// idiomatic shorthand (e.g. `npm test` rather than `npm run test`).
+    fn test_cmd(&self) -> (String, Vec<String>) {
+        self.run_script_cmd("test")
+    }
+
+    /// Lockfile name this backend looks for and produces.
+    fn lockfile_name(&self) -> &'static str;
+}
+
+/// Join a `(program, args)` pair into the shell command string
+/// `execute_node_command` expects.
+pub fn command_string(cmd: (String, Vec<String>)) -> String {
+    let (program, args) = cmd;
+    std::iter::once(program).chain(args).collect::<Vec<_>>().join(" ")
+}
+
+pub struct NpmBackend;
+pub struct PnpmBackend;
+pub struct YarnBackend;
+pub struct BunBackend;
+
+impl PackageManagerBackend for NpmBackend {
+    fn kind(&self) -> PackageManager { PackageManager::Npm }
+
+    fn install_cmd(&self) -> (String, Vec<String>) {
+        ("npm".to_string(), vec!["install".to_string()])
+    }
+
+    fn add_cmd(&self, packages: &[String], dev: bool) -> (String, Vec<String>) {
+        let mut args = vec!["install".to_string()];
+        if dev {
+            args.push("-D".to_string());
+        }
+        args.extend(packages.iter().cloned());
+        ("npm".to_string(), args)
+    }
+
+    fn remove_cmd(&self, packages: &[String]) -> (String, Vec<String>) {
+        let mut args = vec!["uninstall".to_string()];
+        args.extend(packages.iter().cloned());
+        ("npm".to_string(), args)
+    }
+
+    fn exec_cmd(&self, command: &str, args: &[String]) -> (String, Vec<String>) {
+        let mut all = vec![command.to_string()];
+        all.extend(args.iter().cloned());
+        ("npx".to_string(), all)
+    }
+
+    fn run_script_cmd(&self, script: &str) -> (String, Vec<String>) {
+        ("npm".to_string(), vec!["run".to_string(), script.to_string()])
+    }
+
+    fn test_cmd(&self) -> (String, Vec<String>) {
+        ("npm".to_string(), vec!["test".to_string()])
+    }
+
+    fn lockfile_name(&self) -> &'static str { "package-lock.json" }
+}
+
+impl PackageManagerBackend for PnpmBackend {
+    fn kind(&self) -> PackageManager { PackageManager::Pnpm }
+
+    fn install_cmd(&self) -> (String, Vec<String>) {
+        ("pnpm".to_string(), vec!["install".to_string()])
+    }
+
+    fn add_cmd(&self, packages: &[String], dev: bool) -> (String, Vec<String>) {
+        let mut args = vec!["add".to_string()];
+        if dev {
+            args.push("--save-dev".to_string());
+        }
+        args.extend(packages.iter().cloned());
+        ("pnpm".to_string(), args)
+    }
+
+    fn remove_cmd(&self, packages: &[String]) -> (String, Vec<String>) {
+        let mut args = vec!["remove".to_string()];
+        args.extend(packages.iter().cloned());
+        ("pnpm".to_string(), args)
+    }
+
+    fn exec_cmd(&self, command: &str, args: &[String]) -> (String, Vec<String>) {
+        let mut all = vec!["exec".to_string(), command.to_string()];
+        all.extend(args.iter().cloned());
+        ("pnpm".to_string(), all)
+    }
+
+    fn run_script_cmd(&self, script: &str) -> (String, Vec<String>) {
+        ("pnpm".to_string(), vec!["run".to_string(), script.to_string()])
+    }
+
+    fn lockfile_name(&self) -> &'static str { "pnpm-lock.yaml" }
+}
+
+impl PackageManagerBackend for YarnBackend {
+    fn kind(&self) -> PackageManager { PackageManager::Yarn }
+
+    fn install_cmd(&self) -> (String, Vec<String>) {
+        ("yarn".to_string(), vec!["install".to_string()])
+    }
+
+    fn add_cmd(&self, packages: &[String], dev: bool) -> (String, Vec<String>) {
+        let mut args = vec!["add".to_string()];
+        if dev {
+            args.push("--dev".to_string());
+        }
+        args.extend(packages.iter().cloned());
+        ("yarn".to_string(), args)
+    }
+
+    fn remove_cmd(&self, packages: &[String]) -> (String, Vec<String>) {
+        let mut args = vec!["remove".to_string()];
+        args.extend(packages.iter().cloned());
+        ("yarn".to_string(), args)
+    }
+
+    fn exec_cmd(&self, command: &str, args: &[String]) -> (String, Vec<String>) {
+        let mut all = vec![command.to_string()];
+        all.extend(args.iter().cloned());
+        ("yarn".to_string(), all)
+    }
+
+    fn run_script_cmd(&self, script: &str) -> (String, Vec<String>) {
+        ("yarn".to_string(), vec![script.to_string()])
+    }
+
+    fn lockfile_name(&self) -> &'static str { "yarn.lock" }
+}
+
+impl PackageManagerBackend for BunBackend {
+    fn kind(&self) -> PackageManager { PackageManager::Bun }
+
+    fn install_cmd(&self) -> (String, Vec<String>) {
+        ("bun".to_string(), vec!["install".to_string()])
+    }
+
+    fn add_cmd(&self, packages: &[String], dev: bool) -> (String, Vec<String>) {
+        let mut args = vec!["add".to_string()];
+        if dev {
+            args.push("-d".to_string());
+        }
+        args.extend(packages.iter().cloned());
+        ("bun".to_string(), args)
+    }
+
+    fn remove_cmd(&self, packages: &[String]) -> (String, Vec<String>) {
+        let mut args = vec!["remove".to_string()];
+        args.extend(packages.iter().cloned());
+        ("bun".to_string(), args)
+    }
+
+    fn exec_cmd(&self, command: &str, args: &[String]) -> (String, Vec<String>) {
+        let mut all = vec![command.to_string()];
+        all.extend(args.iter().cloned());
+        ("bunx".to_string(), all)
+    }
+
+    fn run_script_cmd(&self, script: &str) -> (String, Vec<String>) {
+        ("bun".to_string(), vec!["run".to_string(), script.to_string()])
+    }
+
+    fn lockfile_name(&self) -> &'static str { "bun.lockb" }
+}
+
+/// Detect the package manager a project is already using, by lockfile
+/// presence, checked in priority order `yarn.lock` -> `pnpm-lock.yaml` ->
+/// `bun.lockb` -> `package-lock.json`. Falls back to npm when no lockfile
+/// is found at all.
+pub fn detect(project_dir: &Path) -> Box<dyn PackageManagerBackend> {
+    if project_dir.join("yarn.lock").exists() {
+        Box::new(YarnBackend)
+    } else if project_dir.join("pnpm-lock.yaml").exists() {
+        Box::new(PnpmBackend)
+    } else if project_dir.join("bun.lockb").exists() {
+        Box::new(BunBackend)
+    } else {
+        Box::new(NpmBackend)
+    }
+}
+
+/// Resolve the backend for a project: an explicit, configured package
+/// manager wins; otherwise fall back to lockfile auto-detection.
+pub fn resolve(project_dir: &Path, configured: Option<&PackageManager>) -> Box<dyn PackageManagerBackend> {
+    match configured {
+        Some(PackageManager::Npm) => Box::new(NpmBackend),
+        Some(PackageManager::Pnpm) => Box::new(PnpmBackend),
+        Some(PackageManager::Yarn) => Box::new(YarnBackend),
+        Some(PackageManager::Bun) => Box::new(BunBackend),
+        _ => detect(project_dir),
+    }
+}
@@ -0,0 +1,286 @@
+//! JSON-RPC plugin protocol for third-party framework/module generators
+//!
+//! Plugins are external executables discovered on a plugins path and driven
+//! as long-lived child processes over newline-delimited JSON-RPC on stdin/stdout.
+//! This lets frameworks/modules be added without recompiling Architech.
+
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use log::{debug, error, warn};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::sync::Mutex;
+
+/// A JSON-RPC 2.0 request envelope
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JsonRpcRequest {
+    pub jsonrpc: String,
+    pub method: String,
+    pub params: Value,
+    pub id: u64,
+}
+
+/// A JSON-RPC 2.0 response envelope
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JsonRpcResponse {
+    pub jsonrpc: String,
+    #[serde(default)]
+    pub result: Option<Value>,
+    #[serde(default)]
+    pub error: Option<JsonRpcError>,
+    pub id: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JsonRpcError {
+    pub code: i32,
+    pub message: String,
+}
+
+/// Plugin signature returned from the `describe` handshake
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PluginSignature {
+    pub framework_id: String,
+    pub name: String,
+    pub supported_modules: Vec<String>,
+    #[serde(default)]
+    pub config_options: Vec<String>,
+}
+
+/// A running plugin process
+pub struct Plugin {
+    pub path: PathBuf,
+    pub signature: PluginSignature,
+    child: Child,
+    stdin: std::process::ChildStdin,
+}
+
+impl Plugin {
+    /// Spawn a plugin binary and perform the `describe` handshake
+    pub async fn spawn(path: &Path) -> Result<Self, String> {
+        let mut child = Command::new(path)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| format!("Failed to spawn plugin '{}': {}", path.display(), e))?;
+
+        let stdin = child
+            .stdin
+            .take()
+            .ok_or_else(|| "Plugin has no stdin".to_string())?;
+        let stdout = child
+            .stdout
+            .take()
+            .ok_or_else(|| "Plugin has no stdout".to_string())?;
+
+        let mut plugin = Self {
+            path: path.to_path_buf(),
+            signature: PluginSignature {
+                framework_id: String::new(),
+                name: String::new(),
+                supported_modules: Vec::new(),
+                config_options: Vec::new(),
+            },
+            child,
+            stdin,
+        };
+
+        let request = JsonRpcRequest {
+            jsonrpc: "2.0".to_string(),
+            method: "describe".to_string(),
+            params: Value::Null,
+            id: 0,
+        };
+        plugin.write_request(&request)?;
+
+        let mut reader = BufReader::new(tokio::process::ChildStdout::from_std(stdout)
+            .map_err(|e| format!("Failed to wrap plugin stdout: {}", e))?);
+        let mut line = String::new();
+        reader
+            .read_line(&mut line)
+            .await
+            .map_err(|e| format!("Failed to read plugin describe response: {}", e))?;
+
+        let response: JsonRpcResponse = serde_json::from_str(line.trim())
+            .map_err(|e| format!("Invalid JSON-RPC response from plugin: {}", e))?;
+
+        let signature_value = response
+            .result
+            .ok_or_else(|| "Plugin describe response missing result".to_string())?;
+        plugin.signature = serde_json::from_value(signature_value)
+            .map_err(|e| format!("Invalid plugin signature: {}", e))?;
+
+        debug!("Plugin describe handshake completed: {}", plugin.signature.framework_id);
+
+        Ok(plugin)
+    }
+
+    fn write_request(&mut self, request: &JsonRpcRequest) -> Result<(), String> {
+        let mut line = serde_json::to_string(request)
+            .map_err(|e| format!("Failed to serialize JSON-RPC request: {}", e))?;
+        line.push('\n');
+        self.stdin
+            .write_all(line.as_bytes())
+            .map_err(|e| format!("Failed to write to plugin stdin: {}", e))
+    }
+
+    /// Send a `generate` request and return the final JSON-RPC response.
+    /// Progress/log objects streamed before the terminal response are handed
+    /// to `on_event` so the caller can route them to `state.add_log` / `set_project_status`.
+    pub async fn generate(
+        &mut self,
+        config: &Value,
+        mut on_event: impl FnMut(Value),
+    ) -> Result<Value, String> {
+        let id = next_request_id();
+        let request = JsonRpcRequest {
+            jsonrpc: "2.0".to_string(),
+            method: "generate".to_string(),
+            params: config.clone(),
+            id,
+        };
+        self.write_request(&request)?;
+
+        let stdout = self
+            .child
+            .stdout
+            .take()
+            .ok_or_else(|| "Plugin stdout already consumed".to_string())?;
+        let mut reader = BufReader::new(
+            tokio::process::ChildStdout::from_std(stdout)
+                .map_err(|e| format!("Failed to wrap plugin stdout: {}", e))?,
+        );
+
+        loop {
+            let mut line = String::new();
+            let bytes = reader
+                .read_line(&mut line)
+                .await
+                .map_err(|e| format!("Failed to read from plugin: {}", e))?;
+            if bytes == 0 {
+                return Err("Plugin closed its stdout before responding".to_string());
+            }
+
+            let value: Value = match serde_json::from_str(line.trim()) {
+                Ok(v) => v,
+                Err(e) => {
+                    warn!("Ignoring malformed plugin message: {}", e);
+                    continue;
+                }
+            };
+
+            // A terminal response carries our request id; anything else is a
+            // progress/log notification to forward.
+            if value.get("id").and_then(|v| v.as_u64()) == Some(id) {
+                let response: JsonRpcResponse = serde_json::from_value(value)
+                    .map_err(|e| format!("Invalid terminal plugin response: {}", e))?;
+                if let Some(error) = response.error {
+                    return Err(format!("Plugin error {}: {}", error.code, error.message));
+                }
+                return response
+                    .result
+                    .ok_or_else(|| "Plugin response missing result".to_string());
+            }
+
+            on_event(value);
+        }
+    }
+
+    /// Terminate the plugin process
+    pub fn kill(&mut self) -> Result<(), String> {
+        self.child
+            .kill()
+            .map_err(|e| format!("Failed to kill plugin process: {}", e))
+    }
+}
+
+static NEXT_REQUEST_ID: AtomicU64 = AtomicU64::new(1);
+
+fn next_request_id() -> u64 {
+    NEXT_REQUEST_ID.fetch_add(1, Ordering::Relaxed)
+}
+
+/// Registry of discovered/loaded plugins, keyed by framework id
+pub struct PluginRegistry {
+    plugins: Mutex<HashMap<String, Plugin>>,
+}
+
+impl PluginRegistry {
+    pub fn new() -> Self {
+        Self {
+            plugins: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Discover plugin binaries on the plugins path and load their signatures
+    pub async fn discover(&self, plugins_dir: &Path) -> Result<Vec<PluginSignature>, String> {
+        if !plugins_dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let entries = std::fs::read_dir(plugins_dir)
+            .map_err(|e| format!("Failed to read plugins directory: {}", e))?;
+
+        let mut signatures = Vec::new();
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if !path.is_file() {
+                continue;
+            }
+
+            match Plugin::spawn(&path).await {
+                Ok(plugin) => {
+                    signatures.push(plugin.signature.clone());
+                    let mut plugins = self.plugins.lock().await;
+                    plugins.insert(plugin.signature.framework_id.clone(), plugin);
+                }
+                Err(e) => {
+                    error!("Failed to load plugin '{}': {}", path.display(), e);
+                }
+            }
+        }
+
+        Ok(signatures)
+    }
+
+    /// Run generation through the plugin registered for `framework_id`
+    pub async fn generate(
+        &self,
+        framework_id: &str,
+        config: &Value,
+        on_event: impl FnMut(Value),
+    ) -> Result<Value, String> {
+        let mut plugins = self.plugins.lock().await;
+        let plugin = plugins
+            .get_mut(framework_id)
+            .ok_or_else(|| format!("No plugin registered for framework: {}", framework_id))?;
+        plugin.generate(config, on_event).await
+    }
+
+    /// Signatures for all currently loaded plugins
+    pub async fn signatures(&self) -> Vec<PluginSignature> {
+        let plugins = self.plugins.lock().await;
+        plugins.values().map(|p| p.signature.clone()).collect()
+    }
+}
+
+impl Default for PluginRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// List the signatures of all currently loaded third-party plugins, so their
+/// frameworks/modules can be merged into `get_frameworks`/`get_modules` on the frontend.
+#[tauri::command]
+pub async fn list_plugins(
+    state: tauri::State<'_, std::sync::Arc<crate::state::AppState>>,
+) -> Result<Vec<PluginSignature>, String> {
+    Ok(state.plugins.signatures().await)
+}
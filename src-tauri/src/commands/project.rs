@@ -13,6 +13,57 @@ pub struct ProjectConfig {
     pub modules: Vec<String>,
     pub options: ProjectOptions,
     pub setup_command: Option<String>,
+    /// Optional declarable pipeline of named steps (e.g. create-app -> install-deps ->
+    /// add-module:auth). When present, the generator runs these as scoped node commands
+    /// respecting `depends_on`, in addition to the framework/module tasks.
+    #[serde(default)]
+    pub pipeline: Vec<PipelineStep>,
+    /// Whether a module/module-like task should roll back its file
+    /// operations when it fails partway through, instead of leaving the
+    /// project in a half-modified state.
+    #[serde(default = "default_rollback_on_failure")]
+    pub rollback_on_failure: bool,
+    /// Package manager to install modules with. `None` means auto-detect
+    /// from the project directory's lockfile (see
+    /// `crate::commands::package_manager::detect`).
+    #[serde(default)]
+    pub package_manager: Option<crate::commands::command_spec::PackageManager>,
+    /// Retry policy for network-sensitive cleanup-phase commands (install,
+    /// build). See `crate::commands::node_commands::RetryPolicy`.
+    #[serde(default)]
+    pub retry_policy: crate::commands::node_commands::RetryPolicy,
+    /// Maximum number of tasks to run concurrently. `None` falls back to
+    /// `crate::tasks::DEFAULT_MAX_PARALLEL_TASKS`. See
+    /// `crate::tasks::TaskExecutor::with_max_concurrency`.
+    #[serde(default)]
+    pub max_parallel_tasks: Option<usize>,
+}
+
+fn default_rollback_on_failure() -> bool {
+    true
+}
+
+/// A single named step in a generation pipeline
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PipelineStep {
+    /// Unique step id, e.g. "install-deps" or "add-module:auth"
+    pub id: String,
+    /// The scoped node command to run for this step (validated by `CommandScope`)
+    pub command: String,
+    /// Step ids that must complete before this step runs
+    #[serde(default)]
+    pub depends_on: Vec<String>,
+    /// Extra environment variables for this step
+    #[serde(default)]
+    pub env: std::collections::HashMap<String, String>,
+}
+
+/// Reported status for one pipeline step
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct StepStatus {
+    pub id: String,
+    pub state: String,
+    pub exit_code: Option<i32>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -43,11 +94,16 @@ pub struct ProjectStatusResponse {
     pub path: Option<String>,
     pub error: Option<String>,
     pub resumable: bool,
+    /// Per-step status, letting the UI render a real progress graph for
+    /// declared pipeline steps instead of a single percentage.
+    pub steps: Vec<StepStatus>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct ProjectLogResponse {
     pub timestamp: u64,
+    pub level: crate::state::LogLevel,
+    pub task_id: Option<String>,
     pub message: String,
 }
 
@@ -86,36 +142,28 @@ pub async fn validate_project_config(config: ProjectConfig) -> Result<Validation
 #[command]
 pub async fn generate_project(
     config: ProjectConfig,
+    window: tauri::Window,
     app_handle: tauri::AppHandle,
     state: State<'_, Arc<crate::state::AppState>>,
 ) -> Result<String, String> {
-    // Add extensive debug logs
-    println!("=============================================");
-    println!("GENERATE_PROJECT COMMAND CALLED");
-    println!("Project name: {}", config.name);
-    println!("Project path: {}", config.path);
-    println!("Framework: {}", config.framework);
-    println!("Modules: {:?}", config.modules);
-    println!("=============================================");
-    
-    // Add debug logs
     log::debug!("generate_project command called with config: {:#?}", config);
     log::info!("Starting project generation with name: {}, framework: {}", config.name, config.framework);
-    
+
     // Create a new project_id
     let project_id = Uuid::new_v4().to_string();
-    
-    println!("Generated new project ID: {}", project_id);
+
     log::debug!("Generated new project ID: {}", project_id);
-    
+
+    // Route this project's events to the window that started it, instead
+    // of broadcasting to every open window.
+    state.register_project_window(&project_id, window.label()).await;
+
     // Set project status to preparing
-    println!("Setting project status to Preparing");
     state.set_project_status(&project_id, crate::state::ProjectStatus::Preparing).await;
-    
+
     // Log the start of generation
     info!("Starting project generation: {}", project_id);
-    println!("Adding log entry for project generation start");
-    state.add_log(&project_id, &format!("Starting generation of {} project with framework {}", 
+    state.add_log(&project_id, &format!("Starting generation of {} project with framework {}",
         config.name, config.framework)).await;
     
     // Create project generator
@@ -133,7 +181,6 @@ pub async fn generate_project(
             Ok(project_id)
         },
         Err(e) => {
-            println!("PROJECT INITIALIZATION FAILED: {}", e);
             log::error!("Failed to initialize project: {}", e);
             Err(e)
         }
@@ -146,11 +193,6 @@ pub async fn initialize_project_tasks(
     app_handle: tauri::AppHandle,
     state: State<'_, Arc<crate::state::AppState>>,
 ) -> Result<(), String> {
-    println!("=============================================");
-    println!("INITIALIZE_PROJECT_TASKS COMMAND CALLED");
-    println!("Project ID: {}", param.project_id);
-    println!("=============================================");
-    
     log::debug!("initialize_project_tasks command called for project ID: {}", param.project_id);
     
     // Create project generator
@@ -162,25 +204,52 @@ pub async fn initialize_project_tasks(
     // Initialize tasks and start generation
     match generator.initialize_and_start(&param.project_id).await {
         Ok(_) => {
-            println!("PROJECT TASKS INITIALIZED: {}", param.project_id);
             log::info!("Project tasks initialized successfully for ID: {}", param.project_id);
             Ok(())
         },
         Err(e) => {
-            println!("PROJECT TASK INITIALIZATION FAILED: {}", e);
             log::error!("Failed to initialize project tasks: {}", e);
             Err(e)
         }
     }
 }
 
+/// Collect the reported status of every declared pipeline step (task ids
+/// prefixed `"step:"`, see `PipelineStepTask`) for a project.
+async fn collect_step_statuses(
+    state: &crate::state::AppState,
+    project_id: &str,
+) -> Vec<StepStatus> {
+    let task_states = state.get_all_task_states(project_id).await;
+
+    task_states
+        .into_iter()
+        .filter_map(|(task_id, task_state)| {
+            let step_id = task_id.strip_prefix("step:")?.to_string();
+            let (state_str, exit_code) = match task_state {
+                crate::tasks::TaskState::Pending => ("pending".to_string(), None),
+                crate::tasks::TaskState::Running => ("running".to_string(), None),
+                crate::tasks::TaskState::Completed => ("completed".to_string(), Some(0)),
+                crate::tasks::TaskState::Skipped => ("skipped".to_string(), Some(0)),
+                crate::tasks::TaskState::Failed(_) => ("failed".to_string(), None),
+            };
+            Some(StepStatus {
+                id: step_id,
+                state: state_str,
+                exit_code,
+            })
+        })
+        .collect()
+}
+
 #[command]
 pub async fn get_project_status(
     param: ProjectIdParam,
     state: State<'_, Arc<crate::state::AppState>>,
 ) -> Result<ProjectStatusResponse, String> {
     let status = state.get_project_status(&param.project_id).await;
-    
+    let steps = collect_step_statuses(&state, &param.project_id).await;
+
     // Convert internal status to response
     let response = match status {
         crate::state::ProjectStatus::NotStarted => ProjectStatusResponse {
@@ -190,6 +259,7 @@ pub async fn get_project_status(
             path: None,
             error: None,
             resumable: false,
+            steps,
         },
         crate::state::ProjectStatus::Preparing => ProjectStatusResponse {
             status: "preparing".to_string(),
@@ -198,6 +268,7 @@ pub async fn get_project_status(
             path: None,
             error: None,
             resumable: false,
+            steps,
         },
         crate::state::ProjectStatus::Generating { current_step, progress } => ProjectStatusResponse {
             status: "generating".to_string(),
@@ -206,6 +277,7 @@ pub async fn get_project_status(
             path: None,
             error: None,
             resumable: false,
+            steps,
         },
         crate::state::ProjectStatus::Completed { path } => ProjectStatusResponse {
             status: "completed".to_string(),
@@ -214,6 +286,7 @@ pub async fn get_project_status(
             path: Some(path),
             error: None,
             resumable: false,
+            steps,
         },
         crate::state::ProjectStatus::Failed { error, resumable } => ProjectStatusResponse {
             status: "failed".to_string(),
@@ -222,6 +295,7 @@ pub async fn get_project_status(
             path: None,
             error: Some(error),
             resumable,
+            steps,
         },
         crate::state::ProjectStatus::Cancelled => ProjectStatusResponse {
             status: "cancelled".to_string(),
@@ -230,30 +304,49 @@ pub async fn get_project_status(
             path: None,
             error: Some("Project generation was cancelled".to_string()),
             resumable: false,
+            steps,
         },
     };
-    
+
     Ok(response)
 }
 
 #[command]
 pub async fn get_project_logs(
     param: ProjectIdParam,
+    level: Option<String>,
     state: State<'_, Arc<crate::state::AppState>>,
 ) -> Result<Vec<ProjectLogResponse>, String> {
-    let logs = state.get_logs(&param.project_id).await;
-    
+    let min_level = level
+        .map(|s| crate::state::LogLevel::parse(&s).ok_or_else(|| format!("Invalid log level: '{}'", s)))
+        .transpose()?;
+
+    let logs = state.get_logs_filtered(&param.project_id, min_level).await;
+
     // Convert internal logs to response
     let response = logs.into_iter()
         .map(|log| ProjectLogResponse {
             timestamp: log.timestamp,
+            level: log.level,
+            task_id: log.task_id,
             message: log.message,
         })
         .collect();
-    
+
     Ok(response)
 }
 
+/// Export a project's persisted JSON-lines log file (and a companion
+/// diagnostic dump) for attaching to a bug report. Returns the log
+/// file's path.
+#[command]
+pub async fn export_project_log(
+    param: ProjectIdParam,
+    state: State<'_, Arc<crate::state::AppState>>,
+) -> Result<String, String> {
+    state.export_project_log(&param.project_id).await
+}
+
 #[command]
 pub async fn cancel_project_generation(
     param: ProjectIdParam,
@@ -273,6 +366,7 @@ pub async fn cancel_project_generation(
 #[command]
 pub async fn resume_project_generation(
     param: ProjectIdParam,
+    window: tauri::Window,
     app_handle: tauri::AppHandle,
     state: State<'_, Arc<crate::state::AppState>>,
 ) -> Result<(), String> {
@@ -280,7 +374,11 @@ pub async fn resume_project_generation(
     if !state.can_resume(&param.project_id).await {
         return Err("Project cannot be resumed".to_string());
     }
-    
+
+    // A resume may be kicked off from a different window than the one that
+    // started the original run; re-route this project's events to it.
+    state.register_project_window(&param.project_id, window.label()).await;
+
     // Create project generator
     let generator = crate::generation::ProjectGenerator::new(
         app_handle.clone(),
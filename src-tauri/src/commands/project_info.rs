@@ -0,0 +1,307 @@
+//! "Doctor" view of an already-generated project: what was actually
+//! produced, as opposed to what the `Framework`/`Module` definitions
+//! requested.
+//!
+//! Reads the project's `package.json`, its lockfile, the module install
+//! record written by `ModuleTask`/`add_module_to_project`
+//! (`.architech/modules.json`), and -- for Rust/Tauri projects -- any
+//! `Cargo.lock`, then shells out for the installed node/package-manager
+//! versions via `execute_node_command`.
+//!
+//! When `.architech/modules.json` is present, its record of what was
+//! installed is cross-checked against `package.json`/`package-lock.json`
+//! (via `PackageInventory`) to flag drift -- a module marked installed
+//! whose packages are gone, or one whose declared dependency is missing.
+//! When it isn't, framework and module detection fall back to inferring
+//! composition straight from the manifests, so a project can still be
+//! audited after its tracking metadata is lost.
+
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use tauri::{command, AppHandle, Emitter};
+
+use super::framework::{get_frameworks, get_modules, Framework, Module};
+use super::module_lockfile::ModulesLockfile;
+use super::node_commands::execute_node_command;
+use super::package_inventory::PackageInventory;
+use super::package_manager;
+
+/// A module this crate installed, alongside the version the `Module`
+/// definition currently declares -- lets the frontend flag drift between
+/// what's defined and what's actually on disk.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ModuleVersionReport {
+    pub id: String,
+    pub installed_version: String,
+    pub declared_version: Option<String>,
+}
+
+/// One `[[package]]` entry from a `Cargo.lock`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CargoLockPackage {
+    pub name: String,
+    pub version: String,
+    pub source: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct ProjectInfo {
+    pub framework: Option<String>,
+    pub package_manager: Option<String>,
+    pub package_manager_version: Option<String>,
+    pub node_version: Option<String>,
+    pub modules: Vec<ModuleVersionReport>,
+    pub cargo_packages: Vec<CargoLockPackage>,
+    /// Drift flagged by cross-checking `.architech/modules.json` against
+    /// what `package.json`/`package-lock.json` actually have installed --
+    /// e.g. a module recorded as installed whose packages are gone, or a
+    /// module's declared dependency that isn't itself installed. Empty when
+    /// no lockfile is present to cross-check, or nothing looks wrong.
+    pub mismatches: Vec<String>,
+}
+
+/// Infer which known modules are installed by checking whether every
+/// package `module.installation.commands` would ask a package manager to
+/// install is already present in `inventory`. This doesn't require the
+/// `.architech/modules.json` lockfile at all, so it still works for
+/// auditing a project whose tracking metadata is missing or stale.
+fn infer_installed_modules(inventory: &PackageInventory, modules: &[Module]) -> Vec<ModuleVersionReport> {
+    modules
+        .iter()
+        .filter_map(|module| {
+            let packages = module
+                .installation
+                .commands
+                .iter()
+                .flat_map(|cmd| cmd.requested_packages())
+                .collect::<Vec<_>>();
+            if packages.is_empty() || !packages.iter().all(|pkg| inventory.is_satisfied(pkg)) {
+                return None;
+            }
+            let installed_version = packages
+                .iter()
+                .find_map(|pkg| inventory.version_of(pkg))
+                .unwrap_or(&module.version)
+                .to_string();
+            Some(ModuleVersionReport {
+                id: module.id.clone(),
+                installed_version,
+                declared_version: Some(module.version.clone()),
+            })
+        })
+        .collect()
+}
+
+/// Infer the framework a project was generated with by picking whichever
+/// known `Framework` has the most overlap between its `compatible_modules`
+/// and the modules `infer_installed_modules` detected -- frameworks don't
+/// carry an npm package name of their own, so there's no direct manifest
+/// match, but the module overlap is usually decisive in practice.
+fn infer_framework<'a>(frameworks: &'a [Framework], installed_module_ids: &[String]) -> Option<&'a Framework> {
+    frameworks
+        .iter()
+        .map(|framework| {
+            let overlap = framework
+                .compatible_modules
+                .iter()
+                .filter(|id| installed_module_ids.contains(id))
+                .count();
+            (framework, overlap)
+        })
+        .filter(|(_, overlap)| *overlap > 0)
+        .max_by_key(|(_, overlap)| *overlap)
+        .map(|(framework, _)| framework)
+}
+
+/// Cross-check the lockfile's view of what's installed against what
+/// `package.json`/`package-lock.json` actually have on disk, flagging any
+/// recorded module whose packages are missing plus any installed module
+/// whose declared dependency module isn't itself installed. Packages on
+/// disk that no recorded module accounts for are not flagged.
+fn find_mismatches(inventory: &PackageInventory, all_modules: &[Module], recorded: &[ModuleVersionReport]) -> Vec<String> {
+    let mut mismatches = Vec::new();
+    let recorded_ids: Vec<&str> = recorded.iter().map(|m| m.id.as_str()).collect();
+
+    for report in recorded {
+        let Some(module) = all_modules.iter().find(|m| m.id == report.id) else {
+            continue;
+        };
+        let packages = module
+            .installation
+            .commands
+            .iter()
+            .flat_map(|cmd| cmd.requested_packages())
+            .collect::<Vec<_>>();
+        if !packages.is_empty() && !packages.iter().all(|pkg| inventory.is_satisfied(pkg)) {
+            mismatches.push(format!(
+                "Module '{}' is recorded as installed but its package(s) are missing from package.json -- possibly removed by hand",
+                report.id
+            ));
+        }
+
+        for dep in &module.dependencies {
+            if !recorded_ids.contains(&dep.as_str()) {
+                mismatches.push(format!(
+                    "Module '{}' depends on '{}', which is not installed",
+                    report.id, dep
+                ));
+            }
+        }
+    }
+
+    mismatches
+}
+
+/// Parse a `Cargo.lock`'s `[[package]]` entries by hand: the repo has no
+/// TOML dependency to reach for, and the subset of the format used here
+/// (flat string fields inside array-of-table blocks) is simple enough to
+/// walk line by line.
+fn parse_cargo_lock(content: &str) -> Vec<CargoLockPackage> {
+    let mut packages = Vec::new();
+    let mut in_package = false;
+    let mut name: Option<String> = None;
+    let mut version: Option<String> = None;
+    let mut source: Option<String> = None;
+
+    let flush = |name: &mut Option<String>, version: &mut Option<String>, source: &mut Option<String>, out: &mut Vec<CargoLockPackage>| {
+        if let (Some(n), Some(v)) = (name.take(), version.take()) {
+            out.push(CargoLockPackage { name: n, version: v, source: source.take() });
+        }
+    };
+
+    for line in content.lines() {
+        let line = line.trim();
+        if line == "[[package]]" {
+            flush(&mut name, &mut version, &mut source, &mut packages);
+            in_package = true;
+            continue;
+        }
+        if line.starts_with('[') {
+            flush(&mut name, &mut version, &mut source, &mut packages);
+            in_package = false;
+            continue;
+        }
+        if !in_package {
+            continue;
+        }
+        if let Some(value) = line.strip_prefix("name = ") {
+            name = Some(value.trim_matches('"').to_string());
+        } else if let Some(value) = line.strip_prefix("version = ") {
+            version = Some(value.trim_matches('"').to_string());
+        } else if let Some(value) = line.strip_prefix("source = ") {
+            source = Some(value.trim_matches('"').to_string());
+        }
+    }
+    flush(&mut name, &mut version, &mut source, &mut packages);
+
+    packages
+}
+
+/// Run `<program> --version` and return the trimmed stdout, or `None` if
+/// the command fails (e.g. the tool isn't installed).
+async fn tool_version(app_handle: &AppHandle, project_dir: &Path, program: &str) -> Option<String> {
+    execute_node_command(app_handle, project_dir, &format!("{} --version", program), None)
+        .await
+        .ok()
+        .filter(|r| r.success)
+        .map(|r| r.stdout.trim().to_string())
+}
+
+/// Inspect a generated project and report what was actually resolved:
+/// the framework it was generated with, the package manager and node
+/// version available in its environment, the resolved version of every
+/// module this crate installed (or, absent a lockfile, every module
+/// `infer_installed_modules` can detect from the manifests), any drift
+/// between what's recorded and what's on disk, and (for Rust/Tauri
+/// projects) the dependency graph recorded in `Cargo.lock`.
+///
+/// Emits the same report as a line-by-line `"log-message"` summary
+/// followed by a structured `"project-info"` event, so a long-running
+/// "doctor" check can be kicked off without blocking on the command's
+/// return value.
+#[command]
+pub async fn get_project_info(project_dir: String, app_handle: AppHandle) -> Result<ProjectInfo, String> {
+    let project_path = Path::new(&project_dir);
+    if !project_path.exists() {
+        return Err(format!("Project directory '{}' does not exist", project_dir));
+    }
+
+    let mut info = ProjectInfo::default();
+    let declared_modules = get_modules().await?;
+    let inventory = PackageInventory::read(project_path);
+
+    if let Some(lockfile) = ModulesLockfile::load(project_path)? {
+        info.framework = Some(lockfile.framework.clone());
+        info.modules = lockfile
+            .modules
+            .iter()
+            .map(|installed| ModuleVersionReport {
+                id: installed.id.clone(),
+                installed_version: installed.version.clone(),
+                declared_version: declared_modules
+                    .iter()
+                    .find(|m| m.id == installed.id)
+                    .map(|m| m.version.clone()),
+            })
+            .collect();
+        info.mismatches = find_mismatches(&inventory, &declared_modules, &info.modules);
+    } else {
+        // No lockfile to cross-check against -- fall back to inferring
+        // composition straight from package.json/package-lock.json, so
+        // this still works on a project whose `.architech/modules.json`
+        // is missing or never existed.
+        info.modules = infer_installed_modules(&inventory, &declared_modules);
+        let installed_ids: Vec<String> = info.modules.iter().map(|m| m.id.clone()).collect();
+        let frameworks = get_frameworks().await?;
+        info.framework = infer_framework(&frameworks, &installed_ids).map(|f| f.id.clone());
+    }
+
+    let backend = package_manager::detect(project_path);
+    let program = backend.install_cmd().0;
+    info.package_manager = Some(program.clone());
+    info.package_manager_version = tool_version(&app_handle, project_path, &program).await;
+    info.node_version = tool_version(&app_handle, project_path, "node").await;
+
+    let cargo_lock_path = project_path.join("Cargo.lock");
+    if cargo_lock_path.exists() {
+        let content = fs::read_to_string(&cargo_lock_path)
+            .map_err(|e| format!("Failed to read '{}': {}", cargo_lock_path.display(), e))?;
+        info.cargo_packages = parse_cargo_lock(&content);
+    }
+
+    let summary = format!(
+        "Doctor report: framework={}, {} module(s) detected{}",
+        info.framework.as_deref().unwrap_or("unknown"),
+        info.modules.len(),
+        if info.mismatches.is_empty() {
+            String::new()
+        } else {
+            format!(", {} mismatch(es) flagged", info.mismatches.len())
+        }
+    );
+    app_handle.emit("log-message", &summary).ok();
+    for module in &info.modules {
+        app_handle
+            .emit(
+                "log-message",
+                format!(
+                    "  - {}: installed {} (declared {})",
+                    module.id,
+                    module.installed_version,
+                    module.declared_version.as_deref().unwrap_or("unknown")
+                ),
+            )
+            .ok();
+    }
+    for mismatch in &info.mismatches {
+        app_handle.emit("log-message", format!("  ! {}", mismatch)).ok();
+    }
+
+    if let Err(e) = app_handle.emit("project-info", &info) {
+        log::warn!("Failed to emit project-info event: {}", e);
+    }
+
+    Ok(info)
+}
@@ -0,0 +1,65 @@
+//! Reproducible lockfile at `<project_dir>/architech.lock`: the exact
+//! framework/module versions a scaffold actually resolved to, read back
+//! from the installed `package.json`/lockfiles rather than the values
+//! `Framework`/`Module` definitions merely declare.
+//!
+//! Written by `tasks::LockfileTask` once a scaffold finishes. Re-read by
+//! `ProjectGenerator::create_tasks` on the next scaffold of the same
+//! project, so a module whose resolved version has drifted since the lock
+//! was written can be flagged instead of silently swapped in.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+const LOCK_FILE_NAME: &str = "architech.lock";
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct LockedModule {
+    pub id: String,
+    pub version: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ProjectLock {
+    pub framework: String,
+    pub framework_version: String,
+    #[serde(default)]
+    pub modules: Vec<LockedModule>,
+}
+
+impl ProjectLock {
+    fn path(project_dir: &Path) -> PathBuf {
+        project_dir.join(LOCK_FILE_NAME)
+    }
+
+    /// Load the lock at `project_dir`, or `None` if the project has never
+    /// been scaffolded with lockfile tracking.
+    pub fn load(project_dir: &Path) -> Result<Option<Self>, String> {
+        let path = Self::path(project_dir);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read lockfile '{}': {}", path.display(), e))?;
+        serde_json::from_str(&content)
+            .map(Some)
+            .map_err(|e| format!("Failed to parse lockfile '{}': {}", path.display(), e))
+    }
+
+    /// The locked version for `module_id`, if it was part of the scaffold
+    /// this lock was written for.
+    pub fn version_of(&self, module_id: &str) -> Option<&str> {
+        self.modules.iter().find(|m| m.id == module_id).map(|m| m.version.as_str())
+    }
+
+    /// Serialize and write this lock to `<project_dir>/architech.lock`.
+    pub fn save(&self, project_dir: &Path) -> Result<(), String> {
+        let path = Self::path(project_dir);
+        let rendered = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize lockfile: {}", e))?;
+        fs::write(&path, rendered)
+            .map_err(|e| format!("Failed to write lockfile '{}': {}", path.display(), e))
+    }
+}
@@ -0,0 +1,267 @@
+//! Parameterized path routing for generated-file destinations.
+//!
+//! `FileOperation::destination` (see `template.rs`) is a literal string, so a
+//! module that wants to place a generated file under e.g. an entity-specific
+//! directory has to build that path itself with ad-hoc string formatting.
+//! This gives blueprint authors a single declarative place to do that
+//! instead, in the router-syntax style of path-tree: register a set of
+//! patterns once, match a concrete path against them to recover its
+//! parameters, or expand a pattern back into a concrete path from a
+//! parameter map.
+//!
+//! Pattern syntax, one level of precedence below string literals:
+//! - `:name` -- exactly one path segment, may not contain `/`.
+//! - `:name?` -- the same, but optional; the segment may be absent entirely.
+//! - `:name*` / `:name+` -- zero-or-more / one-or-more segments, which may
+//!   themselves contain `/` (captured as a single `/`-joined string). Must
+//!   be the pattern's last segment.
+//! - `*` -- an unnamed catch-all, captured under the parameter name `"*"`.
+//!   Must be the pattern's last segment.
+//!
+//! Matching precedence at each position is static > `:name` (required or
+//! optional) > `:name*`/`:name+`/`*`, so a literal segment always wins over
+//! a parameter that could also match it.
+
+use std::collections::HashMap;
+
+/// One parsed segment of a route pattern.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    Static(String),
+    Param(String),
+    ParamOptional(String),
+    /// `(name, one_or_more)` -- `:name*` is `(name, false)`, `:name+` is
+    /// `(name, true)`.
+    ParamMulti(String, bool),
+    Wildcard,
+}
+
+fn parse_pattern(pattern: &str) -> Result<Vec<Segment>, String> {
+    let segments: Vec<Segment> = pattern
+        .split('/')
+        .filter(|s| !s.is_empty())
+        .map(|raw| {
+            if raw == "*" {
+                Segment::Wildcard
+            } else if let Some(name) = raw.strip_prefix(':') {
+                if let Some(base) = name.strip_suffix('?') {
+                    Segment::ParamOptional(base.to_string())
+                } else if let Some(base) = name.strip_suffix('*') {
+                    Segment::ParamMulti(base.to_string(), false)
+                } else if let Some(base) = name.strip_suffix('+') {
+                    Segment::ParamMulti(base.to_string(), true)
+                } else {
+                    Segment::Param(name.to_string())
+                }
+            } else {
+                Segment::Static(raw.to_string())
+            }
+        })
+        .collect();
+
+    for (i, segment) in segments.iter().enumerate() {
+        let is_last = i + 1 == segments.len();
+        if !is_last && matches!(segment, Segment::ParamMulti(..) | Segment::Wildcard) {
+            return Err(format!(
+                "invalid route pattern '{}': a `:name*`/`:name+`/`*` segment must be the last segment",
+                pattern
+            ));
+        }
+    }
+
+    Ok(segments)
+}
+
+/// A node of the prefix tree routes are compiled into. Each edge kind is
+/// tried in match-priority order (static, then `:name`/`:name?`, then the
+/// catch-all kinds), not stored in one combined collection, so that order
+/// doesn't have to be re-derived at match time.
+#[derive(Default)]
+struct Node {
+    statics: HashMap<String, Node>,
+    param: Option<(String, Box<Node>)>,
+    param_optional: Option<(String, Box<Node>)>,
+    /// `:name*`/`:name+` are terminal -- nothing can follow them in a valid
+    /// pattern -- so they carry their route id directly instead of a child.
+    param_multi: Option<(String, bool, String)>,
+    /// Likewise terminal; `*` is unnamed, captured under the key `"*"`.
+    wildcard: Option<String>,
+    route_id: Option<String>,
+}
+
+fn insert(node: &mut Node, route_id: &str, segments: &[Segment]) {
+    match segments.first() {
+        None => node.route_id = Some(route_id.to_string()),
+        Some(Segment::Static(s)) => {
+            insert(node.statics.entry(s.clone()).or_default(), route_id, &segments[1..]);
+        }
+        Some(Segment::Param(name)) => {
+            let child = &mut node.param.get_or_insert_with(|| (name.clone(), Box::default())).1;
+            insert(child, route_id, &segments[1..]);
+        }
+        Some(Segment::ParamOptional(name)) => {
+            // The segment can also be absent, so the rest of the pattern
+            // also has to be reachable directly from this node -- sharing
+            // it with the "present" branch below is exactly what lets
+            // `/a/:b?/c` match both `/a/c` and `/a/x/c`.
+            insert(node, route_id, &segments[1..]);
+            let child = &mut node.param_optional.get_or_insert_with(|| (name.clone(), Box::default())).1;
+            insert(child, route_id, &segments[1..]);
+        }
+        Some(Segment::ParamMulti(name, one_or_more)) => {
+            node.param_multi = Some((name.clone(), *one_or_more, route_id.to_string()));
+        }
+        Some(Segment::Wildcard) => {
+            node.wildcard = Some(route_id.to_string());
+        }
+    }
+}
+
+fn try_match(node: &Node, remaining: &[&str], params: &mut HashMap<String, String>) -> Option<String> {
+    if remaining.is_empty() {
+        if let Some(id) = &node.route_id {
+            return Some(id.clone());
+        }
+        // `:name*` (zero-or-more) is also satisfied by nothing left to match.
+        if let Some((name, false, id)) = &node.param_multi {
+            params.insert(name.clone(), String::new());
+            return Some(id.clone());
+        }
+        return None;
+    }
+
+    let (seg, rest) = (remaining[0], &remaining[1..]);
+
+    if let Some(child) = node.statics.get(seg) {
+        let mut attempt = params.clone();
+        if let Some(id) = try_match(child, rest, &mut attempt) {
+            *params = attempt;
+            return Some(id);
+        }
+    }
+
+    if let Some((name, child)) = &node.param {
+        let mut attempt = params.clone();
+        attempt.insert(name.clone(), seg.to_string());
+        if let Some(id) = try_match(child, rest, &mut attempt) {
+            *params = attempt;
+            return Some(id);
+        }
+    }
+
+    if let Some((name, child)) = &node.param_optional {
+        let mut attempt = params.clone();
+        attempt.insert(name.clone(), seg.to_string());
+        if let Some(id) = try_match(child, rest, &mut attempt) {
+            *params = attempt;
+            return Some(id);
+        }
+    }
+
+    if let Some((name, _one_or_more, id)) = &node.param_multi {
+        params.insert(name.clone(), remaining.join("/"));
+        return Some(id.clone());
+    }
+
+    if let Some(id) = &node.wildcard {
+        params.insert("*".to_string(), remaining.join("/"));
+        return Some(id.clone());
+    }
+
+    None
+}
+
+/// The outcome of a successful `Router::matches` call.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RouteMatch {
+    pub route_id: String,
+    pub params: HashMap<String, String>,
+}
+
+/// A set of route patterns compiled into a prefix tree, for matching
+/// concrete paths against and expanding parameters back into paths.
+#[derive(Default)]
+pub struct Router {
+    root: Node,
+    // Original segments per route id, kept around for `expand` -- the tree
+    // itself only records enough to find the *id* a path matches, not how
+    // to rebuild a path from one.
+    routes: HashMap<String, Vec<Segment>>,
+}
+
+impl Router {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register `pattern` under `route_id`. Route ids must be unique:
+    /// registering the same id twice makes `expand` use the new pattern,
+    /// but the earlier pattern stays in the match tree under the same id.
+    pub fn add(&mut self, route_id: impl Into<String>, pattern: &str) -> Result<(), String> {
+        let segments = parse_pattern(pattern)?;
+        let route_id = route_id.into();
+        insert(&mut self.root, &route_id, &segments);
+        self.routes.insert(route_id, segments);
+        Ok(())
+    }
+
+    /// Match `path` against the registered patterns, returning the winning
+    /// route id and its captured parameters, or `None` if nothing matches.
+    pub fn matches(&self, path: &str) -> Option<RouteMatch> {
+        let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+        let mut params = HashMap::new();
+        let route_id = try_match(&self.root, &segments, &mut params)?;
+        Some(RouteMatch { route_id, params })
+    }
+
+    /// Rebuild a concrete path from `route_id`'s pattern and `params`.
+    /// Rejects a value containing `/` for a `:name`/`:name?` slot, since
+    /// those are defined to match (and therefore must expand back to)
+    /// exactly one path segment.
+    pub fn expand(&self, route_id: &str, params: &HashMap<String, String>) -> Result<String, String> {
+        let segments = self
+            .routes
+            .get(route_id)
+            .ok_or_else(|| format!("unknown route id: {}", route_id))?;
+
+        let mut parts = Vec::new();
+        for segment in segments {
+            match segment {
+                Segment::Static(s) => parts.push(s.clone()),
+                Segment::Param(name) => {
+                    let value = params
+                        .get(name)
+                        .ok_or_else(|| format!("missing parameter '{}' for route '{}'", name, route_id))?;
+                    if value.contains('/') {
+                        return Err(format!("parameter '{}' must be a single path segment, got '{}'", name, value));
+                    }
+                    parts.push(value.clone());
+                }
+                Segment::ParamOptional(name) => {
+                    if let Some(value) = params.get(name).filter(|v| !v.is_empty()) {
+                        if value.contains('/') {
+                            return Err(format!("parameter '{}' must be a single path segment, got '{}'", name, value));
+                        }
+                        parts.push(value.clone());
+                    }
+                }
+                Segment::ParamMulti(name, one_or_more) => {
+                    let value = params.get(name).cloned().unwrap_or_default();
+                    if *one_or_more && value.is_empty() {
+                        return Err(format!("parameter '{}' requires at least one path segment", name));
+                    }
+                    if !value.is_empty() {
+                        parts.push(value.clone());
+                    }
+                }
+                Segment::Wildcard => {
+                    if let Some(value) = params.get("*").filter(|v| !v.is_empty()) {
+                        parts.push(value.clone());
+                    }
+                }
+            }
+        }
+
+        Ok(format!("/{}", parts.join("/")))
+    }
+}
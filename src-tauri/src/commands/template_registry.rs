@@ -0,0 +1,359 @@
+//! Multi-source catalog for framework/module definitions.
+//!
+//! `get_frameworks`/`get_modules` used to read from one hardcoded resource
+//! layout (see the old `read_json_from_data` search path), which left users
+//! with no way to add their own framework/module definitions or point the
+//! app at a shared registry. `TemplateRegistry` replaces that single path
+//! with an ordered list of `TemplateSource`s -- bundled resources, the
+//! user's own `~/.architech/templates` directory, and a remote registry
+//! cached locally by `refresh_registry` -- merged by `id`, where a later
+//! source's entry overrides an earlier one sharing the same id, and
+//! `version` breaks ties between entries contributed by the same source
+//! (e.g. two bundled files both defining `id: "nextjs"`).
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+use tauri::command;
+
+use super::framework::{Framework, Module};
+
+/// Where a batch of framework/module definitions comes from, in priority
+/// order -- later sources in a `TemplateRegistry` win ties by `id`.
+#[derive(Debug, Clone)]
+pub enum TemplateSource {
+    /// JSON files shipped alongside the app (or found via the executable's
+    /// resources directory / dev-mode fallbacks -- see `bundled_search_dirs`).
+    Bundled,
+    /// A user-maintained directory, so custom or locally-edited
+    /// frameworks/modules survive an app update.
+    UserDir(PathBuf),
+    /// A remote registry index, already fetched and cached to disk by
+    /// `refresh_registry`. Reading this source never makes a network call;
+    /// it only ever reads the cache, so the registry still loads offline.
+    Remote { cache_file: PathBuf },
+}
+
+#[derive(Serialize, Deserialize, Default)]
+struct RemoteIndex {
+    #[serde(default)]
+    frameworks: Vec<Framework>,
+    #[serde(default)]
+    modules: Vec<Module>,
+}
+
+/// Default fallback framework, used only when every source above returns
+/// nothing -- keeps a fresh install usable before any templates exist.
+fn fallback_framework() -> Framework {
+    use super::framework::{DirectoryStructure, FrameworkCli};
+
+    Framework {
+        id: "nextjs".to_string(),
+        name: "Next.js".to_string(),
+        description: "React framework for production".to_string(),
+        version: "13.4.0".to_string(),
+        framework_type: "web".to_string(),
+        tags: vec!["react".to_string(), "typescript".to_string(), "frontend".to_string()],
+        cli: FrameworkCli {
+            base_command: "npx create-next-app@latest".to_string(),
+            arguments: serde_json::Map::new(),
+            interactive: false,
+            responses: Vec::new(),
+            platform_commands: std::collections::HashMap::new(),
+        },
+        compatible_modules: vec!["tailwind".to_string(), "i18n".to_string()],
+        directory_structure: DirectoryStructure {
+            enforced: true,
+            directories: vec!["src".to_string(), "public".to_string()],
+        },
+        fetch: vec![],
+    }
+}
+
+/// Default fallback module, mirrored from the same built-in Tailwind
+/// definition `get_modules` has always fallen back to.
+fn fallback_module() -> Module {
+    use super::framework::{FileOperation, ModuleConfiguration, ModuleInstallation, ModuleOption};
+
+    Module {
+        id: "tailwind".to_string(),
+        name: "Tailwind CSS".to_string(),
+        description: "A utility-first CSS framework".to_string(),
+        version: "3.3.2".to_string(),
+        category: "styling".to_string(),
+        dependencies: vec![],
+        incompatible_with: vec![],
+        installation: ModuleInstallation {
+            commands: vec![
+                crate::commands::command_spec::CommandSpec::builder(
+                    crate::commands::command_spec::PackageManager::Npm,
+                    "install",
+                )
+                .args(["-D", "tailwindcss", "postcss", "autoprefixer"])
+                .critical(true)
+                .allow_failure(false)
+                .build(),
+                crate::commands::command_spec::CommandSpec::builder(
+                    crate::commands::command_spec::PackageManager::Npx,
+                    "tailwindcss",
+                )
+                .args(["init", "-p"])
+                .critical(false)
+                .allow_failure(true)
+                .build(),
+            ],
+            file_operations: vec![FileOperation {
+                operation: "create".to_string(),
+                path: "src/styles/globals.css".to_string(),
+                content: "@tailwind base;\n@tailwind components;\n@tailwind utilities;".to_string(),
+                pattern: String::new(),
+                replacement: String::new(),
+                action: String::new(),
+                import: String::new(),
+                merge_strategy: "last-write-wins".to_string(),
+            }],
+        },
+        configuration: ModuleConfiguration {
+            options: vec![ModuleOption {
+                id: "jit".to_string(),
+                option_type: "boolean".to_string(),
+                label: "JIT Mode".to_string(),
+                description: "Enable JIT mode".to_string(),
+                default: serde_json::json!(true),
+                choices: vec![],
+            }],
+        },
+        fetch: vec![],
+    }
+}
+
+/// Bundled framework/module file names, relative to each of
+/// `bundled_search_dirs`. `pub(crate)` so `template_schema` can walk the same
+/// set of files when validating.
+pub(crate) const FRAMEWORK_FILES: &[&str] = &["frameworks/web.json", "frameworks/app.json", "frameworks/desktop.json"];
+pub(crate) const MODULE_FILES: &[&str] = &[
+    "modules/styling.json",
+    "modules/ui.json",
+    "modules/state.json",
+    "modules/i18n.json",
+    "modules/forms.json",
+    "modules/testing.json",
+    "modules/advanced.json",
+];
+
+/// The set of candidate directories the bundled resources might live under
+/// -- same search order `read_json_from_data` used to hardcode: next to the
+/// executable, the OS config directory, and (for `cargo tauri dev`, where
+/// the working directory is `src-tauri`) one level up or the working
+/// directory itself.
+pub(crate) fn bundled_search_dirs() -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+
+    if let Some(exe_dir) = std::env::current_exe().ok().and_then(|p| p.parent().map(|p| p.to_path_buf())) {
+        dirs.push(exe_dir.join("resources").join("data"));
+    }
+
+    if let Some(config_dir) = dirs::config_dir() {
+        dirs.push(config_dir.join(env!("CARGO_PKG_NAME")).join("data"));
+    }
+
+    let current_dir = std::env::current_dir().unwrap_or_default();
+    if let Some(parent) = current_dir.parent() {
+        dirs.push(parent.join("data"));
+    }
+    dirs.push(current_dir.join("data"));
+
+    dirs
+}
+
+/// Directory the user's own framework/module JSON lives in, plus the cache
+/// for a fetched remote registry index.
+pub fn user_template_dir() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| home.join(".architech").join("templates"))
+}
+
+fn read_json_files<T: for<'de> Deserialize<'de>>(dir: &std::path::Path) -> Vec<T> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().and_then(|e| e.to_str()) == Some("json"))
+        .filter_map(|path| std::fs::read_to_string(&path).ok())
+        .filter_map(|content| serde_json::from_str::<Vec<T>>(&content).ok())
+        .flatten()
+        .collect()
+}
+
+impl TemplateSource {
+    fn frameworks(&self) -> Vec<Framework> {
+        match self {
+            TemplateSource::Bundled => bundled_search_dirs()
+                .iter()
+                .flat_map(|dir| {
+                    FRAMEWORK_FILES.iter().filter_map(move |file| std::fs::read_to_string(dir.join(file)).ok())
+                })
+                .filter_map(|content| serde_json::from_str::<Vec<Framework>>(&content).ok())
+                .flatten()
+                .collect(),
+            TemplateSource::UserDir(dir) => read_json_files(&dir.join("frameworks")),
+            TemplateSource::Remote { cache_file } => read_remote_index(cache_file).frameworks,
+        }
+    }
+
+    fn modules(&self) -> Vec<Module> {
+        match self {
+            TemplateSource::Bundled => bundled_search_dirs()
+                .iter()
+                .flat_map(|dir| {
+                    MODULE_FILES.iter().filter_map(move |file| std::fs::read_to_string(dir.join(file)).ok())
+                })
+                .filter_map(|content| serde_json::from_str::<Vec<Module>>(&content).ok())
+                .flatten()
+                .collect(),
+            TemplateSource::UserDir(dir) => read_json_files(&dir.join("modules")),
+            TemplateSource::Remote { cache_file } => read_remote_index(cache_file).modules,
+        }
+    }
+}
+
+fn read_remote_index(cache_file: &std::path::Path) -> RemoteIndex {
+    std::fs::read_to_string(cache_file)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+/// Merge entries from each source by `id`: a source later in `ranked`
+/// replaces an earlier one's entry for the same id outright, and within the
+/// same source/rank, the higher `version` wins.
+fn merge_by_id<T>(
+    ranked: Vec<(usize, Vec<T>)>,
+    id_of: impl Fn(&T) -> &str,
+    version_of: impl Fn(&T) -> &str,
+) -> Vec<T> {
+    let mut best: HashMap<String, (usize, T)> = HashMap::new();
+
+    for (rank, items) in ranked {
+        for item in items {
+            let id = id_of(&item).to_string();
+            match best.get(&id) {
+                Some((existing_rank, existing_item))
+                    if rank < *existing_rank
+                        || (rank == *existing_rank && !version_is_newer(version_of(&item), version_of(existing_item))) =>
+                {
+                    continue;
+                }
+                _ => {
+                    best.insert(id, (rank, item));
+                }
+            }
+        }
+    }
+
+    best.into_values().map(|(_, item)| item).collect()
+}
+
+/// Best-effort dotted-numeric version comparison (`"1.10.0" > "1.9.0"`),
+/// falling back to a plain string comparison for non-numeric segments.
+fn version_is_newer(candidate: &str, current: &str) -> bool {
+    let parse = |v: &str| -> Vec<u32> { v.split('.').map(|part| part.parse().unwrap_or(0)).collect() };
+    let (candidate_parts, current_parts) = (parse(candidate), parse(current));
+    if candidate_parts != current_parts {
+        candidate_parts > current_parts
+    } else {
+        candidate > current
+    }
+}
+
+/// An ordered list of `TemplateSource`s, merged by id on every read.
+pub struct TemplateRegistry {
+    sources: Vec<TemplateSource>,
+}
+
+impl TemplateRegistry {
+    /// The default registry: bundled resources, then the user's template
+    /// directory (if one can be resolved), then a cached remote index (if
+    /// `refresh_registry` has ever successfully run).
+    pub fn new() -> Self {
+        let mut sources = vec![TemplateSource::Bundled];
+
+        if let Some(user_dir) = user_template_dir() {
+            sources.push(TemplateSource::UserDir(user_dir.clone()));
+            sources.push(TemplateSource::Remote { cache_file: remote_cache_file(&user_dir) });
+        }
+
+        Self { sources }
+    }
+
+    pub fn get_frameworks(&self) -> Vec<Framework> {
+        let ranked = self.sources.iter().enumerate().map(|(rank, source)| (rank, source.frameworks())).collect();
+        let merged = merge_by_id(ranked, |f| f.id.as_str(), |f| f.version.as_str());
+        if merged.is_empty() {
+            vec![fallback_framework()]
+        } else {
+            merged
+        }
+    }
+
+    pub fn get_modules(&self) -> Vec<Module> {
+        let ranked = self.sources.iter().enumerate().map(|(rank, source)| (rank, source.modules())).collect();
+        let merged = merge_by_id(ranked, |m| m.id.as_str(), |m| m.version.as_str());
+        if merged.is_empty() {
+            vec![fallback_module()]
+        } else {
+            merged
+        }
+    }
+}
+
+impl Default for TemplateRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn remote_cache_file(user_dir: &std::path::Path) -> PathBuf {
+    user_dir.join(".cache").join("remote-index.json")
+}
+
+/// Fetch `url`'s JSON registry index and cache it under the user template
+/// directory so `TemplateRegistry` picks it up on the next read. On a
+/// network failure, leaves any existing cache in place (the registry keeps
+/// working offline on the last-known-good index) rather than failing the
+/// whole command -- only a failure with no prior cache is reported as an
+/// error.
+#[command]
+pub async fn refresh_registry(url: String) -> Result<(), String> {
+    let user_dir = user_template_dir().ok_or("Could not resolve the user template directory")?;
+    let cache_file = remote_cache_file(&user_dir);
+
+    let fetch = async {
+        let response = reqwest::get(&url).await.map_err(|e| e.to_string())?;
+        if !response.status().is_success() {
+            return Err(format!("registry returned status {}", response.status()));
+        }
+        response.text().await.map_err(|e| e.to_string())
+    };
+
+    match fetch.await {
+        Ok(body) => {
+            serde_json::from_str::<RemoteIndex>(&body)
+                .map_err(|e| format!("Remote registry at '{}' returned invalid JSON: {}", url, e))?;
+
+            if let Some(parent) = cache_file.parent() {
+                std::fs::create_dir_all(parent)
+                    .map_err(|e| format!("Failed to create template cache directory: {}", e))?;
+            }
+            std::fs::write(&cache_file, &body).map_err(|e| format!("Failed to cache remote registry: {}", e))
+        }
+        Err(e) if cache_file.exists() => {
+            log::warn!("Failed to refresh remote registry '{}', keeping cached copy: {}", url, e);
+            Ok(())
+        }
+        Err(e) => Err(format!("Failed to fetch remote registry '{}': {} (no cached copy to fall back to)", url, e)),
+    }
+}
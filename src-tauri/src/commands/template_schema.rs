@@ -0,0 +1,207 @@
+//! JSON Schema generation and validation for framework/module template files.
+//!
+//! `TemplateSource`'s readers used to drop a malformed file silently (a
+//! failed `serde_json::from_str` just got filtered out via `.ok()`), so a
+//! template author had no way to find out *what* was wrong with their JSON.
+//! This derives a schema from the live `Framework`/`Module`/
+//! `ModuleInstallation`/`FileOperation` structs (via `schemars`), and
+//! `validate`/`validate_templates` check a file against it, collecting one
+//! diagnostic per violation: the file, a JSON pointer to the offending
+//! value, and what the schema expected there. `TemplateSource`'s readers
+//! do not run this check; they still skip a file that fails to
+//! deserialize, without a diagnostic.
+//!
+//! Schema generation can't happen in `build.rs` the way `tauri.conf.json`'s
+//! schema does -- a build script is its own compilation unit and can't
+//! import the types of the crate it's building for. Instead
+//! `write_schema_files` is called once at startup (see `main.rs`) and
+//! (re)writes the schema to the same fixed path on disk every run, so an
+//! editor can still point at `schemas/framework.schema.json` for
+//! autocomplete the way it would at a build-time-generated file.
+
+use std::path::{Path, PathBuf};
+
+use schemars::schema_for;
+use serde::Serialize;
+use serde_json::Value;
+use tauri::{command, AppHandle, Emitter};
+
+use super::framework::{Framework, Module};
+
+/// Which template kind a file is being validated as.
+#[derive(Debug, Clone, Copy)]
+pub enum TemplateKind {
+    Framework,
+    Module,
+}
+
+impl TemplateKind {
+    fn schema(self) -> Value {
+        let schema = match self {
+            TemplateKind::Framework => serde_json::to_value(schema_for!(Vec<Framework>)),
+            TemplateKind::Module => serde_json::to_value(schema_for!(Vec<Module>)),
+        };
+        schema.expect("derived schema serializes to JSON")
+    }
+
+    fn file_name(self) -> &'static str {
+        match self {
+            TemplateKind::Framework => "framework.schema.json",
+            TemplateKind::Module => "module.schema.json",
+        }
+    }
+}
+
+/// One point of disagreement between a template file and its schema.
+#[derive(Debug, Clone, Serialize)]
+pub struct ValidationDiagnostic {
+    /// RFC 6901 JSON pointer into the document, e.g.
+    /// `/0/installation/file_operations/1/operation`.
+    pub pointer: String,
+    pub expected: String,
+    pub actual: String,
+}
+
+/// Validation outcome for a single template file.
+#[derive(Debug, Clone, Serialize)]
+pub struct ValidationReport {
+    pub file: String,
+    pub valid: bool,
+    pub diagnostics: Vec<ValidationDiagnostic>,
+}
+
+/// Validate `content` (the raw text of `file`) as `kind`, returning one
+/// diagnostic per schema violation -- or a single one if `content` isn't
+/// even valid JSON.
+pub fn validate(file: &str, kind: TemplateKind, content: &str) -> ValidationReport {
+    let value: Value = match serde_json::from_str(content) {
+        Ok(v) => v,
+        Err(e) => {
+            return ValidationReport {
+                file: file.to_string(),
+                valid: false,
+                diagnostics: vec![ValidationDiagnostic {
+                    pointer: "/".to_string(),
+                    expected: "valid JSON".to_string(),
+                    actual: e.to_string(),
+                }],
+            };
+        }
+    };
+
+    let schema = jsonschema::JSONSchema::compile(&kind.schema()).expect("derived schema is a valid JSON Schema");
+
+    let diagnostics = match schema.validate(&value) {
+        Ok(()) => Vec::new(),
+        Err(errors) => errors
+            .map(|e| ValidationDiagnostic {
+                pointer: e.instance_path.to_string(),
+                expected: e.to_string(),
+                actual: e.instance.to_string(),
+            })
+            .collect(),
+    };
+
+    ValidationReport { file: file.to_string(), valid: diagnostics.is_empty(), diagnostics }
+}
+
+fn collect_file(path: &Path, kind: TemplateKind, reports: &mut Vec<ValidationReport>) {
+    if let Ok(content) = std::fs::read_to_string(path) {
+        reports.push(validate(&path.display().to_string(), kind, &content));
+    }
+}
+
+fn collect_dir(dir: &Path, kind: TemplateKind, reports: &mut Vec<ValidationReport>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+
+    for path in entries.filter_map(Result::ok).map(|e| e.path()) {
+        if path.extension().and_then(|e| e.to_str()) == Some("json") {
+            collect_file(&path, kind, reports);
+        }
+    }
+}
+
+/// Validate every framework/module file `TemplateRegistry` would load --
+/// bundled resources and the user's own template directory -- and emit a
+/// `template-validation` event per file so the frontend can surface
+/// problems as they're found, in addition to returning the full report.
+#[command]
+pub async fn validate_templates(app_handle: AppHandle) -> Result<Vec<ValidationReport>, String> {
+    let mut reports = Vec::new();
+
+    for dir in super::template_registry::bundled_search_dirs() {
+        for file in super::template_registry::FRAMEWORK_FILES {
+            collect_file(&dir.join(file), TemplateKind::Framework, &mut reports);
+        }
+        for file in super::template_registry::MODULE_FILES {
+            collect_file(&dir.join(file), TemplateKind::Module, &mut reports);
+        }
+    }
+
+    if let Some(user_dir) = super::template_registry::user_template_dir() {
+        collect_dir(&user_dir.join("frameworks"), TemplateKind::Framework, &mut reports);
+        collect_dir(&user_dir.join("modules"), TemplateKind::Module, &mut reports);
+    }
+
+    for report in &reports {
+        app_handle
+            .emit("template-validation", report)
+            .map_err(|e| format!("Failed to emit template-validation event: {}", e))?;
+    }
+
+    Ok(reports)
+}
+
+/// Write the current framework/module schema to `dir` (see module docs for
+/// why this happens at startup rather than via `build.rs`). Best-effort:
+/// an editor failing to see a refreshed schema isn't worth failing launch
+/// over.
+pub fn write_schema_files(dir: &Path) {
+    if let Err(e) = std::fs::create_dir_all(dir) {
+        log::warn!("Failed to create schema directory '{}': {}", dir.display(), e);
+        return;
+    }
+
+    for kind in [TemplateKind::Framework, TemplateKind::Module] {
+        let path: PathBuf = dir.join(kind.file_name());
+        let schema = serde_json::to_string_pretty(&kind.schema()).expect("derived schema serializes to JSON");
+        if let Err(e) = std::fs::write(&path, schema) {
+            log::warn!("Failed to write schema file '{}': {}", path.display(), e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn invalid_json_reports_a_single_diagnostic_at_the_root() {
+        let report = validate("broken.json", TemplateKind::Framework, "{ not json");
+
+        assert!(!report.valid);
+        assert_eq!(report.diagnostics.len(), 1);
+        assert_eq!(report.diagnostics[0].pointer, "/");
+        assert_eq!(report.diagnostics[0].expected, "valid JSON");
+    }
+
+    #[test]
+    fn well_formed_json_that_violates_the_schema_reports_diagnostics() {
+        // Valid JSON, but not an array of `Module` -- missing every
+        // required field the schema derives from the `Module` struct.
+        let report = validate("module.json", TemplateKind::Module, r#"[{"id": "only-an-id"}]"#);
+
+        assert!(!report.valid);
+        assert!(!report.diagnostics.is_empty());
+    }
+
+    #[test]
+    fn empty_array_satisfies_the_schema() {
+        let report = validate("frameworks.json", TemplateKind::Framework, "[]");
+
+        assert!(report.valid);
+        assert!(report.diagnostics.is_empty());
+    }
+}
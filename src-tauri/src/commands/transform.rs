@@ -0,0 +1,239 @@
+//! Regex- and JSON-aware transform engine for `FileOperation`s.
+//!
+//! A `"modify"` file operation used to delegate straight to
+//! `commands::file::modify_file`'s `content.replace(pattern, replacement)`,
+//! a literal substring replace -- even though modules declare patterns like
+//! DaisyUI's Tailwind config patch, `plugins: \[.*\]`, that only make sense
+//! as regexes. A pattern like that silently no-ops instead of patching the
+//! file. This engine gives each operation kind real semantics and reports
+//! whether it actually changed anything, instead of failing silently.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use regex::Regex;
+
+use super::framework::FileOperation;
+
+/// What happened when a transform was applied, so the caller can log (or
+/// eventually surface to the UI) exactly which ones took effect.
+#[derive(Debug, Clone)]
+pub struct TransformResult {
+    pub path: PathBuf,
+    pub kind: String,
+    /// Whether the transform changed the file. A transform that ran
+    /// successfully but found nothing to do (pattern/anchor absent, or an
+    /// `insert_after`/`insert_before` whose text is already present)
+    /// reports `false` here instead of erroring, so a re-run of a module
+    /// install stays idempotent without looking like a failure.
+    pub matched: bool,
+}
+
+/// Apply `op` to `path`, dispatching on `op.operation`. Recognizes `regex`,
+/// `literal`, `json_merge` (and the legacy hyphenated `json-merge`),
+/// `insert_after`, `insert_before`, and `modify_import` (delegates to
+/// `command_runner::modify_import`, which prefers AST-aware editing where
+/// supported). `modify` -- the kind every file operation used before these
+/// existed -- is treated as `regex`, which is the actual fix for the
+/// silent-no-op bug above: every existing `"modify"` operation gets regex
+/// semantics without needing its data rewritten.
+pub fn apply_transform(path: &Path, op: &FileOperation) -> Result<TransformResult, String> {
+    let kind = op.operation.as_str();
+    let matched = match kind {
+        "regex" | "modify" => apply_regex(path, &op.pattern, &op.replacement)?,
+        "literal" => apply_literal(path, &op.pattern, &op.replacement)?,
+        "json_merge" | "json-merge" => {
+            super::file::json_merge_file(path, &op.content, &op.merge_strategy)?;
+            true
+        }
+        "insert_after" => apply_insert(path, &op.pattern, &op.replacement, InsertAt::After)?,
+        "insert_before" => apply_insert(path, &op.pattern, &op.replacement, InsertAt::Before)?,
+        "modify_import" => {
+            super::command_runner::modify_import(path, &op.action, &op.import)?;
+            true
+        }
+        other => return Err(format!("Unknown transform kind: {}", other)),
+    };
+
+    Ok(TransformResult { path: path.to_path_buf(), kind: kind.to_string(), matched })
+}
+
+fn read(path: &Path) -> Result<String, String> {
+    fs::read_to_string(path).map_err(|e| format!("Failed to read file '{}': {}", path.display(), e))
+}
+
+fn write(path: &Path, content: &str) -> Result<(), String> {
+    fs::write(path, content).map_err(|e| format!("Failed to write file '{}': {}", path.display(), e))
+}
+
+/// Compile `pattern` as a regex and replace every match with `replacement`,
+/// which may reference captures (`$1`) the way `Regex::replace_all` already
+/// supports.
+fn apply_regex(path: &Path, pattern: &str, replacement: &str) -> Result<bool, String> {
+    let content = read(path)?;
+    let regex = Regex::new(pattern).map_err(|e| format!("Invalid regex pattern '{}': {}", pattern, e))?;
+    if !regex.is_match(&content) {
+        return Ok(false);
+    }
+    write(path, &regex.replace_all(&content, replacement))?;
+    Ok(true)
+}
+
+/// Plain substring replace, for the (now-explicit) case where `pattern`
+/// really is meant literally rather than as a regex.
+fn apply_literal(path: &Path, pattern: &str, replacement: &str) -> Result<bool, String> {
+    let content = read(path)?;
+    if !content.contains(pattern) {
+        return Ok(false);
+    }
+    write(path, &content.replace(pattern, replacement))?;
+    Ok(true)
+}
+
+enum InsertAt {
+    Before,
+    After,
+}
+
+/// Locate the line containing `anchor` and inject `text` immediately before
+/// or after it, skipping the insert entirely if `text` is already present
+/// anywhere in the file -- so re-running a module install doesn't keep
+/// duplicating the same inserted block.
+fn apply_insert(path: &Path, anchor: &str, text: &str, at: InsertAt) -> Result<bool, String> {
+    let content = read(path)?;
+    if content.contains(text) {
+        return Ok(false);
+    }
+
+    let Some(anchor_line) = content.lines().position(|line| line.contains(anchor)) else {
+        return Ok(false);
+    };
+
+    let mut lines: Vec<&str> = content.lines().collect();
+    let insert_at = match at {
+        InsertAt::Before => anchor_line,
+        InsertAt::After => anchor_line + 1,
+    };
+    lines.insert(insert_at, text);
+    write(path, &format!("{}\n", lines.join("\n")))?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn op(operation: &str, pattern: &str, replacement: &str) -> FileOperation {
+        FileOperation {
+            operation: operation.to_string(),
+            path: String::new(),
+            content: String::new(),
+            pattern: pattern.to_string(),
+            replacement: replacement.to_string(),
+            action: String::new(),
+            import: String::new(),
+            merge_strategy: "last-write-wins".to_string(),
+        }
+    }
+
+    fn write_temp(name: &str, content: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "architech-transform-test-{}-{}",
+            std::process::id(),
+            name
+        ));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(name);
+        fs::write(&path, content).unwrap();
+        path
+    }
+
+    #[test]
+    fn modify_kind_gets_regex_semantics_not_literal() {
+        // This is the whole point of the engine: `"modify"`, the legacy
+        // kind every existing operation still uses, must behave like
+        // `"regex"` so a pattern like DaisyUI's `plugins: \[.*\]` actually
+        // matches instead of silently no-oping like a literal replace would.
+        let path = write_temp("modify_kind.js", "module.exports = { plugins: [] };");
+        let operation = op("modify", r"plugins: \[.*\]", "plugins: [require('daisyui')]");
+
+        let result = apply_transform(&path, &operation).unwrap();
+
+        assert!(result.matched);
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "module.exports = { plugins: [require('daisyui')] };"
+        );
+    }
+
+    #[test]
+    fn regex_replace_all_applies_every_match() {
+        let path = write_temp("regex_all.txt", "foo bar foo baz foo");
+        let operation = op("regex", "foo", "qux");
+
+        let result = apply_transform(&path, &operation).unwrap();
+
+        assert!(result.matched);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "qux bar qux baz qux");
+    }
+
+    #[test]
+    fn regex_reports_unmatched_instead_of_erroring() {
+        let path = write_temp("regex_unmatched.txt", "nothing relevant here");
+        let operation = op("regex", "absent-pattern", "replacement");
+
+        let result = apply_transform(&path, &operation).unwrap();
+
+        assert!(!result.matched);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "nothing relevant here");
+    }
+
+    #[test]
+    fn literal_replace_does_not_interpret_pattern_as_regex() {
+        let path = write_temp("literal.txt", "version: 1.0.*");
+        let operation = op("literal", "1.0.*", "2.0.0");
+
+        let result = apply_transform(&path, &operation).unwrap();
+
+        assert!(result.matched);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "version: 2.0.0");
+    }
+
+    #[test]
+    fn insert_after_skips_when_text_already_present() {
+        let path = write_temp("insert_after_idempotent.txt", "import a from 'a';\nimport b from 'b';");
+        let operation = op("insert_after", "import a from 'a';", "import b from 'b';");
+
+        let result = apply_transform(&path, &operation).unwrap();
+
+        assert!(!result.matched);
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "import a from 'a';\nimport b from 'b';"
+        );
+    }
+
+    #[test]
+    fn insert_before_places_text_immediately_above_the_anchor_line() {
+        let path = write_temp("insert_before.txt", "line one\nanchor line\nline three");
+        let operation = op("insert_before", "anchor line", "inserted line");
+
+        let result = apply_transform(&path, &operation).unwrap();
+
+        assert!(result.matched);
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "line one\ninserted line\nanchor line\nline three\n"
+        );
+    }
+
+    #[test]
+    fn unknown_transform_kind_is_rejected() {
+        let path = write_temp("unknown_kind.txt", "content");
+        let operation = op("not-a-real-kind", "", "");
+
+        let result = apply_transform(&path, &operation);
+
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,45 @@
+//! Commands exposing the webhook registry (see `crate::webhook`) to the
+//! frontend: registering, removing, and listing notification endpoints.
+
+use std::sync::Arc;
+
+use serde::Deserialize;
+use tauri::{command, State};
+
+use crate::webhook::{WebhookConfig, WebhookEvent};
+
+#[derive(Debug, Deserialize)]
+pub struct AddWebhookParams {
+    pub url: String,
+    #[serde(default)]
+    pub secret: Option<String>,
+    pub events: Vec<WebhookEvent>,
+}
+
+#[command]
+pub async fn add_webhook(
+    params: AddWebhookParams,
+    state: State<'_, Arc<crate::state::AppState>>,
+) -> Result<WebhookConfig, String> {
+    let app_state = state.inner().clone();
+    app_state
+        .webhooks
+        .add(params.url, params.secret, params.events, &app_state)
+        .await
+}
+
+#[command]
+pub async fn remove_webhook(
+    id: String,
+    state: State<'_, Arc<crate::state::AppState>>,
+) -> Result<(), String> {
+    let app_state = state.inner().clone();
+    app_state.webhooks.remove(&id, &app_state).await
+}
+
+#[command]
+pub async fn list_webhooks(
+    state: State<'_, Arc<crate::state::AppState>>,
+) -> Result<Vec<WebhookConfig>, String> {
+    Ok(state.webhooks.list().await)
+}
@@ -0,0 +1,34 @@
+//! Commands exposing the background worker registry (see `crate::worker`)
+//! to the frontend: listing every in-flight generation worker's live status
+//! and pausing/resuming/cancelling a specific one.
+
+use std::sync::Arc;
+
+use tauri::{command, State};
+
+use crate::worker::{WorkerAction, WorkerInfo};
+
+#[command]
+pub async fn list_workers(
+    state: State<'_, Arc<crate::state::AppState>>,
+) -> Result<Vec<WorkerInfo>, String> {
+    Ok(state.workers.list().await)
+}
+
+#[command]
+pub async fn control_worker(
+    project_id: String,
+    action: WorkerAction,
+    app_handle: tauri::AppHandle,
+    state: State<'_, Arc<crate::state::AppState>>,
+) -> Result<(), String> {
+    // Cancellation also has to flip the project's status and tidy up its
+    // checkpoint, which `ProjectGenerator::cancel_generation` already does --
+    // route through it instead of duplicating that here.
+    if action == WorkerAction::Cancel {
+        let generator = crate::generation::ProjectGenerator::new(app_handle, state.inner().clone());
+        return generator.cancel_generation(&project_id).await;
+    }
+
+    state.workers.control(&project_id, action).await
+}
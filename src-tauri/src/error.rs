@@ -0,0 +1,68 @@
+//! Typed, serializable error taxonomy for generation-related failures.
+//!
+//! `Result<_, String>` is pervasive elsewhere in this crate, but it loses
+//! all structure by the time it crosses the Tauri IPC boundary -- the
+//! frontend is left parsing human-readable sentences to decide what went
+//! wrong. `GenerationError` carries a stable `code()` instead, so callers
+//! can branch on the failure kind without string-matching.
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+#[derive(Debug, Clone, Error, Serialize, Deserialize)]
+#[serde(tag = "code", content = "details", rename_all = "snake_case")]
+pub enum GenerationError {
+    #[error("project {project_id} is already running")]
+    AlreadyRunning { project_id: String },
+
+    #[error("checkpoint error: {0}")]
+    Checkpoint(String),
+
+    #[error("task {task_id} failed: {message}")]
+    TaskFailed {
+        task_id: String,
+        message: String,
+        retryable: bool,
+    },
+
+    #[error("invalid configuration: {0}")]
+    ConfigInvalid(String),
+
+    #[error("generation failed: {message}")]
+    Failed { message: String, resumable: bool },
+
+    #[error("project is not resumable")]
+    NotResumable,
+
+    #[error("io error: {0}")]
+    Io(String),
+}
+
+impl GenerationError {
+    /// Stable, machine-readable identifier for this error variant -- also
+    /// present as the serialized `code` field, but exposed as a method too
+    /// so Rust callers can match on it without round-tripping through JSON.
+    pub fn code(&self) -> &'static str {
+        match self {
+            GenerationError::AlreadyRunning { .. } => "already_running",
+            GenerationError::Checkpoint(_) => "checkpoint",
+            GenerationError::TaskFailed { .. } => "task_failed",
+            GenerationError::ConfigInvalid(_) => "config_invalid",
+            GenerationError::Failed { .. } => "failed",
+            GenerationError::NotResumable => "not_resumable",
+            GenerationError::Io(_) => "io",
+        }
+    }
+}
+
+impl From<std::io::Error> for GenerationError {
+    fn from(e: std::io::Error) -> Self {
+        GenerationError::Io(e.to_string())
+    }
+}
+
+impl From<serde_json::Error> for GenerationError {
+    fn from(e: serde_json::Error) -> Self {
+        GenerationError::Checkpoint(e.to_string())
+    }
+}
@@ -0,0 +1,128 @@
+//! Typed builder for the events `register_event_listeners` routes to a
+//! project's owning window.
+//!
+//! Event names are namespaced per project (`project://{project_id}/{kind}`)
+//! so `AppHandle::emit_to` can target only the window that started that
+//! project's generation, instead of `emit`'s broadcast to every window.
+//! Centralizing the name/payload pairing here also means a new event kind
+//! only needs one constructor, not a hand-inlined `serde_json::json!` at
+//! each call site.
+
+use serde_json::json;
+
+use crate::state::LogLevel;
+
+/// A named, pre-serialized event ready to hand to `AppHandle::emit_to`.
+pub struct Event {
+    pub name: String,
+    pub payload: serde_json::Value,
+}
+
+impl Event {
+    fn new(project_id: &str, kind: &str, payload: serde_json::Value) -> Self {
+        Self {
+            name: format!("project://{}/{}", project_id, kind),
+            payload,
+        }
+    }
+
+    pub fn started(project_id: &str) -> Self {
+        Self::new(project_id, "generation-started", json!(project_id))
+    }
+
+    pub fn progress(project_id: &str, step: &str, progress: u8, task_counts: Option<(u64, u64)>) -> Self {
+        Self::new(project_id, "generation-progress", json!({
+            "project_id": project_id,
+            "step": step,
+            "progress": progress as f32 / 100.0,
+            "message": format!("{}% - {}", progress, step),
+            "completed_tasks": task_counts.map(|(completed, _)| completed),
+            "total_tasks": task_counts.map(|(_, total)| total),
+        }))
+    }
+
+    pub fn completed(project_id: &str, _path: &str) -> Self {
+        Self::new(project_id, "generation-complete", json!(project_id))
+    }
+
+    pub fn failed(project_id: &str, error: &str) -> Self {
+        Self::new(project_id, "generation-failed", json!([project_id, error]))
+    }
+
+    pub fn cancelled(project_id: &str) -> Self {
+        Self::new(project_id, "generation-cancelled", json!(project_id))
+    }
+
+    pub fn task_state_changed(project_id: &str, task_id: &str, state: &str) -> Self {
+        Self::new(project_id, "task-state-changed", json!({
+            "project_id": project_id,
+            "task_id": task_id,
+            "state": state
+        }))
+    }
+
+    pub fn log_message(project_id: &str, message: &str) -> Self {
+        Self::new(project_id, "log-message", json!({
+            "project_id": project_id,
+            "message": message
+        }))
+    }
+
+    pub fn project_log(project_id: &str, level: LogLevel, task_id: Option<&str>, message: &str) -> Self {
+        Self::new(project_id, "project-log", json!({
+            "project_id": project_id,
+            "level": level,
+            "task_id": task_id,
+            "message": message
+        }))
+    }
+
+    pub fn task_initialization_started(project_id: &str) -> Self {
+        Self::new(project_id, "task-initialization-started", json!({ "project_id": project_id }))
+    }
+
+    pub fn task_initialization_progress(project_id: &str, message: &str) -> Self {
+        Self::new(project_id, "task-initialization-progress", json!({
+            "project_id": project_id,
+            "message": message
+        }))
+    }
+
+    pub fn task_initialization_completed(project_id: &str, task_count: usize, task_names: &[String]) -> Self {
+        Self::new(project_id, "task-initialization-completed", json!({
+            "project_id": project_id,
+            "task_count": task_count,
+            "task_names": task_names
+        }))
+    }
+
+    pub fn task_initialization_failed(project_id: &str, reason: &str) -> Self {
+        Self::new(project_id, "task-initialization-failed", json!({
+            "project_id": project_id,
+            "reason": reason
+        }))
+    }
+
+    pub fn task_retrying(project_id: &str, task_id: &str, attempt: u32, next_delay_ms: u64) -> Self {
+        Self::new(project_id, "task-retrying", json!({
+            "project_id": project_id,
+            "task_id": task_id,
+            "attempt": attempt,
+            "next_delay_ms": next_delay_ms
+        }))
+    }
+
+    pub fn task_ready(project_id: &str, task_id: &str) -> Self {
+        Self::new(project_id, "task-ready", json!({
+            "project_id": project_id,
+            "task_id": task_id
+        }))
+    }
+
+    pub fn worker_state_changed(project_id: &str, status: &crate::worker::WorkerStatus) -> Self {
+        Self::new(project_id, "worker-state-changed", json!({
+            "project_id": project_id,
+            "status": status
+        }))
+    }
+}
@@ -0,0 +1,256 @@
+//! Pluggable filesystem backend for command execution and file emission.
+//!
+//! `CommandBuilder` and the `create_file`/`modify_file` family in
+//! `commands::command_runner` write straight through `std::fs`, which bakes
+//! in the assumption that generation always targets the local disk. The
+//! `FileSystem` trait pulls that assumption out to the edge: a generation
+//! run can be handed a `LocalFileSystem` (the default, and the only backend
+//! wired into the app today) or a `RemoteFileSystem` talking to a
+//! networked store, so scaffolding into a container, a CI workspace, or a
+//! shared volume is a matter of swapping the backend, not touching
+//! blueprint logic. It also gives the runner something to be tested
+//! against other than the real disk.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use async_trait::async_trait;
+use reqwest::Client;
+use tokio::io::{AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::sync::Mutex;
+
+/// Opaque handle returned by `FileSystem::create_file`, passed back into
+/// `write`/`close` -- mirrors the shape of Alluxio's REST file API (create
+/// returns a file id, writes stream to `.../stream/<id>`, and a final call
+/// closes it), so `RemoteFileSystem` can map it directly onto that id
+/// instead of inventing its own handle concept.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FileHandle(u64);
+
+/// Where command execution and generated files are written to.
+///
+/// All methods take `&self` rather than `&mut self` so a single backend
+/// instance can be shared (e.g. via `Arc`) across the concurrently-running
+/// tasks of one generation.
+#[async_trait]
+pub trait FileSystem: Send + Sync {
+    /// Whether `path` already exists.
+    async fn exists(&self, path: &Path) -> bool;
+
+    /// Create (or truncate) the file at `path` and return a handle for
+    /// streaming writes to it via `write`.
+    async fn create_file(&self, path: &Path) -> Result<FileHandle, String>;
+
+    /// Append `data` to the file identified by `handle`.
+    async fn write(&self, handle: FileHandle, data: &[u8]) -> Result<(), String>;
+
+    /// Read the whole contents of `path`.
+    async fn read(&self, path: &Path) -> Result<Vec<u8>, String>;
+
+    /// Delete `path`.
+    async fn remove(&self, path: &Path) -> Result<(), String>;
+
+    /// Release `handle`, flushing any buffered writes. A handle not closed
+    /// is a resource leak on a remote backend, which otherwise has no other
+    /// signal that a file is finished.
+    async fn close(&self, handle: FileHandle) -> Result<(), String>;
+}
+
+/// Local-disk implementation, equivalent to the `std::fs` calls
+/// `command_runner`'s free functions made directly before this trait
+/// existed.
+#[derive(Default)]
+pub struct LocalFileSystem {
+    open: Mutex<HashMap<u64, (PathBuf, tokio::fs::File)>>,
+    next_handle: AtomicU64,
+}
+
+impl LocalFileSystem {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl FileSystem for LocalFileSystem {
+    async fn exists(&self, path: &Path) -> bool {
+        tokio::fs::try_exists(path).await.unwrap_or(false)
+    }
+
+    async fn create_file(&self, path: &Path) -> Result<FileHandle, String> {
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent)
+                .await
+                .map_err(|e| format!("Failed to create directory '{}': {}", parent.display(), e))?;
+        }
+
+        let file = tokio::fs::File::create(path)
+            .await
+            .map_err(|e| format!("Failed to create file '{}': {}", path.display(), e))?;
+
+        let id = self.next_handle.fetch_add(1, Ordering::Relaxed);
+        self.open.lock().await.insert(id, (path.to_path_buf(), file));
+        Ok(FileHandle(id))
+    }
+
+    async fn write(&self, handle: FileHandle, data: &[u8]) -> Result<(), String> {
+        let mut open = self.open.lock().await;
+        let (path, file) = open
+            .get_mut(&handle.0)
+            .ok_or_else(|| "write: unknown or already-closed file handle".to_string())?;
+        file.write_all(data)
+            .await
+            .map_err(|e| format!("Failed to write to file '{}': {}", path.display(), e))
+    }
+
+    async fn read(&self, path: &Path) -> Result<Vec<u8>, String> {
+        tokio::fs::read(path)
+            .await
+            .map_err(|e| format!("Failed to read file '{}': {}", path.display(), e))
+    }
+
+    async fn remove(&self, path: &Path) -> Result<(), String> {
+        tokio::fs::remove_file(path)
+            .await
+            .map_err(|e| format!("Failed to remove file '{}': {}", path.display(), e))
+    }
+
+    async fn close(&self, handle: FileHandle) -> Result<(), String> {
+        let entry = self.open.lock().await.remove(&handle.0);
+        let Some((path, mut file)) = entry else {
+            return Ok(());
+        };
+        file.flush()
+            .await
+            .map_err(|e| format!("Failed to flush file '{}': {}", path.display(), e))
+    }
+}
+
+/// REST-backed implementation for a remote/virtual store, modeled on
+/// Alluxio's file-system REST API: `create` returns a file id, `write`
+/// streams a chunk to that id's open stream, and `close` tells the server
+/// the stream is done. `read`/`remove`/`exists` are single request/response
+/// calls with no handle involved.
+pub struct RemoteFileSystem {
+    base_url: String,
+    client: Client,
+}
+
+impl RemoteFileSystem {
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self { base_url: base_url.into(), client: Client::new() }
+    }
+
+    fn url(&self, segment: &str, path: &Path) -> String {
+        format!("{}/{}?path={}", self.base_url.trim_end_matches('/'), segment, path.display())
+    }
+}
+
+#[async_trait]
+impl FileSystem for RemoteFileSystem {
+    async fn exists(&self, path: &Path) -> bool {
+        self.client
+            .get(self.url("exists", path))
+            .send()
+            .await
+            .map(|r| r.status().is_success())
+            .unwrap_or(false)
+    }
+
+    async fn create_file(&self, path: &Path) -> Result<FileHandle, String> {
+        let response = self
+            .client
+            .post(self.url("create", path))
+            .send()
+            .await
+            .map_err(|e| format!("Failed to create remote file '{}': {}", path.display(), e))?;
+
+        if !response.status().is_success() {
+            return Err(format!("Remote create for '{}' returned status {}", path.display(), response.status()));
+        }
+
+        let id: u64 = response
+            .text()
+            .await
+            .map_err(|e| format!("Failed to read remote file id for '{}': {}", path.display(), e))?
+            .trim()
+            .parse()
+            .map_err(|e| format!("Remote create for '{}' returned a non-numeric file id: {}", path.display(), e))?;
+
+        Ok(FileHandle(id))
+    }
+
+    async fn write(&self, handle: FileHandle, data: &[u8]) -> Result<(), String> {
+        // A `BufReader` over the chunk, the way the request describes
+        // streaming writes, rather than sending `data` as one opaque body --
+        // lets a future caller hand this a much larger in-memory buffer
+        // without changing this method's shape.
+        let mut reader = BufReader::new(data);
+        let mut buffered = Vec::with_capacity(data.len());
+        reader
+            .read_to_end(&mut buffered)
+            .await
+            .map_err(|e| format!("Failed to buffer write for handle {}: {}", handle.0, e))?;
+
+        let response = self
+            .client
+            .post(format!("{}/streams/{}", self.base_url.trim_end_matches('/'), handle.0))
+            .body(buffered)
+            .send()
+            .await
+            .map_err(|e| format!("Failed to write to remote stream {}: {}", handle.0, e))?;
+
+        if !response.status().is_success() {
+            return Err(format!("Remote write to stream {} returned status {}", handle.0, response.status()));
+        }
+        Ok(())
+    }
+
+    async fn read(&self, path: &Path) -> Result<Vec<u8>, String> {
+        let response = self
+            .client
+            .get(self.url("open-file", path))
+            .send()
+            .await
+            .map_err(|e| format!("Failed to read remote file '{}': {}", path.display(), e))?;
+
+        if !response.status().is_success() {
+            return Err(format!("Remote read for '{}' returned status {}", path.display(), response.status()));
+        }
+
+        response
+            .bytes()
+            .await
+            .map(|b| b.to_vec())
+            .map_err(|e| format!("Failed to buffer remote file '{}': {}", path.display(), e))
+    }
+
+    async fn remove(&self, path: &Path) -> Result<(), String> {
+        let response = self
+            .client
+            .post(self.url("delete", path))
+            .send()
+            .await
+            .map_err(|e| format!("Failed to delete remote file '{}': {}", path.display(), e))?;
+
+        if !response.status().is_success() {
+            return Err(format!("Remote delete for '{}' returned status {}", path.display(), response.status()));
+        }
+        Ok(())
+    }
+
+    async fn close(&self, handle: FileHandle) -> Result<(), String> {
+        let response = self
+            .client
+            .post(format!("{}/streams/{}/close", self.base_url.trim_end_matches('/'), handle.0))
+            .send()
+            .await
+            .map_err(|e| format!("Failed to close remote stream {}: {}", handle.0, e))?;
+
+        if !response.status().is_success() {
+            return Err(format!("Remote close for stream {} returned status {}", handle.0, response.status()));
+        }
+        Ok(())
+    }
+}
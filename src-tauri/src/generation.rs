@@ -7,7 +7,7 @@ use uuid::Uuid;
 use serde::{Serialize, Deserialize};
 
 use tokio::time::sleep;
-use std::collections::{HashMap, HashSet};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::path::{PathBuf};
 use log::{debug, info, warn, error};
 
@@ -16,10 +16,11 @@ use crate::state::{AppState, ProjectStatus};
 use crate::commands::framework::{get_framework_by_id as get_framework, get_modules};
 use crate::commands::command_runner::{modify_file, modify_import};
 use crate::tasks::{
-    Task, TaskContext, TaskExecutor, TaskState,
+    Task, TaskContext, TaskExecutor, TaskState, RollbackOutcome,
     FrameworkTask, ModuleTask, CleanupTask
 };
 use crate::commands::command_runner::{ CommandResult as CommandRunnerResult};
+use crate::generation_error::SetupError;
 
 // Task result type
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -30,6 +31,15 @@ pub struct CommandResult {
     pub exit_code: i32,
 }
 
+/// Outcome of `ProjectGenerator::execute_command`: either the command ran
+/// (successfully or not -- see `CommandRunnerResult::success`), or a
+/// `cancel-generation` event arrived first and the child was killed before
+/// it could finish.
+pub enum ExecutionOutcome {
+    Completed(CommandRunnerResult),
+    Cancelled,
+}
+
 // Project generator
 pub struct ProjectGenerator {
     app_handle: AppHandle,
@@ -47,14 +57,32 @@ impl ProjectGenerator {
     
     // Store config for a project without starting generation
     pub async fn store_config(&self, project_id: &str, config: crate::commands::project::ProjectConfig) -> Result<(), String> {
+        // Resolve handlebars-style {{field}} references against the config
+        // itself before anything downstream sees it, so tasks, the
+        // checkpoint, and a resumed run all observe the same expanded
+        // values -- see `template::render_config`.
+        let config = match crate::template::render_config(&config) {
+            Ok(config) => config,
+            Err(e) => {
+                let reason = e.to_string();
+                log::error!("Config template rendering failed: {}", reason);
+                self.app_state.emit_event(crate::state::ProjectEvent::TaskInitializationFailed {
+                    project_id: project_id.to_string(),
+                    reason: reason.clone(),
+                }).await;
+                return Err(reason);
+            }
+        };
+
         // Create project checkpoint
         let project_path = PathBuf::from(&config.path).join(&config.name);
         
-        println!("Storing project config for ID: {}", project_id);
         log::debug!("Storing project config for ID: {}", project_id);
         
         // Store config in app state for later use
-        self.app_state.store_project_config(project_id, config.clone()).await?;
+        self.app_state.store_project_config(project_id, config.clone())
+            .await
+            .map_err(|e| e.to_string())?;
         
         // Emit task-initialization-started event
         self.app_state.emit_event(crate::state::ProjectEvent::TaskInitializationStarted { 
@@ -66,7 +94,6 @@ impl ProjectGenerator {
     
     // Initialize tasks and start generation
     pub async fn initialize_and_start(&self, project_id: &str) -> Result<(), String> {
-        println!("Initializing tasks for project ID: {}", project_id);
         log::debug!("Initializing tasks for project ID: {}", project_id);
         
         // Get stored config
@@ -77,8 +104,7 @@ impl ProjectGenerator {
         
         // Generate tasks for this project
         log::debug!("Generating tasks for project: {}", project_id);
-        println!("About to create tasks for project");
-        
+
         // Emit task initialization event
         self.app_state.emit_event(crate::state::ProjectEvent::TaskInitializationProgress { 
             project_id: project_id.to_string(),
@@ -87,12 +113,10 @@ impl ProjectGenerator {
         
         let tasks = match self.create_tasks(project_id, &config).await {
             Ok(tasks) => {
-                println!("Created {} tasks successfully", tasks.len());
                 log::info!("Created {} tasks for project {}", tasks.len(), project_id);
                 tasks
             },
             Err(e) => {
-                println!("TASK CREATION FAILED: {}", e);
                 log::error!("Failed to create tasks: {}", e);
                 
                 // Emit task initialization failed event
@@ -106,7 +130,7 @@ impl ProjectGenerator {
         };
         
         // Emit task initialization completed
-        println!("Emitting task initialization completed event");
+        log::debug!("Emitting task initialization completed event");
         let task_names: Vec<(String, String)> = tasks.iter()
             .map(|task| (task.id().to_string(), task.name().to_string()))
             .collect();
@@ -119,24 +143,24 @@ impl ProjectGenerator {
         
         // Set project status to generating
         log::debug!("Setting project status to Generating");
-        println!("Setting project status to Generating (Initializing)");
-        self.app_state.set_project_status(project_id, ProjectStatus::Generating { 
+        self.app_state.set_project_status(project_id, ProjectStatus::Generating {
             current_step: "Initializing".to_string(), 
             progress: 0
         }).await;
         
         // Register tasks in state
-        println!("Registering {} tasks in state", tasks.len());
+        log::debug!("Registering {} tasks in state", tasks.len());
         for task in &tasks {
             let task_id = task.id().to_string();
             let task_name = task.name().to_string();
             
             // Register task in the app state
             self.app_state.register_task(
-                project_id, 
-                &task_id, 
+                project_id,
+                &task_id,
                 &task_name,
-                task.dependencies()
+                task.dependencies(),
+                task.max_attempts(),
             ).await;
             
             // Set initial task state
@@ -146,7 +170,23 @@ impl ProjectGenerator {
                 crate::tasks::TaskState::Pending
             ).await;
         }
-        
+
+        // Resolve the dependency DAG up front so a cyclic configuration
+        // fails fast with the offending task IDs, instead of silently
+        // stalling once the executor finds no ready tasks.
+        let task_metadata = self.app_state.get_task_metadata(project_id).await;
+        if let Err(e) = crate::scheduler::topological_order(&task_metadata) {
+            let reason = e.to_string();
+            log::error!("Task dependency resolution failed: {}", reason);
+
+            self.app_state.emit_event(crate::state::ProjectEvent::TaskInitializationFailed {
+                project_id: project_id.to_string(),
+                reason: reason.clone(),
+            }).await;
+
+            return Err(reason);
+        }
+
         // Start task execution in the background
         let app_handle = self.app_handle.clone();
         let app_state = self.app_state.clone();
@@ -155,19 +195,16 @@ impl ProjectGenerator {
         
         // Spawn task execution
         log::debug!("Spawning task execution");
-        println!("Spawning task execution on background thread");
         tokio::spawn(async move {
-            println!("TASK EXECUTION THREAD STARTED for {}", project_id_clone);
+            log::debug!("Task execution thread started for {}", project_id_clone);
             match Self::execute_tasks(&project_id_clone, app_handle, app_state, config_clone, tasks).await {
                 Ok(_) => {
                     // Success
                     info!("Project generation completed successfully: {}", project_id_clone);
-                    println!("PROJECT GENERATION COMPLETED: {}", project_id_clone);
                 },
                 Err(e) => {
                     // Failure
                     error!("Project generation failed: {}", e);
-                    println!("PROJECT GENERATION FAILED in execution thread: {}", e);
                 }
             }
         });
@@ -180,7 +217,6 @@ impl ProjectGenerator {
         // Create a new project_id
         let project_id = Uuid::new_v4().to_string();
         
-        println!("START_GENERATION for project ID: {}", project_id);
         log::debug!("Generated new project ID: {}", project_id);
         
         // Store the config
@@ -190,7 +226,6 @@ impl ProjectGenerator {
         match self.initialize_and_start(&project_id).await {
             Ok(_) => {
                 log::info!("Task execution spawned, returning project ID: {}", project_id);
-                println!("Returning project ID to frontend: {}", project_id);
                 Ok(project_id)
             },
             Err(e) => Err(e)
@@ -248,13 +283,20 @@ impl ProjectGenerator {
     }
     
     pub async fn cancel_generation(&self, project_id: &str) -> Result<(), String> {
+        // Signal the running worker, if any, so the executor actually stops
+        // dispatching new task batches instead of just flipping a status
+        // flag that the background run never looks at.
+        if let Some(worker) = self.app_state.workers.get(project_id).await {
+            let _ = worker.control(crate::worker::WorkerAction::Cancel).await;
+        }
+
         // Set project status to cancelled
         self.app_state.set_project_status(project_id, ProjectStatus::Cancelled).await;
-        
+
         // Log the cancellation
         info!("Project generation cancelled: {}", project_id);
         self.app_state.add_log(project_id, "Generation cancelled by user").await;
-        
+
         Ok(())
     }
     
@@ -268,172 +310,232 @@ impl ProjectGenerator {
         let project_path = PathBuf::from(&config.path).join(&config.name);
         debug!("Project path will be: {}", project_path.display());
         
-        // Create task context
+        // Create task context. This context is only ever used to satisfy
+        // task constructors below (several ignore it outright), not to run
+        // tasks -- `execute_tasks` builds the context tasks actually run
+        // against, staging directory included, once the real run starts.
         let context = TaskContext {
             project_id: project_id.to_string(),
+            staging_dir: project_path.clone().into(),
             project_dir: project_path.into(),
             app_handle: self.app_handle.clone(),
             config: Arc::new(config.clone()),
+            rollback_on_failure: config.rollback_on_failure,
+            dependency_outputs: HashMap::new(),
         };
         debug!("Created task context with project ID: {}", project_id);
         
         // Create the tasks
         let mut tasks: Vec<Box<dyn Task>> = Vec::new();
         
-        // Step 1: Framework setup task - No dependencies
+        let framework = get_framework(&config.framework).await?;
+
+        // Step 0.5: Framework-declared fetch tasks -- external archives or
+        // starter repos pinned by sha256 (see `commands::framework::Fetch`),
+        // downloaded and verified before the framework task scaffolds
+        // anything. Root tasks themselves (no dependencies).
+        let mut framework_fetch_task_ids = Vec::new();
+        for fetch in &framework.fetch {
+            let fetch_task = Box::new(crate::tasks::FetchTask::new(fetch.clone(), Vec::new()));
+            info!("Created fetch task for framework asset '{}'", fetch.name);
+            framework_fetch_task_ids.push(fetch_task.id().to_string());
+            tasks.push(fetch_task);
+        }
+
+        // Step 1: Framework setup task - depends only on its own fetch tasks, if any
         debug!("Creating framework task for: {}", config.framework);
-        let framework_task = Box::new(FrameworkTask::new(context.clone()));
+        let mut framework_task = Box::new(FrameworkTask::new(context.clone()));
+        framework_task.set_dependencies(framework_fetch_task_ids);
         let framework_task_id = framework_task.id().to_string();
         tasks.push(framework_task);
         info!("Created framework task with ID: {}", framework_task_id);
-        
+
         // Step 2: Module setup tasks - Depend directly on framework task
-        debug!("Module count: {}", config.modules.len());
         debug!("Modules selected: {:?}", config.modules);
-        
-        // Get all modules
-        let all_modules = match crate::commands::framework::get_modules().await {
+
+        let all_modules = match get_modules().await {
             Ok(modules) => modules,
             Err(e) => {
                 error!("Failed to get modules: {}", e);
                 return Err(format!("Failed to get modules: {}", e));
             }
         };
-        
-        // Resolve module dependencies
-        let mut module_deps: HashMap<String, Vec<String>> = HashMap::new();
-        
-        // First pass: Collect dependencies
-        for module_id in &config.modules {
-            let module = match all_modules.iter().find(|m| m.id == *module_id) {
-                Some(m) => m,
-                None => {
-                    warn!("Module not found: {}", module_id);
-                    continue;
-                }
-            };
-            
-            // Collect module dependencies
-            let mut deps = Vec::new();
-            for dep_id in &module.dependencies {
-                if config.modules.contains(dep_id) {
-                    deps.push(format!("module:{}", dep_id));
-                }
-            }
-            
-            module_deps.insert(module_id.clone(), deps);
-        }
-        
-        // Check for direct circular dependencies in modules
-        for (module_id, deps) in &module_deps {
-            for dep_id in deps {
-                if dep_id == &format!("module:{}", module_id) {
-                    warn!("Module depends on itself: {}", module_id);
-                    return Err(format!("Invalid module dependency: {} depends on itself", module_id));
-                }
-                
-                if let Some(dep_deps) = module_deps.get(dep_id.strip_prefix("module:").unwrap_or(dep_id)) {
-                    if dep_deps.contains(&format!("module:{}", module_id)) {
-                        warn!("Circular dependency detected: {} <-> {}", module_id, dep_id);
-                        return Err(format!("Circular dependency detected between modules: {} and {}", 
-                                         module_id, dep_id.strip_prefix("module:").unwrap_or(dep_id)));
+
+        // Transitively close dependencies, check framework compatibility
+        // and `incompatible_with` conflicts, and produce a deterministic
+        // install order -- see `commands::module_resolver`.
+        let resolved = crate::commands::module_resolver::resolve_modules(&framework, &config.modules, &all_modules)
+            .map_err(|e| e.to_string())?;
+        info!("Resolved module install order: {:?}", resolved.iter().map(|m| &m.id).collect::<Vec<_>>());
+
+        // If this project was already scaffolded once, `LockfileTask` left
+        // an `architech.lock` behind -- flag any requested module whose
+        // declared version has drifted from what's pinned there, so a
+        // regeneration doesn't silently swap in a different version.
+        let locked_project_dir = PathBuf::from(&config.path).join(&config.name);
+        if let Ok(Some(lock)) = crate::commands::project_lock::ProjectLock::load(&locked_project_dir) {
+            for module in &resolved {
+                if let Some(locked_version) = lock.version_of(&module.id) {
+                    if locked_version != module.version {
+                        let warning = format!(
+                            "Module '{}' version drifted from architech.lock: locked at {}, now resolving to {}",
+                            module.id, locked_version, module.version
+                        );
+                        warn!("{}", warning);
+                        self.app_state.add_log_leveled(project_id, crate::state::LogLevel::Warn, None, &warning).await;
                     }
                 }
             }
         }
-        
-        // Second pass: Create module tasks with dependencies
-        for module_id in &config.modules {
-            let module_deps = module_deps.get(module_id).cloned().unwrap_or_default();
-            
-            // All module tasks must depend on framework task
+
+        // Create module tasks in resolved order, each depending on its own
+        // (now-guaranteed-acyclic) dependencies, the framework task, and its
+        // own fetch tasks (external archives/starter repos pinned by
+        // sha256, see `commands::framework::Fetch`), if it declares any.
+        for module in &resolved {
             let mut all_deps = vec![framework_task_id.clone()];
-            all_deps.extend(module_deps);
-            
-            // Create the module task
+            all_deps.extend(module.dependencies.iter().map(|dep_id| format!("module:{}", dep_id)));
+
+            for fetch in &module.fetch {
+                let fetch_task = Box::new(crate::tasks::FetchTask::new(fetch.clone(), vec![framework_task_id.clone()]));
+                info!("Created fetch task for module {} asset '{}'", module.id, fetch.name);
+                all_deps.push(fetch_task.id().to_string());
+                tasks.push(fetch_task);
+            }
+
             let module_task = Box::new(ModuleTask::with_module_id(
-                module_id.clone(),
-                config.framework.clone(), 
+                module.id.clone(),
+                config.framework.clone(),
                 all_deps
             ));
-            
-            info!("Created module task for {} with dependencies: {:?}", module_id, module_task.dependencies());
+
+            info!("Created module task for {} with dependencies: {:?}", module.id, module_task.dependencies());
             tasks.push(module_task);
         }
-        
-        // Step 3: Cleanup task - Depends on all module tasks and framework task
+
+        // Step 2.5: Recipe-declared tasks. A module may ship a
+        // `recipes/<module_id>.yaml` file naming extra tasks (post-install
+        // steps, inter-module ordering) instead of requiring a
+        // `ProjectGenerator` change; see `tasks::Recipe`. Defaults to
+        // depending on that module's own task when a recipe task doesn't
+        // declare its own `depends`.
+        let mut recipe_task_ids = Vec::new();
+        for module in &resolved {
+            let Some(recipe) = crate::tasks::Recipe::load_for_module(&module.id) else {
+                continue;
+            };
+            let default_deps = vec![format!("module:{}", module.id)];
+            for recipe_task in recipe.into_tasks(&default_deps) {
+                info!(
+                    "Created recipe task {} for module {} with dependencies: {:?}",
+                    recipe_task.id(), module.id, recipe_task.dependencies()
+                );
+                recipe_task_ids.push(recipe_task.id().to_string());
+                tasks.push(recipe_task);
+            }
+        }
+
+        // Step 3: Cleanup task - Depends on all module tasks, recipe tasks
+        // and the framework task
         let mut cleanup_deps = Vec::new();
         cleanup_deps.push(framework_task_id.clone());
-        
-        for module_id in &config.modules {
-            cleanup_deps.push(format!("module:{}", module_id));
+
+        for module in &resolved {
+            cleanup_deps.push(format!("module:{}", module.id));
         }
-        
+        cleanup_deps.extend(recipe_task_ids);
+
         debug!("Creating cleanup task with dependencies: {:?}", cleanup_deps);
         let mut cleanup_task = Box::new(CleanupTask::new(context.clone()));
         cleanup_task.set_dependencies(cleanup_deps);
-        
-        info!("Created cleanup task with ID: {}", cleanup_task.id());
+        let cleanup_task_id = cleanup_task.id().to_string();
+
+        info!("Created cleanup task with ID: {}", cleanup_task_id);
         tasks.push(cleanup_task);
-        
-        info!("Created {} tasks for project: {}", tasks.len(), project_id);
-        
-        // Validate task dependencies
-        let mut all_task_ids = HashSet::new();
-        for task in &tasks {
-            all_task_ids.insert(task.id().to_string());
-        }
-        
-        // Check that all dependencies exist
-        for task in &tasks {
-            for dep in task.dependencies() {
-                if !all_task_ids.contains(dep) {
-                    warn!("Task {} depends on non-existent task {}", task.id(), dep);
-                    return Err(format!("Task {} depends on non-existent task {}", task.id(), dep));
-                }
+
+        // Step 3.5: Lockfile task - pins the resolved framework/module
+        // versions once cleanup has finished, for reproducible regeneration.
+        let mut lockfile_task = Box::new(crate::tasks::LockfileTask::new());
+        lockfile_task.set_dependencies(vec![cleanup_task_id]);
+
+        info!("Created lockfile task with ID: {}", lockfile_task.id());
+        tasks.push(lockfile_task);
+
+        // Step 4: Declarable pipeline steps, each depending on the steps the
+        // config marks in `depends_on`, or on the framework task when it marks none.
+        for step in &config.pipeline {
+            let mut deps: Vec<String> = step
+                .depends_on
+                .iter()
+                .map(|dep_id| format!("step:{}", dep_id))
+                .collect();
+            if deps.is_empty() {
+                deps.push(framework_task_id.clone());
             }
+
+            let step_task = Box::new(crate::tasks::PipelineStepTask::new(step.clone(), deps));
+            info!("Created pipeline step task for '{}' with dependencies: {:?}", step.id, step_task.dependencies());
+            tasks.push(step_task);
         }
-        
-        // Detect dependency cycles
+
+        info!("Created {} tasks for project: {}", tasks.len(), project_id);
+
+        // Validate task dependencies: detect both cycles and references to
+        // non-existent tasks, reporting the full chain that reaches the
+        // problem edge rather than just the node where the walk started.
         let mut task_map = HashMap::new();
         for task in &tasks {
             task_map.insert(task.id().to_string(), task.dependencies().to_vec());
         }
-        
-        // Simple cycle detection
-        fn has_cycle(task_id: &str, task_map: &HashMap<String, Vec<String>>, visited: &mut HashSet<String>, path: &mut HashSet<String>) -> bool {
-            if path.contains(task_id) {
-                return true;
+
+        // DFS that threads an ordered root-to-current `path` so a detected
+        // cycle or missing dependency can be reported as a full chain (e.g.
+        // `framework -> module:auth -> module:db -> module:auth`) instead of
+        // an arbitrary single node.
+        fn find_dependency_issue(
+            task_id: &str,
+            task_map: &HashMap<String, Vec<String>>,
+            visited: &mut HashSet<String>,
+            path: &mut Vec<String>,
+        ) -> Option<String> {
+            if let Some(cycle_start) = path.iter().position(|id| id == task_id) {
+                let mut chain = path[cycle_start..].to_vec();
+                chain.push(task_id.to_string());
+                return Some(format!("Dependency cycle detected in task graph: {}", chain.join(" -> ")));
             }
-            
+
             if visited.contains(task_id) {
-                return false;
+                return None;
             }
-            
             visited.insert(task_id.to_string());
-            path.insert(task_id.to_string());
-            
+            path.push(task_id.to_string());
+
             if let Some(deps) = task_map.get(task_id) {
                 for dep in deps {
-                    if has_cycle(dep, task_map, visited, path) {
-                        return true;
+                    if !task_map.contains_key(dep) {
+                        let mut chain = path.clone();
+                        chain.push(dep.clone());
+                        return Some(format!("Task depends on non-existent task: {}", chain.join(" -> ")));
+                    }
+                    if let Some(issue) = find_dependency_issue(dep, task_map, visited, path) {
+                        return Some(issue);
                     }
                 }
             }
-            
-            path.remove(task_id);
-            false
+
+            path.pop();
+            None
         }
-        
+
         let mut visited = HashSet::new();
         for task_id in task_map.keys() {
-            let mut path = HashSet::new();
-            if has_cycle(task_id, &task_map, &mut visited, &mut path) {
-                warn!("Dependency cycle detected in task: {}", task_id);
-                return Err(format!("Dependency cycle detected in task graph starting from: {}", task_id));
+            let mut path = Vec::new();
+            if let Some(issue) = find_dependency_issue(task_id, &task_map, &mut visited, &mut path) {
+                warn!("{}", issue);
+                return Err(issue);
             }
         }
-        
+
         // Debug: Print all tasks and their dependencies
         debug!("Task dependency structure:");
         for task in &tasks {
@@ -455,19 +557,89 @@ impl ProjectGenerator {
         let project_path = PathBuf::from(&config.path).join(&config.name);
         debug!("Project path for task execution: {}", project_path.display());
         let project_path_arc = Arc::from(project_path.as_path());
-        
+
+        // Tasks write into this staging directory instead of project_path
+        // directly, so a mid-pipeline failure never leaves a half-built
+        // project folder behind -- it's promoted to project_path only once
+        // every task has succeeded (see the commit step below), and simply
+        // dropped (deleting its contents) on any error path out of this
+        // function.
+        let staging = tempfile::tempdir()
+            .map_err(|e| format!("Failed to create staging directory: {}", e))?;
+        let staging_dir_arc: Arc<Path> = Arc::from(staging.path());
+        debug!("Staging directory for task execution: {}", staging.path().display());
+
         // Create task context
         let context = TaskContext {
             project_id: project_id.to_string(),
             project_dir: project_path_arc,
+            staging_dir: staging_dir_arc,
             app_handle: app_handle.clone(),
             config: Arc::new(config.clone()),
+            rollback_on_failure: config.rollback_on_failure,
+            dependency_outputs: HashMap::new(),
         };
-        
+
+        // Register a background worker for this run so the UI can
+        // introspect its live status and pause/resume/cancel it, and spawn
+        // the listener that applies control actions as they arrive.
+        let (worker_handle, mut worker_actions) = app_state.workers.register(project_id).await;
+        {
+            let worker_handle = worker_handle.clone();
+            let app_state = app_state.clone();
+            let project_id = project_id.to_string();
+            tokio::spawn(async move {
+                while let Some(action) = worker_actions.recv().await {
+                    worker_handle.apply(action).await;
+                    let status = worker_handle.snapshot().await.status;
+                    app_state.emit_event(crate::state::ProjectEvent::WorkerStateChanged {
+                        project_id: project_id.clone(),
+                        status,
+                    }).await;
+                }
+            });
+        }
+
+        // Watchdog: if this worker stops reporting progress for too long,
+        // presume it's stuck and fail the project in a resumable state
+        // rather than leaving the UI waiting on a run that will never finish.
+        {
+            let worker_handle = worker_handle.clone();
+            let app_state = app_state.clone();
+            let project_id = project_id.to_string();
+            tokio::spawn(async move {
+                loop {
+                    sleep(Duration::from_secs(30)).await;
+
+                    match app_state.workers.get(&project_id).await {
+                        Some(current) if Arc::ptr_eq(&current, &worker_handle) => {
+                            if worker_handle.seconds_since_progress() > app_state.workers.dead_worker_timeout_secs() {
+                                warn!("Worker for project {} reported no progress for over {}s, marking dead", project_id, app_state.workers.dead_worker_timeout_secs());
+                                worker_handle.mark_dead().await;
+                                app_state.emit_event(crate::state::ProjectEvent::WorkerStateChanged {
+                                    project_id: project_id.clone(),
+                                    status: crate::worker::WorkerStatus::Dead,
+                                }).await;
+                                app_state.set_project_status(&project_id, ProjectStatus::Failed {
+                                    error: "Generation worker stopped reporting progress".to_string(),
+                                    resumable: true,
+                                }).await;
+                                break;
+                            }
+                        },
+                        // Worker was removed (run ended) or replaced by a newer run.
+                        _ => break,
+                    }
+                }
+            });
+        }
+
         // Create task executor
-        let executor = TaskExecutor::new(context, tasks);
-        debug!("Created TaskExecutor with {} tasks", executor.get_task_count());
-        
+        let max_concurrency = config.max_parallel_tasks.unwrap_or(crate::tasks::DEFAULT_MAX_PARALLEL_TASKS);
+        let executor = TaskExecutor::new(context, tasks, app_state.clone(), worker_handle.clone())
+            .with_max_concurrency(max_concurrency);
+        debug!("Created TaskExecutor with {} tasks, max concurrency {}", executor.get_task_count(), max_concurrency);
+
         // Log execution start
         info!("Starting task execution for project: {}", project_id);
         app_state.add_log(project_id, "Starting task execution").await;
@@ -483,27 +655,15 @@ impl ProjectGenerator {
             create_checkpoint, 
             checkpoint.is_some());
         
-        // Track progress
-        let total_tasks = executor.get_task_count();
-        let mut completed_tasks = 0;
-        
-        // Execute all tasks
-        debug!("Executing all tasks (total: {})", total_tasks);
-        let results = match executor.execute_all().await {
-            Ok(results) => {
-                debug!("Task execution completed with {} results", results.len());
-                for result in &results {
-                    // Update progress for each completed task
-                    if result.success {
-                        completed_tasks += 1;
-                        let progress = ((completed_tasks as f64 / total_tasks as f64) * 90.0) as u8 + 5;
-                        debug!("Task completed: {}, progress: {}%", result.message, progress);
-                        app_state.update_progress(project_id, &result.message, progress).await;
-                    } else {
-                        warn!("Task failed: {}", result.message);
-                    }
-                }
-                results
+        // Execute all tasks. `execute_all` reports progress itself as each
+        // task finishes (it runs tasks incrementally, not in synchronized
+        // rounds), so there's no post-hoc progress loop here.
+        debug!("Executing all tasks (total: {})", executor.get_task_count());
+        let report = match executor.execute_all().await {
+            Ok(report) => {
+                debug!("Task execution completed with {} results", report.results.len());
+                app_state.store_task_hashes(project_id, executor.computed_hashes().await).await;
+                report
             },
             Err(e) => {
                 // Set project status to failed
@@ -541,18 +701,31 @@ impl ProjectGenerator {
                 
                 // Log failure
                 error!("{}", error_msg);
-                app_state.add_log(project_id, &error_msg).await;
-                
+                app_state.add_log_leveled(project_id, crate::state::LogLevel::Error, None, &error_msg).await;
+
+                app_state.watchers.stop(project_id).await;
+                app_state.workers.remove(project_id).await;
                 return Err(e);
             }
         };
-        
+
         // Process results
         debug!("Processing task results");
-        for result in results {
+        for result in report.results {
             app_state.process_task_result(project_id, result).await;
         }
-        
+
+        // If the worker was cancelled mid-run, the loop above stopped
+        // dispatching new batches and left the remaining tasks `Pending` --
+        // they show up as neither completed nor failed, so without this
+        // check the logic below would misreport the run as `Completed`.
+        if worker_handle.is_cancelled() {
+            info!("Project generation stopped early due to cancellation: {}", project_id);
+            app_state.watchers.stop(project_id).await;
+            app_state.workers.remove(project_id).await;
+            return Ok(());
+        }
+
         // Check for any failed tasks
         let task_states = app_state.get_all_task_states(project_id).await;
         let failed_states = task_states
@@ -561,34 +734,99 @@ impl ProjectGenerator {
             .collect::<Vec<_>>();
             
         if !failed_states.is_empty() {
-            // Set project status to failed
-            let error = format!("{} tasks failed during generation", failed_states.len());
+            // Set project status to failed, describing whether completed
+            // tasks were successfully rolled back -- and, if a rollback ran
+            // at all, refuse to offer resume: resuming would skip tasks
+            // whose effects were just undone, since they're still recorded
+            // as completed.
+            let error = match &report.rollback {
+                RollbackOutcome::Incomplete(task_ids) => format!(
+                    "{} task(s) failed during generation; rollback of already-completed work did not fully succeed, so the project directory may contain partial files from: {:?}",
+                    failed_states.len(), task_ids
+                ),
+                RollbackOutcome::RolledBack => format!(
+                    "{} task(s) failed during generation; already-completed work was rolled back",
+                    failed_states.len()
+                ),
+                RollbackOutcome::NotNeeded => format!("{} tasks failed during generation", failed_states.len()),
+            };
+            let resumable = matches!(report.rollback, RollbackOutcome::NotNeeded);
+
             error!("Project generation failed: {}", error);
             app_state.set_project_status(project_id, ProjectStatus::Failed {
                 error: error.clone(),
-                resumable: true,
+                resumable,
             }).await;
-            
+
             // Log failure
-            error!("Project generation failed: {}", error);
-            app_state.add_log(project_id, &error).await;
-            
+            app_state.add_log_leveled(project_id, crate::state::LogLevel::Error, None, &error).await;
+
+            app_state.watchers.stop(project_id).await;
+            app_state.workers.remove(project_id).await;
             return Err(error);
         }
-        
+
+        // Every task succeeded against the staging directory -- promote it
+        // to the real project path now, atomically if possible.
+        if let Err(e) = Self::commit_staging_dir(staging.path(), &project_path) {
+            error!("Project generation failed: {}", e);
+            app_state.set_project_status(project_id, ProjectStatus::Failed {
+                error: e.clone(),
+                resumable: false,
+            }).await;
+            app_state.add_log_leveled(project_id, crate::state::LogLevel::Error, None, &e).await;
+            app_state.watchers.stop(project_id).await;
+            app_state.workers.remove(project_id).await;
+            return Err(e);
+        }
+
         // Set project status to completed
         debug!("Setting project status to completed");
         app_state.set_project_status(project_id, ProjectStatus::Completed {
             path: project_path.to_string_lossy().to_string(),
         }).await;
-        
+
         // Log completion
         info!("Project generation completed successfully: {}", project_id);
         app_state.add_log(project_id, "Project generation completed successfully").await;
-        
+
+        app_state.watchers.stop(project_id).await;
+        app_state.workers.remove(project_id).await;
+
         Ok(())
     }
-    
+
+    /// Promote a finished staging directory to the final project path.
+    /// Tries a plain rename first -- instant, and atomic as long as both
+    /// paths are on the same filesystem. Falls back to a recursive copy
+    /// (then removing the staging directory) for the cross-device case,
+    /// where `rename` always fails.
+    fn commit_staging_dir(staging_dir: &Path, project_path: &Path) -> Result<(), String> {
+        if fs::rename(staging_dir, project_path).is_ok() {
+            return Ok(());
+        }
+
+        Self::copy_dir_recursive(staging_dir, project_path)
+            .map_err(|e| format!("Failed to move staged project into place: {}", e))?;
+        fs::remove_dir_all(staging_dir)
+            .map_err(|e| format!("Failed to clean up staging directory after copy: {}", e))?;
+        Ok(())
+    }
+
+    fn copy_dir_recursive(src: &Path, dst: &Path) -> std::io::Result<()> {
+        fs::create_dir_all(dst)?;
+        for entry in fs::read_dir(src)? {
+            let entry = entry?;
+            let dst_path = dst.join(entry.file_name());
+            if entry.file_type()?.is_dir() {
+                Self::copy_dir_recursive(&entry.path(), &dst_path)?;
+            } else {
+                fs::copy(entry.path(), &dst_path)?;
+            }
+        }
+        Ok(())
+    }
+
     async fn resolve_module_dependencies(&self, modules: &[String]) -> Result<HashMap<String, Vec<String>>, String> {
         // Get all available modules
         let all_modules = crate::commands::framework::get_modules().await?;
@@ -613,56 +851,153 @@ impl ProjectGenerator {
             dependencies.insert(module_id.clone(), module_deps);
         }
         
-        // Check for circular dependencies
-        fn has_circular_dependency(
-            deps: &HashMap<String, Vec<String>>, 
-            current: &str,
-            visited: &mut Vec<String>,
-            path: &mut Vec<String>
-        ) -> Option<Vec<String>> {
-            if path.contains(&current.to_string()) {
-                // We found a cycle
-                let cycle_start = path.iter().position(|id| id == current).unwrap();
-                let mut cycle = path[cycle_start..].to_vec();
-                cycle.push(current.to_string());
-                return Some(cycle);
-            }
-            
-            if visited.contains(&current.to_string()) {
-                // Already checked this module, no cycle found
-                return None;
-            }
-            
-            visited.push(current.to_string());
-            path.push(current.to_string());
-            
-            if let Some(module_deps) = deps.get(current) {
-                for dep in module_deps {
-                    if let Some(cycle) = has_circular_dependency(deps, dep, visited, path) {
-                        return Some(cycle);
-                    }
-                }
-            }
-            
-            path.pop();
-            None
-        }
-        
         // Check each module for circular dependencies
         for module_id in modules {
             let mut visited = Vec::new();
             let mut path = Vec::new();
-            if let Some(cycle) = has_circular_dependency(&dependencies, module_id, &mut visited, &mut path) {
+            if let Some(cycle) = Self::has_circular_dependency_among(&dependencies, module_id, &mut visited, &mut path) {
                 return Err(format!("Circular dependency detected among modules: {:?}", cycle));
             }
         }
         
         // Log dependency resolution
         info!("Resolved module dependencies: {:?}", dependencies);
-        
+
         Ok(dependencies)
     }
-    
+
+    /// Turn the dependency map `resolve_module_dependencies` built into an
+    /// install order where every module comes after everything it depends
+    /// on, via Kahn's algorithm: seed a queue with every zero-in-degree
+    /// module, repeatedly pop one into the output, and decrement the
+    /// in-degree of everything that depended on it, enqueuing any that just
+    /// reached zero. A shorter output than the input means a cycle remains
+    /// among the modules that never reached zero in-degree -- re-run the
+    /// existing DFS cycle check against just those to report it.
+    fn topological_module_order(dependencies: &HashMap<String, Vec<String>>) -> Result<Vec<String>, String> {
+        let mut in_degree: HashMap<String, usize> = dependencies.keys().map(|id| (id.clone(), 0)).collect();
+        for (module_id, deps) in dependencies {
+            if let Some(count) = in_degree.get_mut(module_id) {
+                *count += deps.len();
+            }
+        }
+
+        // A module's in-degree here counts its own unmet dependencies, so
+        // Kahn's algorithm needs the reverse adjacency (dependents) to know
+        // who to decrement once a module is popped.
+        let mut dependents: HashMap<String, Vec<String>> = HashMap::new();
+        for (module_id, deps) in dependencies {
+            for dep in deps {
+                dependents.entry(dep.clone()).or_default().push(module_id.clone());
+            }
+        }
+
+        // Deterministic order given the same input, rather than whatever
+        // order the HashMap happened to iterate in.
+        let mut seed: Vec<String> = in_degree
+            .iter()
+            .filter(|(_, &count)| count == 0)
+            .map(|(id, _)| id.clone())
+            .collect();
+        seed.sort();
+        let mut queue: VecDeque<String> = seed.into();
+
+        let mut order = Vec::with_capacity(dependencies.len());
+        while let Some(module_id) = queue.pop_front() {
+            order.push(module_id.clone());
+
+            if let Some(module_dependents) = dependents.get(&module_id) {
+                let mut newly_ready = Vec::new();
+                for dependent in module_dependents {
+                    if let Some(count) = in_degree.get_mut(dependent) {
+                        *count -= 1;
+                        if *count == 0 {
+                            newly_ready.push(dependent.clone());
+                        }
+                    }
+                }
+                newly_ready.sort();
+                for dependent in newly_ready {
+                    queue.push_back(dependent);
+                }
+            }
+        }
+
+        if order.len() < dependencies.len() {
+            let unresolved: Vec<String> = dependencies
+                .keys()
+                .filter(|id| !order.contains(id))
+                .cloned()
+                .collect();
+
+            for module_id in &unresolved {
+                let mut visited = Vec::new();
+                let mut path = Vec::new();
+                if let Some(cycle) = Self::has_circular_dependency_among(dependencies, module_id, &mut visited, &mut path) {
+                    return Err(format!("Circular dependency detected among modules: {:?}", cycle));
+                }
+            }
+            return Err(format!("Circular dependency detected among modules: {:?}", unresolved));
+        }
+
+        Ok(order)
+    }
+
+    /// Shared DFS used by both the up-front cycle check in
+    /// `resolve_module_dependencies` and `topological_module_order`'s
+    /// fallback error reporting.
+    fn has_circular_dependency_among(
+        deps: &HashMap<String, Vec<String>>,
+        current: &str,
+        visited: &mut Vec<String>,
+        path: &mut Vec<String>,
+    ) -> Option<Vec<String>> {
+        if path.contains(&current.to_string()) {
+            let cycle_start = path.iter().position(|id| id == current).unwrap();
+            let mut cycle = path[cycle_start..].to_vec();
+            cycle.push(current.to_string());
+            return Some(cycle);
+        }
+
+        if visited.contains(&current.to_string()) {
+            return None;
+        }
+
+        visited.push(current.to_string());
+        path.push(current.to_string());
+
+        if let Some(module_deps) = deps.get(current) {
+            for dep in module_deps {
+                if let Some(cycle) = Self::has_circular_dependency_among(deps, dep, visited, path) {
+                    return Some(cycle);
+                }
+            }
+        }
+
+        path.pop();
+        None
+    }
+
+    /// Install every selected module via `setup_module`, in dependency
+    /// order (see `topological_module_order`), so a module's npm/file
+    /// operations always run after whatever it depends on instead of in
+    /// whatever order the caller originally listed them.
+    async fn install_modules(
+        &self,
+        config: &crate::commands::project::ProjectConfig,
+        project_dir: &Path,
+        app_handle: AppHandle,
+    ) -> Result<(), String> {
+        let dependencies = self.resolve_module_dependencies(&config.modules).await?;
+        let order = Self::topological_module_order(&dependencies)?;
+
+        for module_id in &order {
+            Self::setup_module(module_id, config, project_dir, app_handle.clone()).await?;
+        }
+
+        Ok(())
+    }
+
     // Framework setup implementation
     async fn setup_framework(
         config: &crate::commands::project::ProjectConfig,
@@ -679,8 +1014,16 @@ impl ProjectGenerator {
             "progress": 0.2
         })).unwrap_or_else(|e| error!("Failed to emit progress event: {}", e));
         
-        // Prepare CLI arguments
-        let cmd_name = framework.cli.base_command.clone();
+        // Prepare CLI arguments. `resolved_base_command` picks this OS's
+        // `platform_commands` entry over the flat `base_command` when one's
+        // defined, so a framework whose scaffolder needs a different
+        // invocation on Windows/macOS/Linux doesn't have to pick one and
+        // break the others.
+        let resolved_base = framework.cli.resolved_base_command();
+        let (cmd_name, platform_leading_args) = resolved_base
+            .split_first()
+            .map(|(name, rest)| (name.clone(), rest.to_vec()))
+            .unwrap_or_else(|| (framework.cli.base_command.clone(), Vec::new()));
         let mut cmd_args = Vec::new();
         
         // Add flag arguments
@@ -728,23 +1071,42 @@ impl ProjectGenerator {
         
         // Add project name as a positional argument
         cmd_args.push(&config.name);
-        
+
+        // Platform-specific leading args (if this OS has a `platform_commands`
+        // override) come before the rest of the resolved arguments.
+        let full_args: Vec<&str> = platform_leading_args
+            .iter()
+            .map(|s| s.as_str())
+            .chain(cmd_args.iter().copied())
+            .collect();
+
         // Log what we're about to do
-        app_handle.emit("log-message", format!("Setting up framework with command: {} {}", cmd_name, cmd_args.join(" "))).unwrap();
-        
+        app_handle.emit("log-message", format!("Setting up framework with command: {} {}", cmd_name, full_args.join(" "))).unwrap();
+
         // Emit progress update for command execution
         app_handle.emit("generation-progress", serde_json::json!({
             "step": "framework",
-            "message": format!("Running framework setup command: {} {}", cmd_name, cmd_args.join(" ")),
+            "message": format!("Running framework setup command: {} {}", cmd_name, full_args.join(" ")),
             "progress": 0.3
         })).unwrap_or_else(|e| error!("Failed to emit progress event: {}", e));
-        
+
         // Execute the command with reasonable timeout
-        let cmd_result = ProjectGenerator::execute_command(&cmd_name, &cmd_args, Path::new(&config.path)).await?;
-        
+        let outcome = ProjectGenerator::execute_command(&cmd_name, &full_args, Path::new(&config.path), &framework.id, &app_handle).await?;
+        let cmd_result = match outcome {
+            ExecutionOutcome::Completed(result) => result,
+            ExecutionOutcome::Cancelled => {
+                return Err(SetupError::Cancelled { module_id: framework.id.clone() }.render());
+            }
+        };
+
         // Ensure the command completed successfully
         if !cmd_result.success {
-            return Err(format!("Framework setup failed: {}", cmd_result.stderr));
+            return Err(SetupError::CommandFailed {
+                module_id: framework.id.clone(),
+                operation_index: 0,
+                command: format!("{} {}", cmd_name, full_args.join(" ")),
+                stderr: cmd_result.stderr,
+            }.render());
         }
         
         // Emit progress update for completion
@@ -801,7 +1163,7 @@ impl ProjectGenerator {
         let all_modules = get_modules().await?;
         let module = all_modules.iter()
             .find(|m| m.id == module_id)
-            .ok_or_else(|| format!("Module not found: {}", module_id))?;
+            .ok_or_else(|| SetupError::ModuleNotFound { module_id: module_id.to_string() }.render())?;
         
         // Log
         app_handle.emit("log-message", format!("Setting up module: {}", module.name)).unwrap();
@@ -810,8 +1172,8 @@ impl ProjectGenerator {
         let package_json_path = project_dir.join("package.json");
         if !package_json_path.exists() && !module.installation.commands.is_empty() {
             let has_npm_commands = module.installation.commands.iter()
-                .any(|cmd| cmd.starts_with("npm") || cmd.starts_with("npx"));
-                
+                .any(|cmd| !matches!(cmd.package_manager, crate::commands::command_spec::PackageManager::None));
+
             if has_npm_commands {
                 app_handle.emit("log-message", "Creating package.json before npm operations").unwrap();
                 let default_package = r#"{
@@ -836,9 +1198,10 @@ impl ProjectGenerator {
         for (i, cmd) in module.installation.commands.iter().enumerate() {
             // Update progress
             app_handle.emit("task-progress", format!("Running command {}/{}", i+1, module.installation.commands.len())).unwrap();
-            
+
             // Parse the command
-            let parts: Vec<&str> = cmd.split_whitespace().collect();
+            let command_str = cmd.to_command_string();
+            let parts: Vec<&str> = command_str.split_whitespace().collect();
             if parts.is_empty() {
                 continue;
             }
@@ -849,17 +1212,22 @@ impl ProjectGenerator {
             // Add retry logic for commands that might fail due to timing issues
             let max_retries = 3;
             let mut success = false;
-            
+            let mut last_stderr = String::new();
+
             for attempt in 1..=max_retries {
-                match ProjectGenerator::execute_command(cmd_name, cmd_args, project_dir).await {
-                    Ok(result) => {
+                match ProjectGenerator::execute_command(cmd_name, cmd_args, project_dir, &module.id, &app_handle).await {
+                    Ok(ExecutionOutcome::Cancelled) => {
+                        return Err(SetupError::Cancelled { module_id: module.id.clone() }.render());
+                    }
+                    Ok(ExecutionOutcome::Completed(result)) => {
                         if result.success {
                             success = true;
                             break;
                         } else {
-                            let error_msg = format!("Command failed (attempt {}/{}): {}", attempt, max_retries, result.stderr);
+                            last_stderr = result.stderr;
+                            let error_msg = format!("Command failed (attempt {}/{}): {}", attempt, max_retries, last_stderr);
                             app_handle.emit("log-message", &error_msg).unwrap();
-                            
+
                             if attempt == max_retries {
                                 app_handle.emit("log-message", "All attempts failed, continuing with next command").unwrap();
                             } else {
@@ -869,9 +1237,10 @@ impl ProjectGenerator {
                         }
                     },
                     Err(e) => {
+                        last_stderr = e.clone();
                         let error_msg = format!("Command execution error (attempt {}/{}): {}", attempt, max_retries, e);
                         app_handle.emit("log-message", &error_msg).unwrap();
-                        
+
                         if attempt == max_retries {
                             app_handle.emit("log-message", "All attempts failed, continuing with next command").unwrap();
                         } else {
@@ -881,11 +1250,17 @@ impl ProjectGenerator {
                     }
                 }
             }
-            
-            // If all retries failed but this is a critical command, we might want to fail the entire module setup
-            if !success && (cmd.contains("npm install") || cmd.contains("npx") || cmd.contains("npm i")) {
-                app_handle.emit("log-message", "Critical command failed after all retries").unwrap();
-                // We'll still try the file operations, but log a warning
+
+            // If all retries failed and this is a critical command, fail the
+            // whole module setup with a diagnostic naming the module and
+            // operation instead of silently moving on to file operations.
+            if !success && cmd.critical {
+                return Err(SetupError::CommandFailed {
+                    module_id: module.id.clone(),
+                    operation_index: i,
+                    command: command_str.clone(),
+                    stderr: last_stderr,
+                }.render());
             }
             
             // Add a delay between commands to ensure file system consistency
@@ -927,6 +1302,15 @@ impl ProjectGenerator {
                     if !file_path.exists() {
                         app_handle.emit("log-message", format!("File does not exist, cannot modify: {}", file_path.display())).unwrap();
                     } else {
+                        // `modify_file` replaces `pattern` with `replacement` and
+                        // succeeds even when `pattern` matched nothing -- a
+                        // silent no-op that leaves a manifest author with no
+                        // signal their regex/anchor went stale. Check the match
+                        // up front so a miss is a hard diagnostic instead.
+                        let content = fs::read_to_string(&file_path).unwrap_or_default();
+                        if !content.contains(&op.pattern) {
+                            return Err(SetupError::pattern_not_matched(&module.id, i, op, &op.pattern).render());
+                        }
                         if let Err(e) = modify_file(&file_path, &op.pattern, &op.replacement) {
                             app_handle.emit("log-message", format!("Failed to modify file: {}", e)).unwrap();
                         } else {
@@ -937,12 +1321,21 @@ impl ProjectGenerator {
                 "modify_import" => {
                     if !file_path.exists() {
                         app_handle.emit("log-message", format!("File does not exist, cannot modify imports: {}", file_path.display())).unwrap();
+                    } else if modify_import(&file_path, &op.action, &op.import).is_err() {
+                        // As with `modify` above, a failed import edit (the
+                        // import anchor wasn't found) is surfaced as a hard
+                        // diagnostic naming the module and manifest field
+                        // instead of a logged-and-ignored warning.
+                        return Err(SetupError::pattern_not_matched(&module.id, i, op, &op.import).render());
                     } else {
-                        if let Err(e) = modify_import(&file_path, &op.action, &op.import) {
-                            app_handle.emit("log-message", format!("Failed to modify import: {}", e)).unwrap();
-                        } else {
-                            app_handle.emit("log-message", format!("Modified imports in: {}", file_path.display())).unwrap();
-                        }
+                        app_handle.emit("log-message", format!("Modified imports in: {}", file_path.display())).unwrap();
+                    }
+                },
+                "json-merge" => {
+                    if let Err(e) = crate::commands::file::json_merge_file(&file_path, &op.content, &op.merge_strategy) {
+                        app_handle.emit("log-message", format!("Failed to merge JSON into file: {}", e)).unwrap();
+                    } else {
+                        app_handle.emit("log-message", format!("Merged JSON into file: {}", file_path.display())).unwrap();
                     }
                 },
                 _ => {
@@ -982,24 +1375,29 @@ impl ProjectGenerator {
             // NPM install is critical, so try multiple times with increasing timeouts
             let max_retries = 3;
             let mut success = false;
-            
+            let mut last_stderr = String::new();
+
             for attempt in 1..=max_retries {
                 // Longer timeout and wait for each retry
                 let timeout_seconds = 60 + (attempt * 30); // 90s, 120s, 150s
-                app_handle.emit("log-message", format!("Running npm install (attempt {}/{}, timeout {}s)...", 
+                app_handle.emit("log-message", format!("Running npm install (attempt {}/{}, timeout {}s)...",
                     attempt, max_retries, timeout_seconds)).unwrap();
-                
-                match ProjectGenerator::execute_command("npm", &["install"], project_dir).await {
-                    Ok(result) => {
+
+                match ProjectGenerator::execute_command("npm", &["install"], project_dir, "cleanup", &app_handle).await {
+                    Ok(ExecutionOutcome::Cancelled) => {
+                        return Err(SetupError::Cancelled { module_id: "cleanup".to_string() }.render());
+                    }
+                    Ok(ExecutionOutcome::Completed(result)) => {
                         if result.success {
                             app_handle.emit("log-message", "NPM dependencies installed successfully").unwrap();
                             success = true;
                             break;
                         } else {
-                            app_handle.emit("log-message", 
-                                format!("NPM install failed (attempt {}/{}): {}", 
-                                    attempt, max_retries, result.stderr)).unwrap();
-                                    
+                            last_stderr = result.stderr;
+                            app_handle.emit("log-message",
+                                format!("NPM install failed (attempt {}/{}): {}",
+                                    attempt, max_retries, last_stderr)).unwrap();
+
                             if attempt < max_retries {
                                 app_handle.emit("log-message", "Waiting before retry...").unwrap();
                                 sleep(Duration::from_secs((attempt * 5) as u64)).await;
@@ -1007,10 +1405,11 @@ impl ProjectGenerator {
                         }
                     },
                     Err(e) => {
-                        app_handle.emit("log-message", 
-                            format!("NPM install error (attempt {}/{}): {}", 
+                        last_stderr = e.clone();
+                        app_handle.emit("log-message",
+                            format!("NPM install error (attempt {}/{}): {}",
                                 attempt, max_retries, e)).unwrap();
-                                
+
                         if attempt < max_retries {
                             app_handle.emit("log-message", "Waiting before retry...").unwrap();
                             sleep(Duration::from_secs((attempt * 5) as u64)).await;
@@ -1018,10 +1417,20 @@ impl ProjectGenerator {
                     }
                 }
             }
-            
+
             if !success {
-                // Continue despite error, but log a warning
-                app_handle.emit("log-message", "⚠️ Warning: Failed to install NPM dependencies after multiple attempts").unwrap();
+                // Cleanup is best-effort -- a failed npm install here
+                // doesn't fail the whole project, but it's still rendered
+                // as a full diagnostic (module id, command, stderr) instead
+                // of a bare warning string, so it's just as actionable in
+                // the logs as a hard failure elsewhere in this module.
+                let diagnostic = SetupError::CommandFailed {
+                    module_id: "cleanup".to_string(),
+                    operation_index: 0,
+                    command: "npm install".to_string(),
+                    stderr: last_stderr,
+                }.render();
+                app_handle.emit("log-message", format!("⚠️ Warning: Failed to install NPM dependencies after multiple attempts\n{}", diagnostic)).unwrap();
             }
         } else if package_json_path.exists() && node_modules_path.exists() {
             app_handle.emit("log-message", "Node modules already installed, skipping npm install").unwrap();
@@ -1035,10 +1444,13 @@ impl ProjectGenerator {
             app_handle.emit("log-message", "Running code formatting...").unwrap();
             
             // Format the project code if possible
-            let format_result = ProjectGenerator::execute_command("npm", &["run", "format"], project_dir).await;
-            
+            let format_result = ProjectGenerator::execute_command("npm", &["run", "format"], project_dir, "cleanup", &app_handle).await;
+
             match format_result {
-                Ok(result) => {
+                Ok(ExecutionOutcome::Cancelled) => {
+                    app_handle.emit("log-message", "Code formatting was cancelled").unwrap();
+                }
+                Ok(ExecutionOutcome::Completed(result)) => {
                     if !result.success {
                         app_handle.emit("log-message", "Warning: Code formatting failed, but continuing").unwrap();
                     } else {
@@ -1078,205 +1490,71 @@ impl ProjectGenerator {
         Ok(())
     }
     
-    // Execute a shell command with proper error handling and output capture
+    /// Execute a shell command, streaming each stdout/stderr line to the
+    /// frontend as a `command-output` event tagged with `label` (the
+    /// module or framework id this command belongs to) instead of
+    /// buffering everything until the process exits.
+    ///
+    /// Listens for a `cancel-generation` Tauri event for the duration of
+    /// the command; if one arrives, the child (and anything it spawned) is
+    /// killed immediately via `kill_process_tree` and `Cancelled` is
+    /// returned without running any further retries -- so a stuck `npm
+    /// install` can be stopped instead of waiting out its retry/timeout
+    /// loops.
     async fn execute_command(
         command: &str,
         args: &[&str],
-        working_dir: &Path
-    ) -> Result<CommandRunnerResult, String> {
-        use std::io::{BufRead, BufReader};
-        use std::process::{Command, Stdio};
-        use std::thread::sleep as thread_sleep;
-        use std::time::Duration as StdDuration;
-        use tokio::time::{sleep, Duration};
-        
-        let command_display = format!("{} {}", command, args.join(" "));
-        println!("Executing command: {} in {}", command_display, working_dir.display());
-        
-        // Check if this is a create-next-app command or similar
-        let is_project_generator = 
-            (command == "npx" && args.len() > 0 && args[0].contains("create-")) ||
+        working_dir: &Path,
+        label: &str,
+        app_handle: &AppHandle,
+    ) -> Result<ExecutionOutcome, String> {
+        use crate::commands::command_runner::{kill_process_tree, CommandBuilder};
+        use tauri::Listener;
+
+        let is_project_generator =
+            (command == "npx" && !args.is_empty() && args[0].contains("create-")) ||
             (command == "npm" && args.len() > 1 && args[0] == "init");
-            
-        // Check if this is a project directory that we need to verify gets created
-        let project_name = if is_project_generator && args.len() > 0 {
-            args.last().map(|s| s.to_string())
-        } else {
-            None
-        };
-        
-        // Adjust command for platform if needed
-        let platform_cmd = if (command == "npm" || command == "npx") && cfg!(windows) {
-            format!("{}.cmd", command)
-        } else {
-            command.to_string()
-        };
-        
-        // We'll try the command up to 2 times for generators
         let max_retries = if is_project_generator { 2 } else { 1 };
-        
-        for attempt in 1..=max_retries {
-            // Create a new command instance for each attempt
-            let mut cmd = Command::new(&platform_cmd);
-            cmd.args(args)
-                .current_dir(working_dir)
-                .stdout(Stdio::piped())
-                .stderr(Stdio::piped());
-            
-            // Set environment variables
-            if let Ok(path) = std::env::var("PATH") {
-                cmd.env("PATH", path);
-            }
-            
-            // Force interactive mode for npm
-            if command == "npm" || command == "npx" {
-                cmd.env("CI", "false");
-                cmd.env("NODE_ENV", "development");
-            }
-            
-            // Create a clone of cmd for this attempt
-            let mut cmd_for_closure = cmd;
-        
-            // Execute with a timeout
-            let spawn_result = tokio::task::spawn_blocking(move || {
-                match cmd_for_closure.spawn() {
-                    Ok(mut child) => {
-                        let mut stdout_lines = Vec::new();
-                        let mut stderr_lines = Vec::new();
-                        
-                        // Read stdout lines
-                        if let Some(stdout) = child.stdout.take() {
-                            let stdout_reader = BufReader::new(stdout);
-                            for line in stdout_reader.lines() {
-                                if let Ok(line) = line {
-                                    println!("[STDOUT] {}", line);
-                                    stdout_lines.push(line);
-                                }
-                            }
-                        }
-                        
-                        // Read stderr lines
-                        if let Some(stderr) = child.stderr.take() {
-                            let stderr_reader = BufReader::new(stderr);
-                            for line in stderr_reader.lines() {
-                                if let Ok(line) = line {
-                                    println!("[STDERR] {}", line);
-                                    stderr_lines.push(line);
-                                }
-                            }
-                        }
-                        
-                        // Wait for process to complete
-                        match child.wait() {
-                            Ok(status) => {
-                                let exit_code = status.code().unwrap_or(-1);
-                                let success = status.success();
-                                
-                                CommandRunnerResult {
-                                    success,
-                                    stdout: stdout_lines.join("\n"),
-                                    stderr: stderr_lines.join("\n"),
-                                    exit_code,
-                                }
-                            },
-                            Err(e) => {
-                                CommandRunnerResult {
-                                    success: false,
-                                    stdout: stdout_lines.join("\n"),
-                                    stderr: format!("Failed to wait for command: {}", e),
-                                    exit_code: -1,
-                                }
-                            }
-                        }
-                    },
-                    Err(e) => {
-                        CommandRunnerResult {
-                            success: false,
-                            stdout: String::new(),
-                            stderr: format!("Failed to execute command: {}", e),
-                            exit_code: -1,
-                        }
-                    }
+
+        let cancelled = Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let cancelled_for_listener = cancelled.clone();
+        let listener_id = app_handle.listen("cancel-generation", move |_event| {
+            cancelled_for_listener.store(true, std::sync::atomic::Ordering::SeqCst);
+        });
+
+        let pid_reporter = Arc::new(std::sync::Mutex::new(None::<u32>));
+        let builder = CommandBuilder::new(command)
+            .args(args.iter().map(|a| a.to_string()))
+            .working_dir(working_dir)
+            .stream_to(app_handle.clone(), label.to_string())
+            .report_pid_to(pid_reporter.clone())
+            .retries(max_retries)
+            .verify_project_dir(is_project_generator);
+
+        let mut run = tokio::spawn(builder.execute());
+
+        let outcome = loop {
+            if cancelled.load(std::sync::atomic::Ordering::SeqCst) {
+                run.abort();
+                if let Some(pid) = pid_reporter.lock().unwrap().take() {
+                    kill_process_tree(pid);
                 }
-            }).await;
-            
-            match spawn_result {
-                Ok(result) => {
-                    // Special handling for npm/npx commands - we need to ensure filesystem sync
-                    if command == "npm" || command == "npx" {
-                        // For project generators like create-next-app, we need to verify project creation
-                        if is_project_generator && result.success {
-                            // First wait longer for filesystem to settle
-                            println!("Project generator command completed, waiting for filesystem to settle...");
-                            sleep(Duration::from_secs(3)).await;
-                            
-                            // If we have a project name to verify, check that it exists
-                            if let Some(project_name) = &project_name {
-                                let project_dir = working_dir.join(project_name);
-                                println!("Verifying project directory exists: {}", project_dir.display());
-                                
-                                // Try multiple times with increasing delays
-                                let mut dir_exists = false;
-                                for i in 0..5 {
-                                    if project_dir.exists() && project_dir.is_dir() {
-                                        dir_exists = true;
-                                        println!("Project directory verified!");
-                                        break;
-                                    }
-                                    println!("Directory not found, waiting (attempt {}/5)...", i+1);
-                                    thread_sleep(StdDuration::from_millis(500 * (i+1)));
-                                }
-                                
-                                if !dir_exists {
-                                    // If we've done max retries, fail, otherwise retry the command
-                                    if attempt == max_retries {
-                                        return Err(format!("Project directory {} was not created even though command reported success", project_dir.display()));
-                                    } else {
-                                        println!("Retrying command due to missing project directory (attempt {}/{})", attempt, max_retries);
-                                        sleep(Duration::from_secs(1)).await;
-                                        continue;
-                                    }
-                                }
-                                
-                                // If project exists, check for package.json
-                                let package_json = project_dir.join("package.json");
-                                if !package_json.exists() {
-                                    println!("Warning: package.json not found in project directory");
-                                } else {
-                                    println!("package.json verified!");
-                                }
-                            } else {
-                                // No project name to verify, use a standard delay
-                                sleep(Duration::from_secs(2)).await;
-                            }
-                        } else {
-                            // Standard delay for other npm/npx commands
-                            sleep(Duration::from_secs(1)).await;
-                        }
-                    }
-                    
-                    // If successful or final attempt, return the result
-                    if result.success || attempt == max_retries {
-                        return Ok(result);
-                    } else {
-                        // If failed but we have retries left
-                        println!("Command failed, retrying (attempt {}/{})", attempt, max_retries);
-                        sleep(Duration::from_secs(1)).await;
-                    }
-                },
-                Err(e) => {
-                    // If this is the final retry, return error, otherwise try again
-                    if attempt == max_retries {
-                        return Err(format!("Failed to execute command: {}", e));
-                    } else {
-                        println!("Command execution error, retrying (attempt {}/{})", attempt, max_retries);
-                        sleep(Duration::from_secs(1)).await;
-                    }
+                break Ok(ExecutionOutcome::Cancelled);
+            }
+
+            tokio::select! {
+                result = &mut run => {
+                    break match result {
+                        Ok(Ok(cmd_result)) => Ok(ExecutionOutcome::Completed(cmd_result)),
+                        Ok(Err(process_error)) => Err(process_error.to_string()),
+                        Err(join_error) => Err(format!("Command task panicked: {}", join_error)),
+                    };
                 }
+                _ = tokio::time::sleep(Duration::from_millis(150)) => {}
             }
-        }
-        
-        // We should never reach here (loop always returns), but satisfy the compiler
-        Err("Command execution failed after all retries".to_string())
+        };
+
+        app_handle.unlisten(listener_id);
+        outcome
     }
-} 
\ No newline at end of file
+}
\ No newline at end of file
@@ -0,0 +1,95 @@
+//! Typed, span-carrying error for the legacy module/framework setup path in
+//! `generation.rs`.
+//!
+//! Every failure there used to collapse to a `String` built ad hoc at each
+//! call site plus a `log-message` emit, which gives a manifest author no
+//! pointer to which module or operation actually went wrong. `SetupError`
+//! plays the same role here that `TaskError`/`ModuleTaskError` play for the
+//! task-based pipeline: a `miette::Diagnostic` carrying the offending module
+//! id and operation index, converted to a string only at the
+//! `Result<(), String>` boundary those functions still return.
+
+use miette::{Diagnostic, SourceSpan};
+use thiserror::Error;
+
+#[derive(Debug, Error, Diagnostic)]
+pub enum SetupError {
+    #[error("module not found: {module_id}")]
+    #[diagnostic(code(architech::generation::module_not_found))]
+    ModuleNotFound { module_id: String },
+
+    #[error("command '{command}' failed while setting up module '{module_id}' (operation {operation_index})")]
+    #[diagnostic(
+        code(architech::generation::command_failed),
+        help("stderr: {stderr}")
+    )]
+    CommandFailed {
+        module_id: String,
+        operation_index: usize,
+        command: String,
+        stderr: String,
+    },
+
+    /// A `modify`/`modify_import` operation whose `pattern`/`import` field
+    /// wasn't found anywhere in the target file -- previously `modify_file`
+    /// would silently no-op on a miss instead of telling the manifest
+    /// author their regex/anchor is stale.
+    #[error("'{operation}' in '{path}' did not match anything (module '{module_id}', operation {operation_index})")]
+    #[diagnostic(
+        code(architech::generation::pattern_not_matched),
+        help("Update the manifest's pattern/import to match the current contents of '{path}', or remove this operation if it no longer applies.")
+    )]
+    PatternNotMatched {
+        module_id: String,
+        operation_index: usize,
+        operation: String,
+        path: String,
+        #[source_code]
+        manifest: String,
+        #[label("this value was not found in the target file")]
+        span: SourceSpan,
+    },
+
+    /// Raised when a `cancel-generation` event arrives mid-command --
+    /// setup stops immediately instead of exhausting its retry/timeout
+    /// loops first.
+    #[error("setup for '{module_id}' was cancelled")]
+    #[diagnostic(code(architech::generation::cancelled))]
+    Cancelled { module_id: String },
+}
+
+impl SetupError {
+    /// Render this error's full diagnostic -- message, code, help text, and
+    /// (for `PatternNotMatched`) the underlined manifest field -- into a
+    /// single string, for the boundary where these functions still return
+    /// `Result<(), String>`.
+    pub fn render(self) -> String {
+        format!("{:?}", miette::Report::new(self))
+    }
+
+    /// Build a `PatternNotMatched` error, locating `field_value` (the
+    /// `pattern` or `import` field this operation failed to match) within
+    /// `op` serialized as pretty JSON, so the diagnostic can underline the
+    /// exact manifest value that's stale.
+    pub fn pattern_not_matched(
+        module_id: &str,
+        operation_index: usize,
+        op: &crate::commands::framework::FileOperation,
+        field_value: &str,
+    ) -> Self {
+        let manifest = serde_json::to_string_pretty(op).unwrap_or_else(|_| field_value.to_string());
+        let span = manifest
+            .find(field_value)
+            .map(|offset| SourceSpan::from((offset, field_value.len())))
+            .unwrap_or_else(|| SourceSpan::from((0, manifest.len().min(1))));
+
+        SetupError::PatternNotMatched {
+            module_id: module_id.to_string(),
+            operation_index,
+            operation: op.operation.clone(),
+            path: op.path.clone(),
+            manifest,
+            span,
+        }
+    }
+}
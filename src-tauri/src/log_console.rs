@@ -0,0 +1,60 @@
+//! In-memory ring buffer of recent `log` records, independent of any
+//! particular project, backing `get_recent_logs` for an app-wide live log
+//! console in the frontend. This is distinct from `state::LogEntry`,
+//! which tracks a single project's generation log.
+
+use std::collections::VecDeque;
+use std::sync::RwLock;
+
+use log::LevelFilter;
+use once_cell::sync::Lazy;
+use serde::Serialize;
+
+/// How many of the most recent log records to retain.
+const MAX_LOG_RECORDS: usize = 1000;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LogRecord {
+    pub timestamp: u64,
+    pub level: String,
+    pub target: String,
+    pub message: String,
+}
+
+static RECENT_LOGS: Lazy<RwLock<VecDeque<LogRecord>>> =
+    Lazy::new(|| RwLock::new(VecDeque::with_capacity(MAX_LOG_RECORDS)));
+
+/// Append a record, dropping the oldest once the buffer is full. Called
+/// from the `format` hook installed on the `tauri_plugin_log` builder in
+/// `main()`, so every record handed to any other target also lands here.
+pub fn push(level: log::Level, target: &str, message: String) {
+    let mut logs = RECENT_LOGS.write().unwrap();
+    if logs.len() >= MAX_LOG_RECORDS {
+        logs.pop_front();
+    }
+    logs.push_back(LogRecord {
+        timestamp: chrono::Utc::now().timestamp_millis() as u64,
+        level: level.to_string(),
+        target: target.to_string(),
+        message,
+    });
+}
+
+/// Snapshot of the ring buffer, oldest first.
+#[tauri::command]
+pub fn get_recent_logs() -> Vec<LogRecord> {
+    RECENT_LOGS.read().unwrap().iter().cloned().collect()
+}
+
+/// Adjust the global log level at runtime, without a rebuild. Affects
+/// every target registered on the `tauri_plugin_log` builder, since they
+/// all sit behind the same `log::max_level()` check.
+#[tauri::command]
+pub fn set_log_level(level: String) -> Result<(), String> {
+    let filter = level
+        .parse::<LevelFilter>()
+        .map_err(|_| format!("Invalid log level: '{}'", level))?;
+    log::set_max_level(filter);
+    log::info!("Log level changed to {}", filter);
+    Ok(())
+}
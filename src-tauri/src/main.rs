@@ -17,11 +17,27 @@ use std::path::PathBuf;
 use tauri_plugin_shell::process::{Command, CommandEvent};
 use tauri_plugin_log::{Target as LogTarget};
 use chrono::{Duration, Utc};
+use tauri_plugin_dialog::{DialogExt, MessageDialogButtons, MessageDialogKind};
 
 mod commands;
 mod state;
 mod generation;
+mod generation_error;
+mod template;
 mod tasks;
+mod scheduler;
+mod cache;
+mod worker;
+mod webhook;
+mod error;
+mod process;
+mod watcher;
+mod progress;
+mod filesystem;
+mod test_report;
+mod telemetry;
+mod events;
+mod log_console;
 
 // Add a one-time debug flag to log detailed event info
 static DETAILED_DEBUG: Once = Once::new();
@@ -29,7 +45,8 @@ static DETAILED_DEBUG: Once = Once::new();
 async fn initialize_app_state() -> Result<Arc<AppState>, String> {
     // Create and initialize app state
     let app_state = Arc::new(AppState::new());
-    app_state.initialize().await?;
+    app_state.initialize().await.map_err(|e| e.to_string())?;
+    crate::webhook::spawn_dispatcher(app_state.clone()).await;
     Ok(app_state)
 }
 
@@ -104,17 +121,124 @@ async fn get_task_diagnostic(app_handle: tauri::AppHandle, project_id: String) -
     Ok(result)
 }
 
+/// Default `tauri_plugin_log` level: verbose in builds compiled with the
+/// `debug` feature, quiet otherwise. Either way it's only a starting
+/// point -- `log_console::set_log_level` can raise or lower it at
+/// runtime without a rebuild.
+#[cfg(feature = "debug")]
+fn default_log_level() -> log::LevelFilter {
+    log::LevelFilter::Debug
+}
+
+#[cfg(not(feature = "debug"))]
+fn default_log_level() -> log::LevelFilter {
+    log::LevelFilter::Info
+}
+
+/// Diagnostic text for an `initialize_app_state` failure, shown to the
+/// user via `report_startup_failure`. There's no `AppState` or project to
+/// report on yet at this point, so this can't reuse `get_task_diagnostic`
+/// directly -- it mirrors that command's plain `push_str(format!(...))`
+/// style instead.
+fn build_startup_diagnostic(error: &str) -> String {
+    let mut result = String::new();
+    result.push_str("Architech failed to start\n");
+    result.push_str(&format!("Time: {}\n", Utc::now().to_rfc3339()));
+    result.push_str(&format!("OS: {}\n", std::env::consts::OS));
+    result.push_str(&format!("Error: {}\n", error));
+    result
+}
+
+/// Show a blocking dialog describing an `initialize_app_state` failure,
+/// looping on "Copy Diagnostics" until the user picks "Retry" or "Quit".
+/// Returns `true` if the caller should retry initialization, `false` if
+/// the user chose to quit.
+fn report_startup_failure(app_handle: &tauri::AppHandle, diagnostic: &str) -> bool {
+    loop {
+        let wants_copy = app_handle
+            .dialog()
+            .message(diagnostic)
+            .title("Startup Failed")
+            .kind(MessageDialogKind::Error)
+            .buttons(MessageDialogButtons::OkCancelCustom(
+                "Copy Diagnostics".to_string(),
+                "Continue".to_string(),
+            ))
+            .blocking_show();
+
+        if wants_copy {
+            match write_startup_diagnostic(app_handle, diagnostic) {
+                Ok(path) => {
+                    app_handle
+                        .dialog()
+                        .message(format!("Diagnostics written to:\n{}", path.display()))
+                        .title("Diagnostics Saved")
+                        .kind(MessageDialogKind::Info)
+                        .blocking_show();
+                }
+                Err(e) => log::error!("Failed to write startup diagnostics: {}", e),
+            }
+            continue;
+        }
+
+        return app_handle
+            .dialog()
+            .message("Retry starting Architech?")
+            .title("Startup Failed")
+            .kind(MessageDialogKind::Error)
+            .buttons(MessageDialogButtons::OkCancelCustom(
+                "Retry".to_string(),
+                "Quit".to_string(),
+            ))
+            .blocking_show();
+    }
+}
+
+/// Persist startup diagnostics to disk (no clipboard plugin is registered
+/// in this app) so the user can attach the file to a bug report.
+fn write_startup_diagnostic(app_handle: &tauri::AppHandle, diagnostic: &str) -> Result<PathBuf, String> {
+    let dir = app_handle
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to resolve app data directory: {}", e))?;
+    std::fs::create_dir_all(&dir)
+        .map_err(|e| format!("Failed to create '{}': {}", dir.display(), e))?;
+    let path = dir.join("startup-diagnostic.txt");
+    std::fs::write(&path, diagnostic)
+        .map_err(|e| format!("Failed to write '{}': {}", path.display(), e))?;
+    Ok(path)
+}
+
 // Add this helper function before main
 fn log_event_emission(event_name: &str, data: &impl std::fmt::Debug) {
     log::info!("🔔 EMITTING EVENT: {} with data: {:?}", event_name, data);
 }
 
+/// Emit `event` to the window that owns `project_id` (see
+/// `AppState::register_project_window`), falling back to a global
+/// broadcast if no window has been registered for it yet (e.g. an event
+/// racing the `generate_project` call that would register one).
+async fn emit_routed(handle: &tauri::AppHandle, app_state: &AppState, project_id: &str, event: crate::events::Event) {
+    match app_state.get_project_window(project_id).await {
+        Some(label) => {
+            if let Err(e) = handle.emit_to(&label, &event.name, event.payload) {
+                log::error!("Failed to emit {} to window '{}': {}", event.name, label, e);
+            }
+        }
+        None => {
+            if let Err(e) = handle.emit(&event.name, event.payload) {
+                log::error!("Failed to emit {}: {}", event.name, e);
+            }
+        }
+    }
+}
+
 // Register the event listeners
 fn register_event_listeners(app_handle: &tauri::AppHandle, app_state: Arc<AppState>) {
     // Create a channel for events
     let mut rx = app_state.subscribe();
     let handle = app_handle.clone();
-    
+
     // Spawn a background task to listen for events
     tauri::async_runtime::spawn(async move {
         while let Ok(event) = rx.recv().await {
@@ -125,89 +249,57 @@ fn register_event_listeners(app_handle: &tauri::AppHandle, app_state: Arc<AppSta
                         crate::tasks::TaskState::Pending => "Pending".to_string(),
                         crate::tasks::TaskState::Running => "Running".to_string(),
                         crate::tasks::TaskState::Completed => "Completed".to_string(),
+                        crate::tasks::TaskState::Skipped => "Skipped".to_string(),
                         crate::tasks::TaskState::Failed(msg) => format!("Failed: {}", msg),
                     };
-                    
-                    // Emit the event to the frontend
+
                     log::debug!("Emitting task-state-changed event for task {} with state {}", task_id, state_str);
-                    
-                    if let Err(e) = handle.emit("task-state-changed", serde_json::json!({
-                        "project_id": project_id,
-                        "task_id": task_id,
-                        "state": state_str
-                    })) {
-                        log::error!("Failed to emit task-state-changed event: {}", e);
-                    }
+                    emit_routed(&handle, &app_state, &project_id, crate::events::Event::task_state_changed(&project_id, &task_id, &state_str)).await;
                 },
                 crate::state::ProjectEvent::Started { project_id } => {
-                    if let Err(e) = handle.emit("generation-started", project_id) {
-                        log::error!("Failed to emit generation-started event: {}", e);
-                    }
+                    emit_routed(&handle, &app_state, &project_id, crate::events::Event::started(&project_id)).await;
                 },
-                crate::state::ProjectEvent::Progress { project_id, step, progress } => {
-                    if let Err(e) = handle.emit("generation-progress", serde_json::json!({
-                        "project_id": project_id,
-                        "step": step,
-                        "progress": progress as f32 / 100.0,
-                        "message": format!("{}% - {}", progress, step)
-                    })) {
-                        log::error!("Failed to emit generation-progress event: {}", e);
-                    }
+                crate::state::ProjectEvent::Progress { project_id, step, progress, task_counts } => {
+                    emit_routed(&handle, &app_state, &project_id, crate::events::Event::progress(&project_id, &step, progress, task_counts)).await;
                 },
                 crate::state::ProjectEvent::Completed { project_id, path } => {
-                    if let Err(e) = handle.emit("generation-complete", project_id) {
-                        log::error!("Failed to emit generation-complete event: {}", e);
-                    }
+                    emit_routed(&handle, &app_state, &project_id, crate::events::Event::completed(&project_id, &path)).await;
                 },
                 crate::state::ProjectEvent::Failed { project_id, error, resumable } => {
-                    if let Err(e) = handle.emit("generation-failed", serde_json::json!([project_id, error])) {
-                        log::error!("Failed to emit generation-failed event: {}", e);
-                    }
+                    crate::telemetry::capture_generation_failure(&app_state, &project_id, &error, resumable).await;
+                    emit_routed(&handle, &app_state, &project_id, crate::events::Event::failed(&project_id, &error)).await;
                 },
                 crate::state::ProjectEvent::Cancelled { project_id } => {
-                    if let Err(e) = handle.emit("generation-cancelled", project_id) {
-                        log::error!("Failed to emit generation-cancelled event: {}", e);
-                    }
+                    emit_routed(&handle, &app_state, &project_id, crate::events::Event::cancelled(&project_id)).await;
                 },
-                crate::state::ProjectEvent::LogMessage { project_id, message } => {
-                    if let Err(e) = handle.emit("log-message", serde_json::json!({
-                        "project_id": project_id,
-                        "message": message
-                    })) {
-                        log::error!("Failed to emit log-message event: {}", e);
-                    }
+                crate::state::ProjectEvent::LogMessage { project_id, level, task_id, message } => {
+                    emit_routed(&handle, &app_state, &project_id, crate::events::Event::log_message(&project_id, &message)).await;
+
+                    // Structured counterpart to "log-message" above, carrying
+                    // the level/task id so the frontend can filter a live
+                    // stream instead of only polling `get_project_logs`.
+                    emit_routed(&handle, &app_state, &project_id, crate::events::Event::project_log(&project_id, level, task_id.as_deref(), &message)).await;
                 },
                 crate::state::ProjectEvent::TaskInitializationStarted { project_id } => {
-                    if let Err(e) = handle.emit("task-initialization-started", serde_json::json!({
-                        "project_id": project_id
-                    })) {
-                        log::error!("Failed to emit task-initialization-started event: {}", e);
-                    }
+                    emit_routed(&handle, &app_state, &project_id, crate::events::Event::task_initialization_started(&project_id)).await;
                 },
                 crate::state::ProjectEvent::TaskInitializationProgress { project_id, message } => {
-                    if let Err(e) = handle.emit("task-initialization-progress", serde_json::json!({
-                        "project_id": project_id,
-                        "message": message
-                    })) {
-                        log::error!("Failed to emit task-initialization-progress event: {}", e);
-                    }
+                    emit_routed(&handle, &app_state, &project_id, crate::events::Event::task_initialization_progress(&project_id, &message)).await;
                 },
                 crate::state::ProjectEvent::TaskInitializationCompleted { project_id, task_count, task_names } => {
-                    if let Err(e) = handle.emit("task-initialization-completed", serde_json::json!({
-                        "project_id": project_id,
-                        "task_count": task_count,
-                        "task_names": task_names
-                    })) {
-                        log::error!("Failed to emit task-initialization-completed event: {}", e);
-                    }
+                    emit_routed(&handle, &app_state, &project_id, crate::events::Event::task_initialization_completed(&project_id, task_count, &task_names)).await;
                 },
                 crate::state::ProjectEvent::TaskInitializationFailed { project_id, reason } => {
-                    if let Err(e) = handle.emit("task-initialization-failed", serde_json::json!({
-                        "project_id": project_id,
-                        "reason": reason
-                    })) {
-                        log::error!("Failed to emit task-initialization-failed event: {}", e);
-                    }
+                    emit_routed(&handle, &app_state, &project_id, crate::events::Event::task_initialization_failed(&project_id, &reason)).await;
+                },
+                crate::state::ProjectEvent::TaskRetrying { project_id, task_id, attempt, next_delay_ms } => {
+                    emit_routed(&handle, &app_state, &project_id, crate::events::Event::task_retrying(&project_id, &task_id, attempt, next_delay_ms)).await;
+                },
+                crate::state::ProjectEvent::TaskReady { project_id, task_id } => {
+                    emit_routed(&handle, &app_state, &project_id, crate::events::Event::task_ready(&project_id, &task_id)).await;
+                },
+                crate::state::ProjectEvent::WorkerStateChanged { project_id, status } => {
+                    emit_routed(&handle, &app_state, &project_id, crate::events::Event::worker_state_changed(&project_id, &status)).await;
                 },
             }
         }
@@ -220,35 +312,70 @@ fn main() {
     // env_logger::init();
     
     log::info!("Starting Tauri application");
-    
-    // Initialize the app state in the main thread
-    log::debug!("Initializing app state");
-    let app_state = match tauri::async_runtime::block_on(initialize_app_state()) {
-        Ok(state) => {
-            log::info!("App state initialized successfully");
-            state
-        },
-        Err(e) => {
-            eprintln!("Failed to initialize app state: {}", e);
-            return;
-        }
-    };
-    
+
+    // Refresh the framework/module JSON schema on disk so an editor open on
+    // a template file always points at one matching the structs this build
+    // actually deserializes with (see commands::template_schema for why
+    // this happens here rather than in build.rs).
+    commands::template_schema::write_schema_files(&PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("schemas"));
+
     tauri::Builder::default()
         .plugin(
             LogBuilder::new()
-                .level(log::LevelFilter::Debug)
+                .level(default_log_level())
+                .targets([
+                    LogTarget::new(tauri_plugin_log::TargetKind::Stdout),
+                    LogTarget::new(tauri_plugin_log::TargetKind::Webview),
+                    LogTarget::new(tauri_plugin_log::TargetKind::LogDir { file_name: None }),
+                ])
+                // `tauri_plugin_log` doesn't have a ring-buffer target of
+                // its own; piggyback on the formatter, which every target
+                // runs through, to also feed `log_console`'s buffer.
+                .format(|out, message, record| {
+                    log_console::push(record.level(), record.target(), message.to_string());
+                    out.finish(format_args!("{} [{}] {}", record.level(), record.target(), message))
+                })
                 .build()
         )
-        .manage(app_state.clone())
+        .plugin(tauri_plugin_dialog::init())
         .setup(move |app| {
-            // Set up event listeners for debugging
             let app_handle = app.handle().clone();
-            let state_clone = app_state.clone();
-            
+
+            // Initialize the app state now that the app (and its main
+            // window) exist, instead of before `Builder::default()`, so a
+            // failure here can be reported through a dialog rather than
+            // exiting the process with no window ever shown. Retry in a
+            // loop until the user either succeeds or chooses to quit.
+            log::debug!("Initializing app state");
+            let app_state = loop {
+                match tauri::async_runtime::block_on(initialize_app_state()) {
+                    Ok(state) => {
+                        log::info!("App state initialized successfully");
+                        break state;
+                    },
+                    Err(e) => {
+                        log::error!("Failed to initialize app state: {}", e);
+                        let diagnostic = build_startup_diagnostic(&e);
+                        if !report_startup_failure(&app_handle, &diagnostic) {
+                            app_handle.exit(1);
+                            return Ok(());
+                        }
+                        // User chose to retry; loop back and try again.
+                    }
+                }
+            };
+
+            app.manage(app_state.clone());
+
             // Register event listeners
-            register_event_listeners(&app_handle, state_clone.clone());
-            
+            register_event_listeners(&app_handle, app_state.clone());
+
+            // Opt-in crash/failure telemetry (see `telemetry`). Managed so
+            // the returned guard -- which flushes pending events on drop --
+            // stays alive for the app's lifetime instead of being dropped
+            // at the end of this closure.
+            app.manage(crate::telemetry::init(&app_handle));
+
             // Add window event handlers for resource cleanup
             let app_handle = app.handle();
             
@@ -281,7 +408,12 @@ fn main() {
             get_frameworks,
             get_templates,
             get_modules,
-            
+            framework_capabilities,
+            commands::template_registry::refresh_registry,
+            commands::template_schema::validate_templates,
+            commands::module_add::add_module_to_project,
+            commands::module_apply::apply_modules,
+
             // Project commands
             validate_project_config,
             generate_project,
@@ -290,8 +422,10 @@ fn main() {
             get_project_logs,
             cancel_project_generation,
             resume_project_generation,
+            export_project_log,
             check_directory_exists,
-            
+            commands::project_info::get_project_info,
+
             // System commands
             browse_directory,
             open_in_editor,
@@ -300,11 +434,35 @@ fn main() {
             // Node.js commands
             run_node_command,
             run_node_command_streaming,
+            run_supervised_node_command,
             cleanup_command_resources,
             test_node_sidecar,
             get_task_diagnostic,
+
+            // Plugin commands
+            commands::plugin::list_plugins,
+
+            // Command scope commands
+            commands::command_scope::get_command_scope,
+            commands::command_scope::register_scope_rule,
+
+            // Background worker commands
+            commands::worker::list_workers,
+            commands::worker::control_worker,
+
+            // Webhook commands
+            commands::webhook::list_webhooks,
+            commands::webhook::add_webhook,
+            commands::webhook::remove_webhook,
+
+            // Telemetry commands
+            telemetry::get_telemetry_enabled,
+            telemetry::set_telemetry_enabled,
+
+            // Log console commands
+            log_console::get_recent_logs,
+            log_console::set_log_level,
         ])
-        .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_opener::init())
         .run(tauri::generate_context!())
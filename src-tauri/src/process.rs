@@ -0,0 +1,187 @@
+//! PTY-backed process execution for framework scaffolders.
+//!
+//! `create-next-app` and several other scaffolders check `process.stdout.isTTY`
+//! before deciding how to render their output, so running them through a
+//! plain piped child (like `command_runner::CommandBuilder` does) makes them
+//! silently fall back to a different, non-interactive code path. `ProcessRunner`
+//! instead spawns the command with its stdin/stdout attached to a
+//! pseudo-terminal, streaming output line-by-line as `log-message` emits
+//! instead of buffering to completion. Stderr stays a separate plain pipe, so
+//! callers can tell real errors apart from ordinary progress output -- the
+//! same split remote-execution crates expose on a PTY channel (a data stream
+//! and an independent extended-data/stderr stream) alongside a kill signal.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use log::warn;
+use pty_process::{Command as PtyCommand, Pty, Size};
+use tauri::{AppHandle, Emitter};
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::process::Child;
+use tokio::sync::Notify;
+
+/// Terminal size to allocate for the child's pseudo-terminal.
+#[derive(Debug, Clone, Copy)]
+pub struct PtySize {
+    pub rows: u16,
+    pub cols: u16,
+}
+
+impl Default for PtySize {
+    fn default() -> Self {
+        Self { rows: 24, cols: 80 }
+    }
+}
+
+/// The outcome of a finished (or cancelled) process.
+#[derive(Debug, Clone)]
+pub struct ProcessExit {
+    pub success: bool,
+    pub code: Option<i32>,
+}
+
+/// Builder for a command to run under a pseudo-terminal. Mirrors
+/// `CommandBuilder`'s shape (command, args, working dir, env) but spawns
+/// under a PTY and streams output instead of buffering it to a `CommandResult`.
+pub struct ProcessRunner {
+    command: String,
+    args: Vec<String>,
+    working_dir: PathBuf,
+    env: HashMap<String, String>,
+    pty_size: PtySize,
+}
+
+impl ProcessRunner {
+    pub fn new<S: Into<String>>(command: S) -> Self {
+        Self {
+            command: command.into(),
+            args: Vec::new(),
+            working_dir: PathBuf::from("."),
+            env: HashMap::new(),
+            pty_size: PtySize::default(),
+        }
+    }
+
+    pub fn args<I, S>(mut self, args: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.args.extend(args.into_iter().map(Into::into));
+        self
+    }
+
+    pub fn working_dir<P: Into<PathBuf>>(mut self, dir: P) -> Self {
+        self.working_dir = dir.into();
+        self
+    }
+
+    pub fn env<K: Into<String>, V: Into<String>>(mut self, key: K, value: V) -> Self {
+        self.env.insert(key.into(), value.into());
+        self
+    }
+
+    pub fn pty_size(mut self, size: PtySize) -> Self {
+        self.pty_size = size;
+        self
+    }
+
+    /// Spawn the command under a PTY and start streaming its output to
+    /// `log-message` events in the background. Returns a handle the caller
+    /// can cancel and await the exit status of.
+    pub async fn spawn(self, app_handle: AppHandle) -> Result<ProcessHandle, String> {
+        let pty = Pty::new().map_err(|e| format!("Failed to allocate pseudo-terminal: {}", e))?;
+        pty.resize(Size::new(self.pty_size.rows, self.pty_size.cols))
+            .map_err(|e| format!("Failed to size pseudo-terminal: {}", e))?;
+        let pts = pty.pts().map_err(|e| format!("Failed to open pseudo-terminal slave: {}", e))?;
+
+        let mut cmd = PtyCommand::new(&self.command);
+        cmd.args(&self.args)
+            .current_dir(&self.working_dir)
+            .envs(&self.env)
+            .stderr(std::process::Stdio::piped());
+
+        let mut child = cmd
+            .spawn(&pts)
+            .map_err(|e| format!("Failed to spawn '{}': {}", self.command, e))?;
+
+        let stderr = child.stderr.take();
+        let (mut pty_reader, _pty_writer) = tokio::io::split(pty);
+
+        {
+            let app_handle = app_handle.clone();
+            tokio::spawn(async move {
+                stream_lines(&mut pty_reader, |line| emit_log_line(&app_handle, line)).await;
+            });
+        }
+
+        if let Some(stderr) = stderr {
+            let app_handle = app_handle.clone();
+            tokio::spawn(async move {
+                let mut stderr = stderr;
+                stream_lines(&mut stderr, |line| emit_log_line(&app_handle, line)).await;
+            });
+        }
+
+        Ok(ProcessHandle {
+            child,
+            cancel: Arc::new(Notify::new()),
+        })
+    }
+}
+
+async fn stream_lines<R: tokio::io::AsyncRead + Unpin>(reader: &mut R, mut on_line: impl FnMut(&str)) {
+    let mut reader = BufReader::new(reader);
+    let mut line = String::new();
+    loop {
+        line.clear();
+        match reader.read_line(&mut line).await {
+            Ok(0) | Err(_) => break,
+            Ok(_) => on_line(line.trim_end_matches(['\r', '\n'])),
+        }
+    }
+}
+
+fn emit_log_line(app_handle: &AppHandle, line: &str) {
+    if line.is_empty() {
+        return;
+    }
+    if let Err(e) = app_handle.emit("log-message", line) {
+        warn!("Failed to emit log-message event: {}", e);
+    }
+}
+
+/// A running (or just-finished) scaffolder process spawned by `ProcessRunner`.
+pub struct ProcessHandle {
+    child: Child,
+    cancel: Arc<Notify>,
+}
+
+impl ProcessHandle {
+    /// Kill the process instead of waiting for it to exit on its own --
+    /// for a scaffolder that's hung waiting on input it'll never receive.
+    pub fn cancel(&self) {
+        self.cancel.notify_one();
+    }
+
+    /// Wait for the process to exit, or for `cancel` to fire and kill it.
+    pub async fn wait(mut self) -> ProcessExit {
+        tokio::select! {
+            _ = self.cancel.notified() => {
+                if let Err(e) = self.child.kill().await {
+                    warn!("Failed to kill cancelled process: {}", e);
+                }
+                ProcessExit { success: false, code: None }
+            }
+            status = self.child.wait() => match status {
+                Ok(status) => ProcessExit { success: status.success(), code: status.code() },
+                Err(e) => {
+                    warn!("Failed to wait on child process: {}", e);
+                    ProcessExit { success: false, code: None }
+                }
+            }
+        }
+    }
+}
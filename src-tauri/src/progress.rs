@@ -0,0 +1,188 @@
+//! Structured per-task progress reporting.
+//!
+//! Tasks currently narrate what they're doing with free-form
+//! `app_handle.emit("log-message", ...)` strings, which gives the frontend
+//! nothing to render but a scrolling log. `ProgressReporter` emits
+//! structured `task-progress` events instead -- a phase (spinner vs. a
+//! fraction-based bar) plus elapsed time -- so the UI can show a real
+//! progress indicator per task.
+//!
+//! `LineReporter` is the same idea for a command's captured output lines,
+//! for call sites with no `AppHandle` to emit structured events through --
+//! it picks a spinner, plain CI-friendly printing, or a GUI event sink.
+
+use std::sync::Mutex;
+use std::time::Instant;
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+
+/// A task's current phase, as presented to the frontend.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case", tag = "phase")]
+pub enum ProgressPhase {
+    /// A step has started with no known fraction yet -- render as a
+    /// spinner.
+    Running { step: String },
+    /// `current` of `total` units of the current step are done.
+    Progress { step: String, current: u64, total: u64, fraction: f64 },
+    /// The task finished, successfully or not.
+    Finished { success: bool, message: String },
+}
+
+/// One `task-progress` event, emitted on the channel of the same name.
+#[derive(Debug, Clone, Serialize)]
+pub struct TaskProgressEvent {
+    pub task_id: String,
+    pub phase: ProgressPhase,
+    pub elapsed_ms: u128,
+}
+
+/// Emits `task-progress` events for a single task. Construct one via
+/// `TaskContext::progress_reporter` at the start of `Task::execute`.
+pub struct ProgressReporter {
+    task_id: String,
+    app_handle: AppHandle,
+    started_at: Instant,
+    current_step: Mutex<String>,
+}
+
+impl ProgressReporter {
+    pub fn new(task_id: impl Into<String>, app_handle: AppHandle) -> Self {
+        Self {
+            task_id: task_id.into(),
+            app_handle,
+            started_at: Instant::now(),
+            current_step: Mutex::new(String::new()),
+        }
+    }
+
+    fn emit(&self, phase: ProgressPhase) {
+        let event = TaskProgressEvent {
+            task_id: self.task_id.clone(),
+            phase,
+            elapsed_ms: self.started_at.elapsed().as_millis(),
+        };
+        if let Err(e) = self.app_handle.emit("task-progress", event) {
+            log::warn!("Failed to emit task-progress event: {}", e);
+        }
+    }
+
+    /// Begin a named step with no known fraction yet (a spinner, not a bar).
+    pub fn start(&self, step_name: impl Into<String>) {
+        let step_name = step_name.into();
+        *self.current_step.lock().unwrap() = step_name.clone();
+        self.emit(ProgressPhase::Running { step: step_name });
+    }
+
+    /// Report `current` of `total` units done for the step started by the
+    /// last call to `start`.
+    pub fn tick(&self, current: u64, total: u64) {
+        let step = self.current_step.lock().unwrap().clone();
+        let fraction = if total == 0 { 0.0 } else { current as f64 / total as f64 };
+        self.emit(ProgressPhase::Progress { step, current, total, fraction });
+    }
+
+    /// Finalize the task's progress as succeeded or failed.
+    pub fn finish(&self, success: bool, message: impl Into<String>) {
+        self.emit(ProgressPhase::Finished { success, message: message.into() });
+    }
+}
+
+/// Wrap a long-running async operation in `start`/`finish` calls on
+/// `reporter` -- analogous to how CLIs wrap async routines with a spinner.
+/// The future's `Err` becomes the finish message on failure; its `Ok`
+/// passes through on success.
+pub async fn with_progress_async<F, T>(
+    reporter: &ProgressReporter,
+    message: impl Into<String>,
+    future: F,
+) -> Result<T, String>
+where
+    F: std::future::Future<Output = Result<T, String>>,
+{
+    let message = message.into();
+    reporter.start(message.clone());
+    match future.await {
+        Ok(value) => {
+            reporter.finish(true, message);
+            Ok(value)
+        }
+        Err(e) => {
+            reporter.finish(false, e.clone());
+            Err(e)
+        }
+    }
+}
+
+/// Where a command's captured stdout/stderr lines go -- the GUI-agnostic
+/// counterpart to `ProgressReporter` above, for the handful of call sites
+/// (e.g. `run_interactive_command`) that don't have a `Task`/`AppHandle` to
+/// emit structured events through and used to just `println!` every line.
+pub trait LineReporter: Send + Sync {
+    fn report_line(&self, source: crate::commands::command_runner::StreamSource, line: &str);
+}
+
+/// Prints each line as-is. What a CI log collector expects: it displays
+/// stdout verbatim and doesn't understand carriage-return overwrites.
+pub struct PlainLineReporter;
+
+impl LineReporter for PlainLineReporter {
+    fn report_line(&self, _source: crate::commands::command_runner::StreamSource, line: &str) {
+        println!("{}", line);
+    }
+}
+
+/// Overwrites the current terminal line with the latest output instead of
+/// scrolling a new one per line -- a spinner that shows real progress text
+/// instead of a fixed message.
+pub struct SpinnerLineReporter;
+
+impl LineReporter for SpinnerLineReporter {
+    fn report_line(&self, _source: crate::commands::command_runner::StreamSource, line: &str) {
+        use std::io::Write;
+        print!("\r\x1b[K{}", line);
+        let _ = std::io::stdout().flush();
+    }
+}
+
+/// Forwards each line as a `command-output` event instead of printing it --
+/// what the Tauri frontend listens for.
+pub struct EventSinkLineReporter {
+    app_handle: AppHandle,
+    step: String,
+}
+
+impl EventSinkLineReporter {
+    pub fn new(app_handle: AppHandle, step: impl Into<String>) -> Self {
+        Self { app_handle, step: step.into() }
+    }
+}
+
+impl LineReporter for EventSinkLineReporter {
+    fn report_line(&self, source: crate::commands::command_runner::StreamSource, line: &str) {
+        let payload = serde_json::json!({
+            "step": self.step,
+            "stream": source,
+            "line": line,
+        });
+        if let Err(e) = self.app_handle.emit("command-output", payload) {
+            log::warn!("Failed to emit command-output event: {}", e);
+        }
+    }
+}
+
+/// Pick the right `LineReporter` for a run: a structured `EventSinkLineReporter`
+/// whenever a Tauri `AppHandle` is available (the normal GUI path), otherwise
+/// a `PlainLineReporter` if `CI` is set, or a `SpinnerLineReporter` for an
+/// interactive terminal.
+pub fn default_line_reporter(gui_target: Option<(AppHandle, String)>) -> Box<dyn LineReporter> {
+    if let Some((app_handle, step)) = gui_target {
+        return Box::new(EventSinkLineReporter::new(app_handle, step));
+    }
+    if std::env::var_os("CI").is_some() {
+        Box::new(PlainLineReporter)
+    } else {
+        Box::new(SpinnerLineReporter)
+    }
+}
@@ -0,0 +1,203 @@
+//! Dependency scheduler for project generation tasks.
+//!
+//! `GenerationTask` carries a `dependencies` list that `AppState` used to
+//! just store without consulting. This resolves that list into a DAG: a
+//! topological order for diagnostics/cycle detection, and a "ready" frontier
+//! -- tasks whose dependencies are all satisfied -- that `AppState` recomputes
+//! each time a task completes or is skipped so independent tasks can be dispatched
+//! concurrently instead of strictly in registration order.
+
+use std::collections::{HashMap, HashSet};
+
+use thiserror::Error;
+
+use crate::state::GenerationTask;
+use crate::tasks::TaskState;
+
+#[derive(Debug, Error)]
+pub enum SchedulerError {
+    #[error("dependency cycle detected among tasks: {0:?}")]
+    CycleDetected(Vec<String>),
+}
+
+/// Compute a topological execution order over `tasks`, or name every task
+/// still stuck once ordinary resolution stalls (a dependency cycle).
+pub fn topological_order(tasks: &HashMap<String, GenerationTask>) -> Result<Vec<String>, SchedulerError> {
+    let mut in_degree: HashMap<&str, usize> = tasks.keys().map(|id| (id.as_str(), 0)).collect();
+    let mut dependents: HashMap<&str, Vec<&str>> = HashMap::new();
+
+    for task in tasks.values() {
+        for dep in &task.dependencies {
+            if let Some(degree) = in_degree.get_mut(task.id.as_str()) {
+                *degree += 1;
+            }
+            dependents.entry(dep.as_str()).or_default().push(task.id.as_str());
+        }
+    }
+
+    let mut frontier: Vec<&str> = in_degree
+        .iter()
+        .filter(|(_, &degree)| degree == 0)
+        .map(|(id, _)| *id)
+        .collect();
+    // Kept sorted descending so `pop()` always takes the lexicographically
+    // smallest ready task, making the order deterministic.
+    frontier.sort_by(|a, b| b.cmp(a));
+
+    let mut remaining = in_degree;
+    let mut order = Vec::with_capacity(tasks.len());
+
+    while let Some(id) = frontier.pop() {
+        order.push(id.to_string());
+        if let Some(deps) = dependents.get(id) {
+            let mut unlocked: Vec<&str> = Vec::new();
+            for &dependent in deps {
+                if let Some(degree) = remaining.get_mut(dependent) {
+                    *degree -= 1;
+                    if *degree == 0 {
+                        unlocked.push(dependent);
+                    }
+                }
+            }
+            frontier.extend(unlocked);
+            frontier.sort_by(|a, b| b.cmp(a));
+        }
+    }
+
+    if order.len() != tasks.len() {
+        let resolved: HashSet<&str> = order.iter().map(String::as_str).collect();
+        let stuck = tasks
+            .keys()
+            .filter(|id| !resolved.contains(id.as_str()))
+            .cloned()
+            .collect();
+        return Err(SchedulerError::CycleDetected(stuck));
+    }
+
+    Ok(order)
+}
+
+/// Tasks eligible to run right now: not already completed, skipped,
+/// running, or failed, and every dependency is satisfied -- either already
+/// `TaskState::Completed`/`Skipped`, or present in `completed_tasks` (the
+/// resume-from-checkpoint case, where state hasn't been replayed into
+/// `task_states` yet).
+pub fn ready_tasks(
+    tasks: &HashMap<String, GenerationTask>,
+    task_states: &HashMap<String, TaskState>,
+    completed_tasks: &[String],
+) -> Vec<String> {
+    let completed_on_disk: HashSet<&str> = completed_tasks.iter().map(String::as_str).collect();
+
+    let dependency_satisfied = |dep: &str| -> bool {
+        completed_on_disk.contains(dep)
+            || matches!(task_states.get(dep), Some(TaskState::Completed) | Some(TaskState::Skipped))
+    };
+
+    tasks
+        .values()
+        .filter(|task| {
+            let already_handled = matches!(
+                task_states.get(&task.id),
+                Some(TaskState::Completed) | Some(TaskState::Skipped) | Some(TaskState::Running) | Some(TaskState::Failed(_))
+            );
+            !already_handled && task.dependencies.iter().all(|dep| dependency_satisfied(dep))
+        })
+        .map(|task| task.id.clone())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::TaskStatus;
+
+    fn task(id: &str, dependencies: &[&str]) -> GenerationTask {
+        GenerationTask {
+            id: id.to_string(),
+            name: id.to_string(),
+            description: String::new(),
+            status: TaskStatus::Pending,
+            progress: 0.0,
+            dependencies: dependencies.iter().map(|d| d.to_string()).collect(),
+            attempt: 1,
+            max_attempts: crate::tasks::DEFAULT_MAX_TASK_ATTEMPTS,
+        }
+    }
+
+    fn tasks(entries: &[GenerationTask]) -> HashMap<String, GenerationTask> {
+        entries.iter().map(|t| (t.id.clone(), t.clone())).collect()
+    }
+
+    #[test]
+    fn orders_dependencies_before_dependents() {
+        let tasks = tasks(&[task("a", &[]), task("b", &["a"]), task("c", &["b"])]);
+
+        let order = topological_order(&tasks).unwrap();
+
+        assert_eq!(order, vec!["a".to_string(), "b".to_string(), "c".to_string()]);
+    }
+
+    #[test]
+    fn breaks_ties_lexicographically_for_a_deterministic_order() {
+        let tasks = tasks(&[task("z", &[]), task("a", &[]), task("m", &[])]);
+
+        let order = topological_order(&tasks).unwrap();
+
+        assert_eq!(order, vec!["a".to_string(), "m".to_string(), "z".to_string()]);
+    }
+
+    #[test]
+    fn detects_a_direct_cycle() {
+        let tasks = tasks(&[task("a", &["b"]), task("b", &["a"])]);
+
+        let SchedulerError::CycleDetected(mut stuck) = topological_order(&tasks).unwrap_err();
+        stuck.sort();
+        assert_eq!(stuck, vec!["a".to_string(), "b".to_string()]);
+    }
+
+    #[test]
+    fn detects_a_cycle_that_does_not_include_every_task() {
+        // "standalone" has no dependencies and isn't part of the cycle --
+        // only "a"/"b" should be reported stuck.
+        let tasks = tasks(&[task("standalone", &[]), task("a", &["b"]), task("b", &["a"])]);
+
+        let SchedulerError::CycleDetected(mut stuck) = topological_order(&tasks).unwrap_err();
+        stuck.sort();
+        assert_eq!(stuck, vec!["a".to_string(), "b".to_string()]);
+    }
+
+    #[test]
+    fn ready_tasks_includes_only_tasks_with_satisfied_dependencies() {
+        let tasks = tasks(&[task("a", &[]), task("b", &["a"]), task("c", &["b"])]);
+        let mut task_states = HashMap::new();
+        task_states.insert("a".to_string(), TaskState::Completed);
+
+        let ready = ready_tasks(&tasks, &task_states, &[]);
+
+        assert_eq!(ready, vec!["b".to_string()]);
+    }
+
+    #[test]
+    fn ready_tasks_treats_a_checkpointed_completion_as_satisfied() {
+        // "a" isn't in task_states at all yet (not replayed since resume),
+        // but it is in completed_tasks from the checkpoint.
+        let tasks = tasks(&[task("a", &[]), task("b", &["a"])]);
+
+        let ready = ready_tasks(&tasks, &HashMap::new(), &["a".to_string()]);
+
+        assert_eq!(ready, vec!["b".to_string()]);
+    }
+
+    #[test]
+    fn ready_tasks_excludes_tasks_already_running_or_failed() {
+        let tasks = tasks(&[task("a", &[]), task("b", &[])]);
+        let mut task_states = HashMap::new();
+        task_states.insert("a".to_string(), TaskState::Running);
+        task_states.insert("b".to_string(), TaskState::Failed("boom".to_string()));
+
+        let ready = ready_tasks(&tasks, &task_states, &[]);
+
+        assert!(ready.is_empty());
+    }
+}
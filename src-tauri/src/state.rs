@@ -4,6 +4,8 @@ use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::fs;
+use std::fs::OpenOptions;
+use std::io::Write;
 
 use serde::{Deserialize, Serialize};
 use tauri::AppHandle;
@@ -12,6 +14,7 @@ use tokio::sync::broadcast;
 use log::{info, warn, error, debug};
 
 use crate::tasks::{TaskState, TaskResult};
+use crate::error::GenerationError;
 
 // Define TaskStatus enum for backward compatibility
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -26,11 +29,25 @@ pub enum TaskStatus {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GenerationTask {
     pub id: String,
-    pub name: String, 
+    pub name: String,
     pub description: String,
     pub status: TaskStatus,
     pub progress: f32,
     pub dependencies: Vec<String>,
+    /// The attempt currently in progress (or last attempted), 1-indexed.
+    #[serde(default = "default_task_attempt")]
+    pub attempt: u32,
+    /// Attempts allowed before a retryable failure becomes permanent.
+    #[serde(default = "default_task_max_attempts")]
+    pub max_attempts: u32,
+}
+
+fn default_task_attempt() -> u32 {
+    1
+}
+
+fn default_task_max_attempts() -> u32 {
+    crate::tasks::DEFAULT_MAX_TASK_ATTEMPTS
 }
 
 /// Project generation status enum
@@ -79,6 +96,10 @@ pub enum ProjectEvent {
         step: String,
         /// Progress percentage (0-100)
         progress: u8,
+        /// Completed/total task counts, when the caller is a task-based
+        /// pipeline run (`TaskExecutor::execute_all`) rather than the legacy
+        /// `ProjectGenerator`, which has no discrete task count to report.
+        task_counts: Option<(u64, u64)>,
     },
     /// Project generation completed
     Completed {
@@ -90,9 +111,9 @@ pub enum ProjectEvent {
     /// Project generation failed
     Failed {
         /// Project ID
-        project_id: String, 
-        /// Error message
-        error: String,
+        project_id: String,
+        /// Structured error describing what went wrong
+        error: GenerationError,
         /// Whether the generation can be resumed
         resumable: bool,
     },
@@ -114,6 +135,10 @@ pub enum ProjectEvent {
     LogMessage {
         /// Project ID
         project_id: String,
+        /// Severity of this entry
+        level: LogLevel,
+        /// Task that produced this entry, if any
+        task_id: Option<String>,
         /// Log message
         message: String,
     },
@@ -145,43 +170,139 @@ pub enum ProjectEvent {
         /// Error reason
         reason: String,
     },
+    /// A task failed with a retryable error and will be re-run after a
+    /// backoff delay instead of being marked `Failed`.
+    TaskRetrying {
+        /// Project ID
+        project_id: String,
+        /// Task ID
+        task_id: String,
+        /// The attempt about to be retried (1-indexed)
+        attempt: u32,
+        /// How long to wait before the retry, in milliseconds
+        next_delay_ms: u64,
+    },
+    /// A task's dependencies are now all satisfied, so it has joined the
+    /// ready frontier (see `scheduler::ready_tasks`).
+    TaskReady {
+        /// Project ID
+        project_id: String,
+        /// Task ID
+        task_id: String,
+    },
+    /// A project's background worker changed status (see `worker::WorkerHandle`).
+    WorkerStateChanged {
+        /// Project ID
+        project_id: String,
+        /// The worker's new status
+        status: crate::worker::WorkerStatus,
+    },
 }
 
+/// Severity of a project log entry, ordered most to least severe (matching
+/// `log::Level`'s ordering) so a "level" filter can mean "at least this
+/// severe" via a plain `<=` comparison rather than an exact match.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogLevel {
+    Error,
+    Warn,
+    Info,
+    Debug,
+}
+
+impl LogLevel {
+    /// Parse a level name from the frontend's filter parameter, case
+    /// insensitively, accepting "warning" as a synonym for "warn".
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "error" => Some(LogLevel::Error),
+            "warn" | "warning" => Some(LogLevel::Warn),
+            "info" => Some(LogLevel::Info),
+            "debug" => Some(LogLevel::Debug),
+            _ => None,
+        }
+    }
+}
+
+/// Per-project log ring buffer capacity. Oldest entries are dropped once a
+/// project's log exceeds this, so a long-running or resumed generation
+/// can't grow its in-memory (and checkpointed) log without bound.
+const MAX_LOG_ENTRIES_PER_PROJECT: usize = 2000;
+
 /// Project log entry
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LogEntry {
     /// Timestamp of the log entry
     pub timestamp: u64,
+    /// Severity of this entry. Defaulted for checkpoints written before
+    /// this field existed.
+    #[serde(default = "default_log_level")]
+    pub level: LogLevel,
+    /// Task that produced this entry, if any
+    #[serde(default)]
+    pub task_id: Option<String>,
     /// Message content
     pub message: String,
 }
 
+fn default_log_level() -> LogLevel {
+    LogLevel::Info
+}
+
 impl LogEntry {
     /// Create a new log entry
-    pub fn new(message: String) -> Self {
+    pub fn new(level: LogLevel, task_id: Option<String>, message: String) -> Self {
         Self {
             timestamp: std::time::SystemTime::now()
                 .duration_since(std::time::UNIX_EPOCH)
                 .unwrap()
                 .as_secs(),
+            level,
+            task_id,
             message,
         }
     }
 }
 
+/// On-disk checkpoint format version, written into every checkpoint. Bump
+/// this whenever a field is added, removed, or changes meaning.
+/// `load_checkpoints` does not check it: a checkpoint of any version is
+/// loaded as is.
+const CHECKPOINT_FORMAT_VERSION: u32 = 1;
+
 /// Checkpoint data for resumable generation
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GenerationCheckpoint {
+    /// Checkpoint file format version
+    #[serde(default = "default_checkpoint_version")]
+    pub version: u32,
     /// Project ID
     pub project_id: String,
     /// Configuration
     pub config: crate::commands::project::ProjectConfig,
+    /// Status at the time the checkpoint was taken
+    #[serde(default)]
+    pub status: Option<ProjectStatus>,
     /// Completed tasks
     pub completed_tasks: Vec<String>,
+    /// Log history at the time the checkpoint was taken
+    #[serde(default)]
+    pub logs: Vec<LogEntry>,
+    /// Content-addressed cache key computed for each task that ran, keyed by
+    /// task ID (see `cache::hash_task`) -- a record of what ran with which
+    /// inputs, not an active skip mechanism (see the `cache` module docs).
+    #[serde(default)]
+    pub task_hashes: HashMap<String, String>,
     /// Path to the project
     pub project_path: PathBuf,
 }
 
+fn default_checkpoint_version() -> u32 {
+    // Checkpoints written before the version field existed are treated as v1.
+    1
+}
+
 /// Application state
 pub struct AppState {
     /// Project statuses
@@ -194,12 +315,30 @@ pub struct AppState {
     task_metadata: Mutex<HashMap<String, HashMap<String, GenerationTask>>>,
     /// Completed tasks (for resumable generation)
     completed_tasks: Mutex<HashMap<String, Vec<String>>>,
+    /// Content-addressed cache keys per task, recording what ran with which
+    /// inputs on a project's last run (see `cache::hash_task`)
+    task_hashes: Mutex<HashMap<String, HashMap<String, String>>>,
     /// Checkpoints for resumable generation
     checkpoints: Mutex<HashMap<String, GenerationCheckpoint>>,
     /// Project configs
     project_configs: Mutex<HashMap<String, crate::commands::project::ProjectConfig>>,
     /// Event broadcaster
     event_tx: broadcast::Sender<ProjectEvent>,
+    /// Loaded third-party framework/module plugins
+    pub plugins: crate::commands::plugin::PluginRegistry,
+    /// Background generation workers, one per currently-running project
+    /// (see `worker::WorkerManager`)
+    pub workers: crate::worker::WorkerManager,
+    /// Registered webhook endpoints notified of project events
+    /// (see `webhook::WebhookRegistry`)
+    pub webhooks: crate::webhook::WebhookRegistry,
+    /// Post-scaffold filesystem watchers, one per currently-running project
+    /// (see `watcher::WatcherRegistry`)
+    pub watchers: crate::watcher::WatcherRegistry,
+    /// Window label that started each project's generation, so
+    /// `register_event_listeners` can route that project's events to just
+    /// that window instead of broadcasting to every open window.
+    project_windows: Mutex<HashMap<String, String>>,
 }
 
 impl AppState {
@@ -212,31 +351,50 @@ impl AppState {
             task_states: Mutex::new(HashMap::new()),
             task_metadata: Mutex::new(HashMap::new()),
             completed_tasks: Mutex::new(HashMap::new()),
+            task_hashes: Mutex::new(HashMap::new()),
             checkpoints: Mutex::new(HashMap::new()),
             project_configs: Mutex::new(HashMap::new()),
             event_tx: tx,
+            plugins: crate::commands::plugin::PluginRegistry::new(),
+            workers: crate::worker::WorkerManager::new(),
+            webhooks: crate::webhook::WebhookRegistry::new(),
+            watchers: crate::watcher::WatcherRegistry::new(),
+            project_windows: Mutex::new(HashMap::new()),
         }
     }
-    
+
     /// Initialize the state
-    pub async fn initialize(&self) -> Result<(), String> {
+    pub async fn initialize(&self) -> Result<(), GenerationError> {
         // Create checkpoints directory if it doesn't exist
-        let app_dir = self.get_app_data_dir()?;
+        let app_dir = self.get_app_data_dir().map_err(GenerationError::Io)?;
         let checkpoints_dir = app_dir.join("checkpoints");
-        
+
         if !checkpoints_dir.exists() {
-            fs::create_dir_all(&checkpoints_dir)
-                .map_err(|e| format!("Failed to create checkpoints directory: {}", e))?;
+            fs::create_dir_all(&checkpoints_dir)?;
         }
-        
+
         // Load any existing checkpoints
         self.load_checkpoints().await?;
-        
+
+        // Discover and load third-party generator plugins
+        let plugins_dir = app_dir.join("plugins");
+        match self.plugins.discover(&plugins_dir).await {
+            Ok(signatures) => info!("Loaded {} plugin(s) from {}", signatures.len(), plugins_dir.display()),
+            Err(e) => warn!("Failed to discover plugins: {}", e),
+        }
+
+        // Load registered webhook endpoints; their delivery workers are
+        // started separately, once the caller has an `Arc<AppState>` to
+        // give them (see `webhook::spawn_dispatcher`).
+        if let Err(e) = self.webhooks.load(&app_dir).await {
+            warn!("Failed to load webhook configs: {}", e);
+        }
+
         Ok(())
     }
-    
+
     /// Get the app data directory
-    fn get_app_data_dir(&self) -> Result<PathBuf, String> {
+    pub(crate) fn get_app_data_dir(&self) -> Result<PathBuf, String> {
         let app_data_dir = dirs::data_dir()
             .ok_or_else(|| "Could not find app data directory".to_string())?
             .join("tauri-nextjs-template");
@@ -250,44 +408,58 @@ impl AppState {
     }
     
     /// Load checkpoints from disk
-    async fn load_checkpoints(&self) -> Result<(), String> {
-        let app_dir = self.get_app_data_dir()?;
+    async fn load_checkpoints(&self) -> Result<(), GenerationError> {
+        let app_dir = self.get_app_data_dir().map_err(GenerationError::Io)?;
         let checkpoints_dir = app_dir.join("checkpoints");
-        
+
         if !checkpoints_dir.exists() {
             return Ok(());
         }
-        
-        let entries = fs::read_dir(&checkpoints_dir)
-            .map_err(|e| format!("Failed to read checkpoints directory: {}", e))?;
+
+        let entries = fs::read_dir(&checkpoints_dir)?;
             
         let mut checkpoints = self.checkpoints.lock().await;
         let mut projects = self.projects.lock().await;
         let mut completed = self.completed_tasks.lock().await;
-        
+        let mut configs = self.project_configs.lock().await;
+        let mut logs = self.logs.lock().await;
+        let mut task_hashes = self.task_hashes.lock().await;
+
         for entry in entries {
             if let Ok(entry) = entry {
                 if let Some(file_name) = entry.file_name().to_str() {
+                    // Ignore in-flight temp files from an interrupted atomic write.
                     if file_name.ends_with(".json") {
                         let project_id = file_name.trim_end_matches(".json");
                         let checkpoint_path = checkpoints_dir.join(file_name);
-                        
+
                         match fs::read_to_string(&checkpoint_path) {
                             Ok(content) => {
                                 match serde_json::from_str::<GenerationCheckpoint>(&content) {
                                     Ok(checkpoint) => {
                                         // Add to checkpoints
                                         checkpoints.insert(project_id.to_string(), checkpoint.clone());
-                                        
-                                        // Add to completed tasks
+
+                                        // Rehydrate config, completed tasks and logs
+                                        configs.insert(project_id.to_string(), checkpoint.config.clone());
                                         completed.insert(project_id.to_string(), checkpoint.completed_tasks.clone());
-                                        
-                                        // Add to projects as failed but resumable
-                                        projects.insert(project_id.to_string(), ProjectStatus::Failed {
-                                            error: "Project generation was interrupted".to_string(),
-                                            resumable: true,
-                                        });
-                                        
+                                        logs.insert(project_id.to_string(), checkpoint.logs.clone());
+                                        task_hashes.insert(project_id.to_string(), checkpoint.task_hashes.clone());
+
+                                        // Rehydrate status: an in-progress checkpoint means the
+                                        // process died mid-generation, so report it as a
+                                        // resumable failure regardless of what was last recorded.
+                                        let rehydrated_status = match checkpoint.status {
+                                            Some(ProjectStatus::Completed { .. }) | Some(ProjectStatus::Cancelled) => {
+                                                checkpoint.status.clone().unwrap()
+                                            },
+                                            _ => ProjectStatus::Failed {
+                                                error: "Project generation was interrupted".to_string(),
+                                                resumable: true,
+                                            },
+                                        };
+                                        projects.insert(project_id.to_string(), rehydrated_status);
+
                                         info!("Loaded checkpoint for project: {}", project_id);
                                     },
                                     Err(e) => {
@@ -303,24 +475,63 @@ impl AppState {
                 }
             }
         }
-        
+
         Ok(())
     }
     
     /// Save a checkpoint to disk
-    async fn save_checkpoint(&self, checkpoint: &GenerationCheckpoint) -> Result<(), String> {
-        let app_dir = self.get_app_data_dir()?;
+    ///
+    /// Writes to a temp file and renames it into place so a crash or power
+    /// loss mid-write can never leave a truncated/corrupt checkpoint behind.
+    async fn save_checkpoint(&self, checkpoint: &GenerationCheckpoint) -> Result<(), GenerationError> {
+        let app_dir = self.get_app_data_dir().map_err(GenerationError::Io)?;
         let checkpoints_dir = app_dir.join("checkpoints");
         let checkpoint_path = checkpoints_dir.join(format!("{}.json", checkpoint.project_id));
-        
-        let content = serde_json::to_string_pretty(checkpoint)
-            .map_err(|e| format!("Failed to serialize checkpoint: {}", e))?;
-            
-        fs::write(&checkpoint_path, content)
-            .map_err(|e| format!("Failed to write checkpoint file: {}", e))?;
-            
+        let tmp_path = checkpoints_dir.join(format!("{}.json.tmp", checkpoint.project_id));
+
+        let content = serde_json::to_string_pretty(checkpoint)?;
+
+        fs::write(&tmp_path, content)?;
+        fs::rename(&tmp_path, &checkpoint_path)?;
+
         Ok(())
     }
+
+    /// Rebuild a `GenerationCheckpoint` from current in-memory state and
+    /// persist it, if this project has a stored config to checkpoint against.
+    /// Called after status changes, task completions, and log batches so a
+    /// crash mid-generation loses as little progress as possible.
+    async fn checkpoint_current_state(&self, project_id: &str) {
+        let config = match self.get_project_config(project_id).await {
+            Some(config) => config,
+            None => return,
+        };
+        let project_path = PathBuf::from(&config.path).join(&config.name);
+        let status = self.get_project_status(project_id).await;
+        let completed_tasks = self.get_completed_tasks(project_id).await;
+        let logs = self.get_logs(project_id).await;
+        let task_hashes = self.get_task_hashes(project_id).await;
+
+        let checkpoint = GenerationCheckpoint {
+            version: CHECKPOINT_FORMAT_VERSION,
+            project_id: project_id.to_string(),
+            config,
+            status: Some(status),
+            completed_tasks,
+            logs,
+            task_hashes,
+            project_path,
+        };
+
+        {
+            let mut checkpoints = self.checkpoints.lock().await;
+            checkpoints.insert(project_id.to_string(), checkpoint.clone());
+        }
+
+        if let Err(e) = self.save_checkpoint(&checkpoint).await {
+            warn!("Failed to persist checkpoint for project {}: {}", project_id, e);
+        }
+    }
     
     /// Delete a checkpoint from disk
     async fn delete_checkpoint(&self, project_id: &str) -> Result<(), String> {
@@ -359,36 +570,38 @@ impl AppState {
                     project_id: project_id.to_string(),
                     step: current_step.clone(),
                     progress: *progress,
+                    task_counts: None,
                 });
+
+                self.checkpoint_current_state(project_id).await;
             },
             ProjectStatus::Completed { path } => {
                 let _ = self.event_tx.send(ProjectEvent::Completed {
                     project_id: project_id.to_string(),
                     path: path.clone(),
                 });
-                
+
                 // Clean up checkpoint if generation was successful
                 let _ = self.delete_checkpoint(project_id).await;
             },
             ProjectStatus::Failed { error, resumable } => {
                 let _ = self.event_tx.send(ProjectEvent::Failed {
                     project_id: project_id.to_string(),
-                    error: error.clone(),
+                    error: GenerationError::Failed { message: error.clone(), resumable: *resumable },
                     resumable: *resumable,
                 });
-                
-                // Create checkpoint if resumable
+
+                // Create/refresh the checkpoint if resumable, so it reflects
+                // the failure status and the full log history up to this point
                 if *resumable {
-                    if let Some(checkpoint) = self.checkpoints.lock().await.get(project_id) {
-                        let _ = self.save_checkpoint(checkpoint).await;
-                    }
+                    self.checkpoint_current_state(project_id).await;
                 }
             },
             ProjectStatus::Cancelled => {
                 let _ = self.event_tx.send(ProjectEvent::Cancelled {
                     project_id: project_id.to_string(),
                 });
-                
+
                 // Clean up checkpoint if generation was cancelled
                 let _ = self.delete_checkpoint(project_id).await;
             },
@@ -403,20 +616,155 @@ impl AppState {
             .unwrap_or(ProjectStatus::NotStarted)
     }
     
-    /// Add a log entry for a project
+    /// Add an info-level log entry for a project. See `add_log_leveled` for
+    /// a tagged level/task id.
     pub async fn add_log(&self, project_id: &str, message: &str) {
-        let mut logs = self.logs.lock().await;
-        let project_logs = logs.entry(project_id.to_string()).or_insert_with(Vec::new);
-        let log_entry = LogEntry::new(message.to_string());
-        project_logs.push(log_entry);
-        
+        self.add_log_leveled(project_id, LogLevel::Info, None, message).await;
+    }
+
+    /// Add a level- and (optionally) task-tagged log entry for a project:
+    /// routes through the qualified `log` macros, appends to the
+    /// project's ring buffer `get_project_logs` reads, and broadcasts a
+    /// `ProjectEvent::LogMessage` the frontend can subscribe to live via
+    /// the `project-log` event.
+    pub async fn add_log_leveled(&self, project_id: &str, level: LogLevel, task_id: Option<&str>, message: &str) {
+        match level {
+            LogLevel::Error => log::error!("[{}] {}", project_id, message),
+            LogLevel::Warn => log::warn!("[{}] {}", project_id, message),
+            LogLevel::Info => log::info!("[{}] {}", project_id, message),
+            LogLevel::Debug => log::debug!("[{}] {}", project_id, message),
+        }
+
+        let task_id = task_id.map(|s| s.to_string());
+        let should_checkpoint;
+        {
+            let mut logs = self.logs.lock().await;
+            let project_logs = logs.entry(project_id.to_string()).or_insert_with(Vec::new);
+            project_logs.push(LogEntry::new(level, task_id.clone(), message.to_string()));
+
+            if project_logs.len() > MAX_LOG_ENTRIES_PER_PROJECT {
+                // Drop at least ten at a time so the length keeps cycling
+                // and the every-10-lines checkpoint below still holds at the cap.
+                let overflow = project_logs.len() - MAX_LOG_ENTRIES_PER_PROJECT;
+                project_logs.drain(0..overflow.max(10));
+            }
+
+            // Persist every 10 log lines rather than every single one, so a
+            // crash loses at most one small batch of log history.
+            should_checkpoint = project_logs.len() % 10 == 0;
+        }
+
+        if let Err(e) = self.append_project_log_file(
+            project_id,
+            &LogEntry::new(level, task_id.clone(), message.to_string()),
+        ).await {
+            warn!("Failed to persist log entry for project '{}': {}", project_id, e);
+        }
+
         // Emit log event
         let _ = self.event_tx.send(ProjectEvent::LogMessage {
             project_id: project_id.to_string(),
+            level,
+            task_id,
             message: message.to_string(),
         });
+
+        if should_checkpoint {
+            self.checkpoint_current_state(project_id).await;
+        }
     }
-    
+
+    /// Associate `project_id` with the window that started (or resumed) its
+    /// generation, so its events can be routed only to that window.
+    pub async fn register_project_window(&self, project_id: &str, window_label: &str) {
+        self.project_windows.lock().await.insert(project_id.to_string(), window_label.to_string());
+    }
+
+    /// The window label `project_id`'s events should be routed to, if one
+    /// has been registered.
+    pub async fn get_project_window(&self, project_id: &str) -> Option<String> {
+        self.project_windows.lock().await.get(project_id).cloned()
+    }
+
+    /// Path to a project's persisted JSON-lines log file under the app
+    /// data directory, creating the containing directory if needed.
+    pub(crate) fn project_log_file_path(&self, project_id: &str) -> Result<PathBuf, String> {
+        let app_dir = self.get_app_data_dir()?;
+        let logs_dir = app_dir.join("project-logs");
+        if !logs_dir.exists() {
+            fs::create_dir_all(&logs_dir)
+                .map_err(|e| format!("Failed to create '{}': {}", logs_dir.display(), e))?;
+        }
+        Ok(logs_dir.join(format!("{}.jsonl", project_id)))
+    }
+
+    /// Append one JSON-lines record to a project's persistent log file, so
+    /// generation history survives the in-memory ring buffer (`logs`)
+    /// being lost across restarts or an early window close (see the
+    /// `cleanup-resources` event).
+    pub async fn append_project_log_file(&self, project_id: &str, entry: &LogEntry) -> Result<(), String> {
+        let path = self.project_log_file_path(project_id)?;
+        let mut line = serde_json::to_string(entry)
+            .map_err(|e| format!("Failed to serialize log entry: {}", e))?;
+        line.push('\n');
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .map_err(|e| format!("Failed to open '{}': {}", path.display(), e))?;
+        file.write_all(line.as_bytes())
+            .map_err(|e| format!("Failed to write '{}': {}", path.display(), e))
+    }
+
+    /// Export a project's persisted log file for attaching to a bug
+    /// report, writing a companion plain-text diagnostic dump alongside
+    /// it. Returns the log file's path.
+    pub async fn export_project_log(&self, project_id: &str) -> Result<String, String> {
+        let log_path = self.project_log_file_path(project_id)?;
+        if !log_path.exists() {
+            return Err(format!("No persisted log file for project '{}'", project_id));
+        }
+
+        let diagnostic = self.build_project_diagnostic(project_id).await;
+        let diagnostic_path = log_path.with_extension("diagnostic.txt");
+        if let Err(e) = fs::write(&diagnostic_path, diagnostic) {
+            warn!("Failed to write diagnostic dump for project '{}': {}", project_id, e);
+        }
+
+        Ok(log_path.display().to_string())
+    }
+
+    /// Plain-text diagnostic dump for a project: status, per-task state
+    /// and metadata. Mirrors `get_task_diagnostic`'s formatting (see
+    /// `main.rs`), duplicated here since that command needs a
+    /// `tauri::AppHandle` this module doesn't have.
+    async fn build_project_diagnostic(&self, project_id: &str) -> String {
+        let mut result = String::new();
+        result.push_str(&format!("Project ID: {}\n", project_id));
+
+        let status = self.get_project_status(project_id).await;
+        result.push_str(&format!("Project status: {:?}\n", status));
+
+        let task_states = self.get_all_task_states(project_id).await;
+        result.push_str(&format!("Total tasks: {}\n\n", task_states.len()));
+
+        let task_metadata = self.get_task_metadata(project_id).await;
+        for (task_id, state) in &task_states {
+            result.push_str(&format!("Task ID: {}\n", task_id));
+            if let Some(metadata) = task_metadata.get(task_id) {
+                result.push_str(&format!("  Name: {}\n", metadata.name));
+                result.push_str(&format!("  Dependencies: {:?}\n", metadata.dependencies));
+            } else {
+                result.push_str("  Metadata: Not found\n");
+            }
+            result.push_str(&format!("  State: {:?}\n", state));
+            result.push('\n');
+        }
+
+        result
+    }
+
     /// Get a project's logs
     pub async fn get_logs(&self, project_id: &str) -> Vec<LogEntry> {
         let logs = self.logs.lock().await;
@@ -424,6 +772,16 @@ impl AppState {
             .cloned()
             .unwrap_or_default()
     }
+
+    /// Get a project's logs, keeping only entries at least as severe as
+    /// `min_level` (`None` keeps everything).
+    pub async fn get_logs_filtered(&self, project_id: &str, min_level: Option<LogLevel>) -> Vec<LogEntry> {
+        let logs = self.get_logs(project_id).await;
+        match min_level {
+            Some(level) => logs.into_iter().filter(|entry| entry.level <= level).collect(),
+            None => logs,
+        }
+    }
     
     /// Set a task's state
     pub async fn set_task_state(&self, project_id: &str, task_id: &str, state: TaskState) {
@@ -431,23 +789,76 @@ impl AppState {
         let project_tasks = task_states.entry(project_id.to_string()).or_insert_with(HashMap::new);
         project_tasks.insert(task_id.to_string(), state.clone());
         
-        // If task completed, add to completed tasks for checkpoint
-        if let TaskState::Completed = state {
+        // If task completed (or was skipped), add it to
+        // completed tasks for checkpoint and dependency-satisfaction purposes
+        let task_completed = matches!(state, TaskState::Completed | TaskState::Skipped);
+        if task_completed {
             let mut completed = self.completed_tasks.lock().await;
             let project_completed = completed.entry(project_id.to_string()).or_insert_with(Vec::new);
             if !project_completed.contains(&task_id.to_string()) {
                 project_completed.push(task_id.to_string());
             }
         }
-        
+
+        if let Err(e) = self.append_project_log_file(
+            project_id,
+            &LogEntry::new(LogLevel::Info, Some(task_id.to_string()), format!("Task state changed to {:?}", state)),
+        ).await {
+            warn!("Failed to persist task state change for project '{}': {}", project_id, e);
+        }
+
         // Emit task state change event
         let _ = self.event_tx.send(ProjectEvent::TaskStateChanged {
             project_id: project_id.to_string(),
             task_id: task_id.to_string(),
             state,
         });
+
+        // A completed step is meaningful progress worth persisting immediately.
+        if task_completed {
+            self.checkpoint_current_state(project_id).await;
+        }
+
+        // A completion may unblock tasks whose dependencies are now all
+        // satisfied; announce those so they can be dispatched right away
+        // instead of waiting for the next scheduling pass.
+        if task_completed {
+            self.emit_newly_ready_tasks(project_id, task_id).await;
+        }
     }
-    
+
+    /// Tasks in `project_id` whose dependencies are all satisfied and that
+    /// haven't already run (see `scheduler::ready_tasks`).
+    pub async fn ready_tasks(&self, project_id: &str) -> Vec<String> {
+        let metadata = self.get_task_metadata(project_id).await;
+        let task_states = self.get_all_task_states(project_id).await;
+        let completed = self.get_completed_tasks(project_id).await;
+
+        crate::scheduler::ready_tasks(&metadata, &task_states, &completed)
+    }
+
+    /// Emit `ProjectEvent::TaskReady` for every task directly unblocked by
+    /// `completed_task_id` just finishing, so a single completion doesn't
+    /// re-announce tasks that were already ready for other reasons.
+    async fn emit_newly_ready_tasks(&self, project_id: &str, completed_task_id: &str) {
+        let metadata = self.get_task_metadata(project_id).await;
+        let ready = self.ready_tasks(project_id).await;
+
+        for task_id in ready {
+            let depends_on_completed = metadata
+                .get(&task_id)
+                .map(|task| task.dependencies.iter().any(|dep| dep == completed_task_id))
+                .unwrap_or(false);
+
+            if depends_on_completed {
+                self.emit_event(ProjectEvent::TaskReady {
+                    project_id: project_id.to_string(),
+                    task_id,
+                }).await;
+            }
+        }
+    }
+
     /// Get a task's state
     pub async fn get_task_state(&self, project_id: &str, task_id: &str) -> Option<TaskState> {
         let task_states = self.task_states.lock().await;
@@ -471,23 +882,51 @@ impl AppState {
             .cloned()
             .unwrap_or_default()
     }
-    
+
+    /// Get the cache keys recorded for a project's tasks, as of the last
+    /// checkpoint (see `cache::hash_task`).
+    pub async fn get_task_hashes(&self, project_id: &str) -> HashMap<String, String> {
+        let task_hashes = self.task_hashes.lock().await;
+        task_hashes.get(project_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Merge freshly computed task cache keys into a project's stored set
+    /// and persist them in its checkpoint immediately, so they survive a
+    /// crash even before the next status-driven checkpoint.
+    pub async fn store_task_hashes(&self, project_id: &str, hashes: HashMap<String, String>) {
+        {
+            let mut task_hashes = self.task_hashes.lock().await;
+            task_hashes.entry(project_id.to_string()).or_default().extend(hashes);
+        }
+
+        self.checkpoint_current_state(project_id).await;
+    }
+
     /// Create a checkpoint for a project
     pub async fn create_checkpoint(
         &self,
         project_id: &str,
         config: crate::commands::project::ProjectConfig,
         project_path: PathBuf,
-    ) -> Result<(), String> {
+    ) -> Result<(), GenerationError> {
         let completed_tasks = self.get_completed_tasks(project_id).await;
-        
+        let status = self.get_project_status(project_id).await;
+        let logs = self.get_logs(project_id).await;
+        let task_hashes = self.get_task_hashes(project_id).await;
+
         let checkpoint = GenerationCheckpoint {
+            version: CHECKPOINT_FORMAT_VERSION,
             project_id: project_id.to_string(),
             config,
+            status: Some(status),
             completed_tasks,
+            logs,
+            task_hashes,
             project_path,
         };
-        
+
         // Add to memory
         {
             let mut checkpoints = self.checkpoints.lock().await;
@@ -504,19 +943,30 @@ impl AppState {
         checkpoints.get(project_id).cloned()
     }
     
-    /// Process a task result
+    /// Process a task result. By the time a result reaches here, any
+    /// retries have already been exhausted (or the failure was classified
+    /// non-retryable) by `TaskExecutor`, so a `success = false` result
+    /// always means the task is permanently `Failed`.
     pub async fn process_task_result(&self, project_id: &str, result: TaskResult) {
         // Update task state
         if result.success {
-            self.set_task_state(project_id, &result.task_id, TaskState::Completed).await;
+            let state = if result.skipped { TaskState::Skipped } else { TaskState::Completed };
+            self.set_task_state(project_id, &result.task_id, state).await;
         } else {
             self.set_task_state(
                 project_id,
                 &result.task_id,
                 TaskState::Failed(result.message.clone()),
             ).await;
+
+            if !result.retryable {
+                self.add_log(project_id, &format!(
+                    "Task {} failed with a non-retryable error: {}", result.task_id, result.message
+                )).await;
+                return;
+            }
         }
-        
+
         // Add log entry
         self.add_log(project_id, &result.message).await;
     }
@@ -533,27 +983,35 @@ impl AppState {
     
     /// Add a progress update for a project
     pub async fn update_progress(&self, project_id: &str, step: &str, progress: u8) {
+        self.update_progress_with_counts(project_id, step, progress, None).await
+    }
+
+    /// Add a progress update for a project, additionally carrying the
+    /// completed/total task counts it was derived from -- see
+    /// `ProjectEvent::Progress::task_counts`.
+    pub async fn update_progress_with_counts(&self, project_id: &str, step: &str, progress: u8, task_counts: Option<(u64, u64)>) {
         // Update the project status
         let mut projects = self.projects.lock().await;
-        projects.insert(project_id.to_string(), ProjectStatus::Generating { 
-            current_step: step.to_string(), 
-            progress 
+        projects.insert(project_id.to_string(), ProjectStatus::Generating {
+            current_step: step.to_string(),
+            progress
         });
-        
+
         // Also emit a progress event
         let _ = self.event_tx.send(ProjectEvent::Progress {
             project_id: project_id.to_string(),
             step: step.to_string(),
             progress,
+            task_counts,
         });
-        
+
         // Add to logs as well
         drop(projects); // Release lock before calling add_log
         self.add_log(project_id, &format!("Progress: {}% - {}", progress, step)).await;
     }
     
     /// Store project config
-    pub async fn store_project_config(&self, project_id: &str, config: crate::commands::project::ProjectConfig) -> Result<(), String> {
+    pub async fn store_project_config(&self, project_id: &str, config: crate::commands::project::ProjectConfig) -> Result<(), GenerationError> {
         let mut configs = self.project_configs.lock().await;
         configs.insert(project_id.to_string(), config);
         Ok(())
@@ -566,12 +1024,12 @@ impl AppState {
     }
     
     /// Register a task
-    pub async fn register_task(&self, project_id: &str, task_id: &str, task_name: &str, dependencies: &[String]) {
+    pub async fn register_task(&self, project_id: &str, task_id: &str, task_name: &str, dependencies: &[String], max_attempts: u32) {
         let mut metadata = self.task_metadata.lock().await;
-        
+
         // Get or create project task map
         let project_tasks = metadata.entry(project_id.to_string()).or_insert_with(HashMap::new);
-        
+
         // Create task metadata
         let task = GenerationTask {
             id: task_id.to_string(),
@@ -580,17 +1038,41 @@ impl AppState {
             status: TaskStatus::Pending,
             progress: 0.0,
             dependencies: dependencies.to_vec(),
+            attempt: 1,
+            max_attempts,
         };
-        
+
         // Insert task
         project_tasks.insert(task_id.to_string(), task);
     }
-    
+
     /// Get task metadata
     pub async fn get_task_metadata(&self, project_id: &str) -> HashMap<String, GenerationTask> {
         let metadata = self.task_metadata.lock().await;
         metadata.get(project_id).cloned().unwrap_or_default()
     }
+
+    /// Record that a task is about to be retried, updating its metadata's
+    /// attempt counter and emitting `ProjectEvent::TaskRetrying`.
+    pub async fn mark_task_retrying(&self, project_id: &str, task_id: &str, attempt: u32, next_delay_ms: u64) {
+        {
+            let mut metadata = self.task_metadata.lock().await;
+            if let Some(project_tasks) = metadata.get_mut(project_id) {
+                if let Some(task) = project_tasks.get_mut(task_id) {
+                    task.attempt = attempt;
+                }
+            }
+        }
+
+        self.set_task_state(project_id, task_id, TaskState::Pending).await;
+
+        self.emit_event(ProjectEvent::TaskRetrying {
+            project_id: project_id.to_string(),
+            task_id: task_id.to_string(),
+            attempt,
+            next_delay_ms,
+        }).await;
+    }
     
     /// Emit event
     pub async fn emit_event(&self, event: ProjectEvent) {
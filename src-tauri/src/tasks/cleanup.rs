@@ -7,8 +7,9 @@ use async_trait::async_trait;
 use log::{info, warn};
 use serde_json::Value;
 
-use crate::commands::node_commands::execute_node_command;
-use super::{Task, TaskContext};
+use crate::commands::node_commands::{execute_node_command, execute_node_command_with_retry};
+use crate::commands::package_manager::command_string;
+use super::{Task, TaskContext, TaskOutput};
 
 /// Task for project cleanup
 pub struct CleanupTask {
@@ -50,15 +51,17 @@ impl Task for CleanupTask {
         &self.dependencies
     }
     
-    async fn execute(&self, context: &TaskContext) -> Result<(), String> {
+    async fn execute(&self, context: &TaskContext) -> Result<TaskOutput, String> {
         // Use only the needed context variables
         let app_handle = &context.app_handle;
-        let base_dir = &context.project_dir;
         let config = &context.config;
-        
-        // Create the full project path (base_dir/project_name)
-        let project_dir = base_dir.join(&config.name);
-        
+
+        // Write into the run's staging directory, same as `FrameworkTask`
+        // and `ModuleTask` -- this task runs right after them in the same
+        // pipeline, so its effects need to land wherever theirs did, not at
+        // the real `project_dir` before the whole staged run has succeeded.
+        let project_dir = context.staging_dir.to_path_buf();
+
         // Log the actual directory we're working in
         info!("Working directory for cleanup task: {}", project_dir.display());
         app_handle.emit("log-message", format!("Cleaning up project in: {}", project_dir.display())).unwrap();
@@ -81,51 +84,56 @@ impl Task for CleanupTask {
                 warn!("Failed to create completion file: {}", e);
             }
             
-            return Ok(());  // Skip further cleanup since we just created the directory
+            return Ok(TaskOutput::default());  // Skip further cleanup since we just created the directory
         }
         
         // Start the cleanup phase
         info!("Starting project cleanup phase");
         app_handle.emit("log-message", "Starting project cleanup phase").unwrap();
-        
+
+        // Resolve the package-manager backend once, so every command below
+        // runs in whichever manager the project is actually configured for
+        // (or auto-detected from its lockfile) instead of hardcoded npm.
+        let backend = crate::commands::package_manager::resolve(&project_dir, config.package_manager.as_ref());
+
         // Check if we need to install dependencies
         let package_json_path = project_dir.join("package.json");
         let node_modules_path = project_dir.join("node_modules");
-        let package_lock_path = project_dir.join("package-lock.json");
-        
-        // If we have a package.json but no node_modules, we need to run npm install
-        if package_json_path.exists() && (!node_modules_path.exists() || !package_lock_path.exists()) {
-            info!("Installing npm dependencies");
-            app_handle.emit("log-message", "Installing npm dependencies...").unwrap();
-            
-            // Run npm install with retry logic
-            let npm_result = execute_node_command(
+        let lockfile_path = project_dir.join(backend.lockfile_name());
+
+        // If we have a package.json but no node_modules, we need to install
+        if package_json_path.exists() && (!node_modules_path.exists() || !lockfile_path.exists()) {
+            info!("Installing dependencies");
+            app_handle.emit("log-message", "Installing dependencies...").unwrap();
+
+            let install_result = execute_node_command_with_retry(
                 app_handle,
                 &project_dir,
-                "npm install",
-                None
+                &command_string(backend.install_cmd()),
+                None,
+                &config.retry_policy,
             ).await;
-                
-            match npm_result {
+
+            match install_result {
                 Ok(result) => {
                     if result.success {
-                        info!("NPM dependencies installed successfully");
-                        app_handle.emit("log-message", "NPM dependencies installed successfully").unwrap();
+                        info!("Dependencies installed successfully");
+                        app_handle.emit("log-message", "Dependencies installed successfully").unwrap();
                     } else {
-                        let warning = format!("Warning: NPM install failed: {}", result.stderr);
+                        let warning = format!("Warning: Dependency install failed: {}", result.stderr);
                         warn!("{}", warning);
                         app_handle.emit("log-message", warning).unwrap();
                     }
                 },
                 Err(e) => {
-                    let warning = format!("Warning: NPM install error: {}", e);
+                    let warning = format!("Warning: Dependency install error: {}", e);
                     warn!("{}", warning);
                     app_handle.emit("log-message", warning).unwrap();
                 }
             }
         } else if package_json_path.exists() && node_modules_path.exists() {
-            info!("Node modules already installed, skipping npm install");
-            app_handle.emit("log-message", "Node modules already installed, skipping npm install").unwrap();
+            info!("Dependencies already installed, skipping install");
+            app_handle.emit("log-message", "Dependencies already installed, skipping install").unwrap();
         }
         
         // Check for formatter configurations like prettier
@@ -137,17 +145,18 @@ impl Task for CleanupTask {
             app_handle.emit("log-message", "Running code formatting...").unwrap();
             
             // Format the project code if possible
-            info!("Running npm format");
-            app_handle.emit("log-message", "Running npm format").unwrap();
-            
-            let npm_result = execute_node_command(
+            let format_cmd = command_string(backend.run_script_cmd("format"));
+            info!("Running {}", format_cmd);
+            app_handle.emit("log-message", format!("Running {}", format_cmd)).unwrap();
+
+            let format_result = execute_node_command(
                 app_handle,
                 &project_dir,
-                "npm run format",
+                &format_cmd,
                 None
             ).await;
-                
-            match npm_result {
+
+            match format_result {
                 Ok(result) => {
                     if !result.success {
                         let warning = "Warning: Code formatting failed, but continuing";
@@ -207,14 +216,16 @@ impl Task for CleanupTask {
                                     app_handle.emit("log-message", "Running development build...").unwrap();
                                     
                                     // Run the build command
-                                    info!("Running npm run build");
-                                    app_handle.emit("log-message", "Running npm run build to pre-build the project").unwrap();
-                                    
-                                    let build_result = execute_node_command(
+                                    let build_cmd = command_string(backend.run_script_cmd("build"));
+                                    info!("Running {}", build_cmd);
+                                    app_handle.emit("log-message", format!("Running {} to pre-build the project", build_cmd)).unwrap();
+
+                                    let build_result = execute_node_command_with_retry(
                                         app_handle,
                                         &project_dir,
-                                        "npm run build",
-                                        None
+                                        &build_cmd,
+                                        None,
+                                        &config.retry_policy,
                                     ).await;
                                         
                                     match build_result {
@@ -256,22 +267,30 @@ impl Task for CleanupTask {
         info!("Running tests before finalizing the project");
         app_handle.emit("log-message", "Running tests to ensure project quality").unwrap();
         
-        let build_result = execute_node_command(
+        let test_result = execute_node_command(
             app_handle,
             &project_dir,
-            "npm test",
+            &command_string(backend.test_cmd()),
             None
         ).await;
-        
-        match build_result {
+
+        match test_result {
             Ok(result) => {
-                if result.success {
-                    info!("Tests passed");
-                    app_handle.emit("log-message", "Tests passed").unwrap();
-                } else {
-                    let warning = format!("Warning: Tests failed: {}", result.stderr);
-                    warn!("{}", warning);
-                    app_handle.emit("log-message", warning).unwrap();
+                // Jest's `--json` output and TAP both show up in generated
+                // projects' `test` scripts; when we can parse one, emit a
+                // structured test list instead of just the pass/fail flag.
+                match crate::test_report::parse_test_output(&result.stdout) {
+                    Some(events) => crate::test_report::emit_events(app_handle, &events),
+                    None => {
+                        if result.success {
+                            info!("Tests passed");
+                            app_handle.emit("log-message", "Tests passed").unwrap();
+                        } else {
+                            let warning = format!("Warning: Tests failed: {}", result.stderr);
+                            warn!("{}", warning);
+                            app_handle.emit("log-message", warning).unwrap();
+                        }
+                    }
                 }
             },
             Err(e) => {
@@ -283,7 +302,7 @@ impl Task for CleanupTask {
         
         info!("Project cleanup completed");
         app_handle.emit("log-message", "Project cleanup completed").unwrap();
-        
-        Ok(())
+
+        Ok(TaskOutput::default())
     }
 } 
\ No newline at end of file
@@ -1,12 +1,15 @@
 //! Directory structure task implementation
 
+use std::collections::HashSet;
 use std::fs;
-use tauri::{Emitter};
+use std::sync::Arc;
+use tauri::{Emitter, Manager};
 
 use async_trait::async_trait;
-use log::{info, error};
+use log::{info, error, warn};
 
-use super::{Task, TaskContext};
+use super::{Capabilities, Task, TaskContext, TaskError, TaskOutput};
+use crate::commands::framework::Framework;
 
 // Import the get_framework function from the framework module
 use super::framework::get_framework;
@@ -22,14 +25,11 @@ pub struct DirectoryTask {
 }
 
 impl DirectoryTask {
-    /// Create a new directory task
-    pub fn new(context: TaskContext) -> Self {
-        let config = &context.config;
-        let framework_name = &config.framework;
-        
+    /// Create a new directory task for the given framework.
+    pub fn new(framework_id: &str) -> Self {
         Self {
-            id: format!("directory:{}", framework_name),
-            name: format!("Create directory structure for {}", framework_name),
+            id: format!("directory:{}", framework_id),
+            name: format!("Create directory structure for {}", framework_id),
             dependencies: Vec::new(), // Dependencies will be set separately
         }
     }
@@ -54,42 +54,72 @@ impl Task for DirectoryTask {
         &self.dependencies
     }
     
-    async fn execute(&self, context: &TaskContext) -> Result<(), String> {
+    async fn execute(&self, context: &TaskContext) -> Result<TaskOutput, String> {
         let config = &context.config;
         let app_handle = &context.app_handle;
-        let project_dir = &context.project_dir;
+        // Write the enforced directories into the run's staging directory
+        // rather than the final project path -- see `TaskContext::staging_dir`.
+        let project_dir = &context.staging_dir;
         
         // Get framework details
         let framework = get_framework(&config.framework).await?;
-        
+
+        // Start watching the staging directory now, so the frontend sees
+        // live `fs-change` events for every later task (npm install, git
+        // init, ...) that mutates it -- not just the directories this task
+        // itself creates. Failing to start the watcher shouldn't fail
+        // generation; it's a supplementary UX feature.
+        let app_state = app_handle.state::<Arc<crate::state::AppState>>();
+        if let Err(e) = app_state
+            .watchers
+            .start(&context.project_id, project_dir.to_path_buf(), app_handle.clone(), HashSet::new())
+            .await
+        {
+            warn!("Failed to start filesystem watcher for {}: {}", context.project_id, e);
+        }
+
         // Skip this task if the framework doesn't enforce directory structure
         if !framework.directory_structure.enforced {
             info!("Framework does not enforce directory structure, skipping");
             app_handle.emit("log-message", "Framework does not enforce directory structure, skipping").unwrap();
-            return Ok(());
+            return Ok(TaskOutput::default());
         }
         
         // Create enforced directories
-        for dir in &framework.directory_structure.directories {
+        let reporter = context.progress_reporter(self.id.clone());
+        reporter.start("Creating directory structure");
+        let total = framework.directory_structure.directories.len() as u64;
+        for (i, dir) in framework.directory_structure.directories.iter().enumerate() {
             let dir_path = project_dir.join(dir);
             if !dir_path.exists() {
                 info!("Creating directory: {}", dir_path.display());
                 app_handle.emit("log-message", format!("Creating directory: {}", dir_path.display())).unwrap();
-                
+
                 if let Err(e) = fs::create_dir_all(&dir_path) {
-                    let error = format!("Failed to create directory '{}': {}", dir_path.display(), e);
+                    let error = TaskError::FilesystemError { path: dir_path.display().to_string(), source: e }.render();
                     error!("{}", error);
-                    app_handle.emit("log-message", format!("Failed to create directory: {}", e)).unwrap();
+                    app_handle.emit("log-message", format!("Failed to create directory: {}", error)).unwrap();
+                    reporter.finish(false, error.clone());
                     return Err(error);
                 }
             } else {
                 info!("Directory already exists: {}", dir_path.display());
                 app_handle.emit("log-message", format!("Directory already exists: {}", dir_path.display())).unwrap();
             }
+            reporter.tick((i + 1) as u64, total);
         }
-        
+
         info!("Directory structure created successfully");
         app_handle.emit("log-message", "Directory structure created successfully").unwrap();
-        Ok(())
+        reporter.finish(true, "Directory structure created successfully");
+        Ok(TaskOutput::default())
+    }
+
+    fn capabilities(&self, framework: &Framework) -> Capabilities {
+        Capabilities {
+            enforces_directory_structure: framework.directory_structure.enforced,
+            enforced_directories: framework.directory_structure.directories.clone(),
+            ..Default::default()
+        }
     }
 } 
\ No newline at end of file
@@ -0,0 +1,175 @@
+//! Task that downloads and verifies an external archive or starter repo a
+//! framework/module declares via `commands::framework::Fetch`, so a
+//! dependent task can rely on a known, tamper-evident local file instead of
+//! reaching out to the network itself.
+//!
+//! Verified downloads are cached by digest under the app data directory, so
+//! a repeated generation -- of this project, a regeneration, or a
+//! different project pinning the same artifact -- reuses the download
+//! instead of refetching it.
+
+use std::path::{Path, PathBuf};
+
+use async_trait::async_trait;
+use log::{info, warn};
+use sha2::{Digest, Sha256};
+use tauri::Manager;
+
+use crate::commands::framework::Fetch;
+use super::{Task, TaskContext, TaskOutput};
+
+pub struct FetchTask {
+    id: String,
+    name: String,
+    dependencies: Vec<String>,
+    fetch: Fetch,
+}
+
+impl FetchTask {
+    pub fn new(fetch: Fetch, dependencies: Vec<String>) -> Self {
+        Self {
+            id: format!("fetch:{}", fetch.name),
+            name: format!("Fetch: {}", fetch.name),
+            dependencies,
+            fetch,
+        }
+    }
+
+    /// Where the verified download for this digest lives, shared across
+    /// every project that pins the same `sha256`.
+    fn cache_path(app_data_dir: &Path, sha256: &str) -> PathBuf {
+        app_data_dir.join("fetch-cache").join(sha256)
+    }
+
+    /// This project's copy of the downloaded artifact, at a path the
+    /// dependent task can reference by `fetch.name`.
+    fn project_path(project_dir: &Path, name: &str) -> PathBuf {
+        project_dir.join("downloads").join(name)
+    }
+
+    async fn fetch_and_place(&self, context: &TaskContext, reporter: &crate::progress::ProgressReporter) -> Result<(usize, PathBuf, PathBuf), String> {
+        let app_data_dir = context
+            .app_handle
+            .path()
+            .app_data_dir()
+            .map_err(|e| format!("Failed to resolve app data directory: {}", e))?;
+        let cache_path = Self::cache_path(&app_data_dir, &self.fetch.sha256);
+
+        let bytes = if cache_path.exists() {
+            let cached = std::fs::read(&cache_path)
+                .map_err(|e| format!("Failed to read cached download '{}': {}", cache_path.display(), e))?;
+            if sha256_hex(&cached) == self.fetch.sha256 {
+                info!("Reusing cached download for '{}' ({})", self.fetch.name, self.fetch.sha256);
+                reporter.tick(1, 2);
+                cached
+            } else {
+                warn!("Cached download for '{}' no longer matches its digest, refetching", self.fetch.name);
+                // Drop the stale file so the verified bytes replace it below.
+                let _ = std::fs::remove_file(&cache_path);
+                self.download_and_verify().await?
+            }
+        } else {
+            self.download_and_verify().await?
+        };
+
+        if !cache_path.exists() {
+            if let Some(parent) = cache_path.parent() {
+                std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create fetch cache directory: {}", e))?;
+            }
+            let tmp_path = cache_path.with_extension("tmp");
+            std::fs::write(&tmp_path, &bytes).map_err(|e| format!("Failed to write '{}': {}", tmp_path.display(), e))?;
+            std::fs::rename(&tmp_path, &cache_path)
+                .map_err(|e| format!("Failed to finalize '{}': {}", cache_path.display(), e))?;
+        }
+
+        // Same staging directory every other task writes into -- landing
+        // this in `context.project_dir` instead would put a verified
+        // download at the real final path before the run has succeeded,
+        // breaking the atomic-generation contract (and risking a collision
+        // with `commit_staging_dir`'s `fs::rename`).
+        let project_path = Self::project_path(&context.staging_dir, &self.fetch.name);
+        if let Some(parent) = project_path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create downloads directory: {}", e))?;
+        }
+        std::fs::copy(&cache_path, &project_path).map_err(|e| format!("Failed to copy '{}' into project: {}", self.fetch.name, e))?;
+
+        reporter.tick(2, 2);
+        info!("Fetched and verified '{}' ({} bytes)", self.fetch.name, bytes.len());
+        Ok((bytes.len(), cache_path, project_path))
+    }
+
+    async fn download_and_verify(&self) -> Result<Vec<u8>, String> {
+        info!("Downloading '{}' from {}", self.fetch.name, self.fetch.url);
+        let response = reqwest::get(&self.fetch.url)
+            .await
+            .map_err(|e| format!("Failed to download '{}': {}", self.fetch.name, e))?;
+        if !response.status().is_success() {
+            return Err(format!("Download of '{}' returned status {}", self.fetch.name, response.status()));
+        }
+        let bytes = response
+            .bytes()
+            .await
+            .map_err(|e| format!("Failed to read download body for '{}': {}", self.fetch.name, e))?
+            .to_vec();
+
+        let digest = sha256_hex(&bytes);
+        if digest != self.fetch.sha256 {
+            return Err(format!(
+                "Checksum mismatch for '{}': expected sha256 {}, got {}",
+                self.fetch.name, self.fetch.sha256, digest
+            ));
+        }
+
+        Ok(bytes)
+    }
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(bytes);
+    format!("{:x}", hasher.finalize())
+}
+
+#[async_trait]
+impl Task for FetchTask {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn dependencies(&self) -> &[String] {
+        &self.dependencies
+    }
+
+    async fn execute(&self, context: &TaskContext) -> Result<TaskOutput, String> {
+        let reporter = context.progress_reporter(self.id.clone());
+        reporter.start(format!("Resolving '{}'", self.fetch.name));
+
+        let result = self.fetch_and_place(context, &reporter).await;
+        match &result {
+            Ok((bytes_len, ..)) => reporter.finish(true, format!("Fetched and verified '{}' ({} bytes)", self.fetch.name, bytes_len)),
+            Err(e) => reporter.finish(false, e.clone()),
+        }
+        // Expose where the verified artifact landed -- both the shared,
+        // digest-keyed cache copy and this project's own copy -- so a
+        // dependent task (e.g. a pipeline step extracting it) can locate it
+        // deterministically via `TaskContext::dependency_outputs` instead of
+        // re-deriving `cache_path`/`project_path` itself.
+        result.map(|(_, cache_path, project_path)| {
+            TaskOutput::new()
+                .with("sha256", self.fetch.sha256.clone())
+                .with("cache_path", cache_path.display().to_string())
+                .with("project_path", project_path.display().to_string())
+        })
+    }
+
+    /// Output only depends on the declared url/digest, not the whole
+    /// project config, so the same pinned artifact cache-hits across
+    /// unrelated projects (see `cache::hash_task`).
+    fn cache_inputs(&self, _context: &TaskContext) -> Vec<String> {
+        vec![self.fetch.url.clone(), self.fetch.sha256.clone()]
+    }
+}
@@ -0,0 +1,54 @@
+//! Transaction log for file operations within a single task.
+//!
+//! Each `create`/`modify` is recorded before it's applied so that, if the
+//! task fails partway through and `TaskContext::rollback_on_failure` is set,
+//! every touched file can be restored to its prior state instead of leaving
+//! the project half-modified.
+
+use std::path::{Path, PathBuf};
+
+/// What a file looked like before a transaction touched it.
+enum PriorState {
+    /// The file did not exist; rollback deletes it.
+    Absent,
+    /// The file existed with these bytes; rollback restores them.
+    Present(Vec<u8>),
+}
+
+/// Records the prior state of every file touched during a task so it can be
+/// rolled back on failure.
+#[derive(Default)]
+pub struct FileTransaction {
+    journal: Vec<(PathBuf, PriorState)>,
+}
+
+impl FileTransaction {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the state of `path` before it is created or modified. Call
+    /// this once per file, immediately before applying the operation.
+    pub fn record(&mut self, path: &Path) {
+        let prior = match std::fs::read(path) {
+            Ok(bytes) => PriorState::Present(bytes),
+            Err(_) => PriorState::Absent,
+        };
+        self.journal.push((path.to_path_buf(), prior));
+    }
+
+    /// Restore every recorded file to its prior state, in reverse order.
+    /// Best-effort: a single file failing to roll back doesn't stop the rest.
+    pub fn rollback(&self) {
+        for (path, prior) in self.journal.iter().rev() {
+            match prior {
+                PriorState::Absent => {
+                    let _ = std::fs::remove_file(path);
+                },
+                PriorState::Present(bytes) => {
+                    let _ = std::fs::write(path, bytes);
+                },
+            }
+        }
+    }
+}
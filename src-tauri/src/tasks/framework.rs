@@ -4,10 +4,10 @@ use tauri::{AppHandle, Emitter};
 use std::path::Path;
 
 use async_trait::async_trait;
-use log::{info, debug, warn};
+use log::{info, debug, error};
 use crate::commands::framework::{get_frameworks, Framework};
 use crate::commands::node_commands::execute_node_command;
-use crate::tasks::{Task, TaskContext, TaskState};
+use crate::tasks::{Task, TaskContext, TaskState, TaskError, TaskOutput};
 
 /// Task for setting up the framework
 pub struct FrameworkTask {
@@ -42,6 +42,13 @@ impl FrameworkTask {
             state: TaskState::Pending,
         }
     }
+
+    /// Override this task's dependencies, e.g. to wait on the framework's
+    /// declared `Fetch` tasks before scaffolding runs. Mirrors
+    /// `CleanupTask`/`LockfileTask::set_dependencies`.
+    pub fn set_dependencies(&mut self, dependencies: Vec<String>) {
+        self.dependencies = dependencies;
+    }
 }
 
 #[async_trait]
@@ -58,7 +65,7 @@ impl Task for FrameworkTask {
         &self.dependencies
     }
     
-    async fn execute(&self, context: &TaskContext) -> Result<(), String> {
+    async fn execute(&self, context: &TaskContext) -> Result<TaskOutput, String> {
         info!("Executing framework task");
         
         let config = &context.config;
@@ -68,11 +75,14 @@ impl Task for FrameworkTask {
         let frameworks = get_frameworks().await?;
         let framework = frameworks.iter()
             .find(|f| f.id == config.framework)
-            .ok_or_else(|| format!("Framework {} not found", config.framework))?;
+            .ok_or_else(|| TaskError::FrameworkNotFound { id: config.framework.clone() }.render())?;
         
-        // Get the base directory (not including project name)
-        // Framework commands like create-next-app already include the project name as an argument
-        let base_dir = &context.project_dir;
+        // Get the base directory (not including project name). Framework
+        // commands like create-next-app already include the project name
+        // as an argument, and this task writes into the run's staging
+        // directory rather than the final project path -- see
+        // `TaskContext::staging_dir`.
+        let base_dir = &context.staging_dir;
         
         // Log the task start
         info!("Setting up {} framework in {}", framework.name, base_dir.display());
@@ -85,61 +95,92 @@ impl Task for FrameworkTask {
             app_handle.emit("log-message", format!("Setting up framework with command: {}", setup_command))
                 .map_err(|e| format!("Failed to emit log message: {}", e))?;
             
-            // Execute the command directly using system command instead of the consolidated API
-            // which seems to be having issues with the nodejs-sidecar
             info!("Working directory: {}", base_dir.display());
-            
+
             // First check if the directory exists
             if !base_dir.exists() {
-                let error_msg = format!("Base directory does not exist: {}", base_dir.display());
+                let error_msg = TaskError::BaseDirMissing { path: base_dir.display().to_string() }.render();
                 app_handle.emit("log-message", format!("Error: {}", error_msg))
                     .map_err(|e| format!("Failed to emit log message: {}", e))?;
                 return Err(error_msg);
             }
-            
-            // Try to create the project directory directly using tokio's fs module
-            let project_folder = base_dir.join(&config.name);
-            if project_folder.exists() {
-                let warning_msg = format!("Project folder already exists: {}", project_folder.display());
-                warn!("{}", warning_msg);
-                app_handle.emit("log-message", warning_msg)
-                    .map_err(|e| format!("Failed to emit log message: {}", e))?;
-            }
-            
-            // Create a simple success check file to simulate framework success without running
-            // the actual command which is failing with nodejs-sidecar issues
-            let success_file = project_folder.join(".initialized");
-            info!("Creating project folder: {}", project_folder.display());
-            
-            // Create the project folder if it doesn't exist
-            if !project_folder.exists() {
-                if let Err(e) = std::fs::create_dir_all(&project_folder) {
-                    let error_msg = format!("Failed to create project folder: {}", e);
-                    app_handle.emit("log-message", format!("Error: {}", error_msg))
-                        .map_err(|e| format!("Failed to emit log message: {}", e))?;
-                    return Err(error_msg);
-                }
-            }
-            
-            // Create a success file to show the task completed
-            if let Err(e) = std::fs::write(&success_file, "Framework initialized successfully") {
-                let error_msg = format!("Failed to create success file: {}", e);
+
+            // Run the setup command under a PTY, since scaffolders like
+            // create-next-app probe `process.stdout.isTTY` and behave
+            // differently (or refuse interactive prompts entirely) when it's
+            // not set.
+            let parts: Vec<&str> = setup_command.split_whitespace().collect();
+            let (cmd_name, cmd_args) = parts.split_first()
+                .ok_or_else(|| TaskError::SetupCommandMissing { framework: framework.id.clone() }.render())?;
+
+            let reporter = context.progress_reporter(self.id.clone());
+            let exit = crate::progress::with_progress_async(
+                &reporter,
+                format!("Running {}", setup_command),
+                async {
+                    let handle = crate::process::ProcessRunner::new(*cmd_name)
+                        .args(cmd_args.iter().copied())
+                        .working_dir(base_dir.as_ref())
+                        .spawn(app_handle.clone())
+                        .await?;
+                    Ok(handle.wait().await)
+                },
+            )
+            .await?;
+
+            if !exit.success {
+                let error_msg = format!(
+                    "Framework setup command '{}' exited with status {:?}",
+                    setup_command, exit.code
+                );
+                error!("{}", error_msg);
                 app_handle.emit("log-message", format!("Error: {}", error_msg))
                     .map_err(|e| format!("Failed to emit log message: {}", e))?;
                 return Err(error_msg);
             }
-            
+
             app_handle.emit("log-message", format!("{} framework setup successful", framework.name))
                 .map_err(|e| format!("Failed to emit log message: {}", e))?;
-            
-            Ok(())
+
+            Ok(TaskOutput::new()
+                .with("framework_id", framework.id.clone())
+                .with("base_dir", base_dir.display().to_string()))
         } else {
             // No setup command provided
-            let error_msg = format!("No setup command provided for framework {}", framework.id);
+            let error_msg = TaskError::SetupCommandMissing { framework: framework.id.clone() }.render();
             app_handle.emit("log-message", format!("Error: {}", error_msg))
                 .map_err(|e| format!("Failed to emit log message: {}", e))?;
-            
+
             Err(error_msg)
         }
     }
-} 
\ No newline at end of file
+
+    fn is_retryable_error(&self, error: &str) -> bool {
+        // These diagnostic codes mean the configuration is wrong, not that
+        // the attempt was unlucky - retrying won't help.
+        !error.contains("architech::task::framework_not_found")
+            && !error.contains("architech::task::setup_command_missing")
+            && !error.contains("architech::task::base_dir_missing")
+    }
+
+    fn cache_inputs(&self, context: &TaskContext) -> Vec<String> {
+        // Only the framework choice and its setup command actually affect
+        // what this task produces.
+        vec![
+            context.config.framework.clone(),
+            context.config.setup_command.clone().unwrap_or_default(),
+        ]
+    }
+
+    fn capabilities(&self, _framework: &Framework) -> crate::tasks::Capabilities {
+        // Every framework runs its setup command the same way: under a
+        // PTY, expecting the command itself to create the project
+        // directory (e.g. `create-next-app <name>`).
+        crate::tasks::Capabilities {
+            requires_setup_command: true,
+            supports_pty: true,
+            creates_own_project_dir: true,
+            ..Default::default()
+        }
+    }
+}
\ No newline at end of file
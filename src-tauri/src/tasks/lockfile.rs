@@ -0,0 +1,124 @@
+//! Lockfile task implementation
+
+use async_trait::async_trait;
+use log::{info, warn};
+use tauri::Emitter;
+
+use crate::commands::framework::{get_framework_by_id, get_modules};
+use crate::commands::module_lockfile::ModulesLockfile;
+use crate::commands::package_inventory::PackageInventory;
+use crate::commands::project_lock::{LockedModule, ProjectLock};
+use super::{Task, TaskContext, TaskOutput};
+
+/// Task that pins the resolved framework/module versions of a finished
+/// scaffold into `architech.lock`, reading actual versions back from the
+/// project's `package.json`/lockfiles instead of trusting the declared
+/// `Framework`/`Module` definitions, so regeneration is reproducible and
+/// auditable.
+pub struct LockfileTask {
+    /// The task ID
+    id: String,
+    /// The task name
+    name: String,
+    /// The task dependencies
+    dependencies: Vec<String>,
+}
+
+impl LockfileTask {
+    /// Create a new lockfile task
+    pub fn new() -> Self {
+        Self {
+            id: "lockfile".to_string(),
+            name: "Write project lockfile".to_string(),
+            dependencies: Vec::new(), // Will be populated later
+        }
+    }
+
+    /// Set the dependencies for this task
+    pub fn set_dependencies(&mut self, dependencies: Vec<String>) {
+        self.dependencies = dependencies;
+    }
+}
+
+#[async_trait]
+impl Task for LockfileTask {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn dependencies(&self) -> &[String] {
+        &self.dependencies
+    }
+
+    async fn execute(&self, context: &TaskContext) -> Result<TaskOutput, String> {
+        let app_handle = &context.app_handle;
+
+        // Same staging directory `ModuleTask` wrote its lockfile into --
+        // this task reads it back before the run has been promoted to the
+        // real `project_dir`.
+        let project_dir = context.staging_dir.to_path_buf();
+
+        if !project_dir.exists() {
+            let warning = format!("Project directory does not exist for lockfile task, skipping: {}", project_dir.display());
+            warn!("{}", warning);
+            app_handle.emit("log-message", &warning).unwrap();
+            return Ok(TaskOutput::default());
+        }
+
+        // The module install record is what actually tells us which
+        // modules landed in this project -- `config.modules` is only what
+        // was requested before transitive resolution and conflict checks.
+        let modules_lockfile = match ModulesLockfile::load(&project_dir)? {
+            Some(lockfile) => lockfile,
+            None => {
+                info!("No modules lockfile found for {}, nothing to pin into architech.lock", project_dir.display());
+                return Ok(TaskOutput::default());
+            }
+        };
+
+        let inventory = PackageInventory::read(&project_dir);
+        let all_modules = get_modules().await?;
+
+        let framework = get_framework_by_id(&modules_lockfile.framework).await.ok();
+        let framework_version = framework
+            .as_ref()
+            .and_then(|f| inventory.version_of(&f.id).map(|v| v.to_string()))
+            .or_else(|| framework.as_ref().map(|f| f.version.clone()))
+            .unwrap_or_else(|| "unknown".to_string());
+
+        let modules = modules_lockfile.modules.iter().map(|installed| {
+            // Prefer the version actually resolved in package.json/the
+            // lockfile for whatever package(s) this module's install
+            // commands requested; fall back to the declared version for
+            // modules with no npm package to look up (file-only modules).
+            let resolved_version = all_modules.iter()
+                .find(|m| m.id == installed.id)
+                .and_then(|module| module.installation.commands.iter()
+                    .flat_map(|cmd| cmd.requested_packages())
+                    .find_map(|pkg| inventory.version_of(&pkg).map(|v| v.to_string())))
+                .unwrap_or_else(|| installed.version.clone());
+
+            LockedModule {
+                id: installed.id.clone(),
+                version: resolved_version,
+            }
+        }).collect();
+
+        let lock = ProjectLock {
+            framework: modules_lockfile.framework.clone(),
+            framework_version,
+            modules,
+        };
+
+        lock.save(&project_dir)?;
+
+        info!("Wrote architech.lock for project {}", context.project_id);
+        app_handle.emit("log-message", "Wrote architech.lock").unwrap();
+
+        Ok(TaskOutput::new().with("framework_version", lock.framework_version.clone()))
+    }
+}
@@ -6,22 +6,39 @@
 use std::collections::HashMap;
 use std::path::Path;
 use std::sync::Arc;
-use std::collections::HashSet;
 
 use async_trait::async_trait;
 use tauri::{AppHandle, Emitter};
 use tokio::sync::Mutex;
 
+use crate::cache;
+use crate::commands::framework::Framework;
+use crate::worker::WorkerHandle;
+
 // Re-export task modules
 mod framework;
 mod module;
 mod directory;
 mod cleanup;
+mod lockfile;
+mod pipeline;
+mod file_transaction;
+mod module_error;
+mod task_error;
+mod recipe;
+mod fetch;
 
 pub use framework::FrameworkTask;
 pub use module::ModuleTask;
 pub use directory::DirectoryTask;
 pub use cleanup::CleanupTask;
+pub use lockfile::LockfileTask;
+pub use pipeline::PipelineStepTask;
+pub use file_transaction::FileTransaction;
+pub use module_error::ModuleTaskError;
+pub use task_error::TaskError;
+pub use recipe::{Recipe, RecipeTask};
+pub use fetch::FetchTask;
 
 /// Context provided to tasks during execution
 #[derive(Clone)]
@@ -30,10 +47,73 @@ pub struct TaskContext {
     pub project_id: String,
     /// The project directory path
     pub project_dir: Arc<Path>,
+    /// Scratch directory tasks that write project files should use instead
+    /// of `project_dir` while the pipeline is running. `execute_tasks`
+    /// allocates this as a `tempfile::TempDir` per run and only moves its
+    /// contents into `project_dir` once every task has succeeded, so a
+    /// mid-pipeline failure leaves nothing behind at the final path.
+    pub staging_dir: Arc<Path>,
     /// The Tauri application handle for event emission
     pub app_handle: AppHandle,
     /// The project configuration
     pub config: Arc<crate::commands::project::ProjectConfig>,
+    /// Whether a task should roll back its file operations (via
+    /// `FileTransaction`) when it fails partway through, instead of leaving
+    /// the project in a half-modified state.
+    pub rollback_on_failure: bool,
+    /// This task's direct dependencies' outputs, keyed by task id --
+    /// snapshotted by `TaskExecutor::execute_all` the moment the task is
+    /// scheduled, since every dependency has finished by then. Empty for a
+    /// task run outside `execute_all` (e.g. built ad hoc just to read its
+    /// `Capabilities`), or for a dependency that returned no output.
+    pub dependency_outputs: HashMap<String, TaskOutput>,
+}
+
+impl TaskContext {
+    /// A progress reporter scoped to `task_id`, emitting structured
+    /// `task-progress` events on this context's `app_handle` -- see
+    /// `crate::progress::ProgressReporter`.
+    pub fn progress_reporter(&self, task_id: impl Into<String>) -> crate::progress::ProgressReporter {
+        crate::progress::ProgressReporter::new(task_id, self.app_handle.clone())
+    }
+
+    /// This task's dependencies' outputs, in declaration order -- e.g. the
+    /// `base_dir` a `FrameworkTask` scaffolded into, read here instead of
+    /// re-deriving it from `config`. Dependencies with no
+    /// recorded output (never ran under `execute_all`, or returned
+    /// `TaskOutput::default()`) are skipped rather than represented as empty.
+    pub fn dependency_outputs(&self, dependencies: &[String]) -> Vec<(&str, &TaskOutput)> {
+        dependencies
+            .iter()
+            .filter_map(|id| self.dependency_outputs.get(id).map(|output| (id.as_str(), output)))
+            .collect()
+    }
+}
+
+/// Keyed data a task hands to the tasks that depend on it -- e.g. the
+/// `framework_id`/`base_dir` `FrameworkTask` set up, or the path `FetchTask`
+/// downloaded its artifact to -- so a downstream task can read it by key
+/// (see `TaskContext::dependency_outputs`) instead of re-deriving the same
+/// information from `ProjectConfig`. Defaults to empty for a task with
+/// nothing to hand downstream.
+#[derive(Debug, Clone, Default)]
+pub struct TaskOutput(HashMap<String, serde_json::Value>);
+
+impl TaskOutput {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set `key` to `value`, returning `self` for chaining at the `execute`
+    /// call site: `Ok(TaskOutput::new().with("package_manager", pm.name()))`.
+    pub fn with(mut self, key: impl Into<String>, value: impl Into<serde_json::Value>) -> Self {
+        self.0.insert(key.into(), value.into());
+        self
+    }
+
+    pub fn get(&self, key: &str) -> Option<&serde_json::Value> {
+        self.0.get(key)
+    }
 }
 
 /// Represents the result of a task execution
@@ -45,6 +125,75 @@ pub struct TaskResult {
     pub success: bool,
     /// A message describing the result
     pub message: String,
+    /// Whether a failure here was the kind `TaskExecutor` retries (a
+    /// transient error). Always `true` on success. `false` means the
+    /// failure was either classified permanent (see
+    /// `Task::is_retryable_error`) or retries were already exhausted.
+    pub retryable: bool,
+    /// Whether this task was skipped instead of executed. Nothing sets this
+    /// to `true` today -- see `TaskState::Skipped` -- but it's kept distinct
+    /// from a normal completion for whatever does in the future.
+    pub skipped: bool,
+    /// Data this task handed to whatever depends on it.
+    pub output: TaskOutput,
+}
+
+/// Whether `execute_all` needed to compensate for a failed run by rolling
+/// back the tasks that had already completed, and how that went. The UI can
+/// use this to tell a user "nothing was written" apart from "some debris may
+/// be left behind" instead of a single undifferentiated failure.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RollbackOutcome {
+    /// Every task succeeded; no rollback was attempted.
+    NotNeeded,
+    /// A task failed terminally, and every completed task's `Task::rollback`
+    /// ran without error.
+    RolledBack,
+    /// A task failed terminally, and at least one completed task's
+    /// `Task::rollback` itself returned an error -- their IDs, in the order
+    /// rollback was attempted (newest-completed first).
+    Incomplete(Vec<String>),
+}
+
+/// What a completed `execute_all` run produced: every task's result, plus
+/// whether a failure partway through required (and managed) a compensating
+/// rollback of the tasks that had already succeeded.
+#[derive(Debug, Clone)]
+pub struct ExecutionReport {
+    pub results: Vec<TaskResult>,
+    pub rollback: RollbackOutcome,
+}
+
+/// Default number of attempts (including the first) a retryable task
+/// failure gets before it's treated as permanent. Matches
+/// `GenerationTask`'s default when a task doesn't override `max_attempts`.
+pub const DEFAULT_MAX_TASK_ATTEMPTS: u32 = 3;
+
+/// Backoff schedule for retrying a task after a transient failure: delay
+/// doubles with each attempt, capped at `max_delay_ms`. Mirrors the
+/// supervised-command backoff in `node_commands::run_supervised_command`.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    pub base_delay_ms: u64,
+    pub max_delay_ms: u64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            base_delay_ms: 500,
+            max_delay_ms: 30_000,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Delay before the given attempt (1-indexed, the attempt about to run):
+    /// `base_delay_ms * 2^(attempt - 1)`, capped at `max_delay_ms`.
+    pub fn delay_for(&self, attempt: u32) -> u64 {
+        let factor = 2u64.saturating_pow(attempt.saturating_sub(1));
+        self.base_delay_ms.saturating_mul(factor).min(self.max_delay_ms)
+    }
 }
 
 /// The state of a task
@@ -56,10 +205,58 @@ pub enum TaskState {
     Running,
     /// The task completed successfully
     Completed,
+    /// The task was skipped rather than executed. Nothing in `execute_all`
+    /// produces this today (see `TaskResult::skipped`), but it's still a
+    /// distinct state from `Completed` for whatever does in the future, and
+    /// is already treated the same as `Completed` for dependency
+    /// satisfaction and checkpointing.
+    Skipped,
     /// The task failed
     Failed(String),
 }
 
+/// What a task can tell callers about itself for a given framework, before
+/// any project configuration exists to run it against -- so the frontend
+/// can gray out irrelevant options and validate a config up front instead
+/// of hardcoding per-framework assumptions. Mirrors the capability sets a
+/// client queries from a remote-operation API before issuing an operation.
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct Capabilities {
+    /// Whether this framework has an enforced directory structure at all.
+    pub enforces_directory_structure: bool,
+    /// The directories that structure enforces, if any.
+    pub enforced_directories: Vec<String>,
+    /// Whether this task needs `ProjectConfig::setup_command` set to do
+    /// anything.
+    pub requires_setup_command: bool,
+    /// Whether this task runs its command under a pseudo-terminal (see
+    /// `crate::process::ProcessRunner`).
+    pub supports_pty: bool,
+    /// Whether this task's command already creates the final project
+    /// directory itself (e.g. `create-next-app <name>`), so callers
+    /// shouldn't pre-create it.
+    pub creates_own_project_dir: bool,
+}
+
+impl Capabilities {
+    /// Combine this task's capability set with another's: boolean flags OR
+    /// together, and enforced directory lists are merged without
+    /// duplicates. Used to aggregate capabilities across every task type
+    /// that runs for a framework into one set for the frontend.
+    pub fn merge(mut self, other: Capabilities) -> Capabilities {
+        self.enforces_directory_structure |= other.enforces_directory_structure;
+        self.requires_setup_command |= other.requires_setup_command;
+        self.supports_pty |= other.supports_pty;
+        self.creates_own_project_dir |= other.creates_own_project_dir;
+        for dir in other.enforced_directories {
+            if !self.enforced_directories.contains(&dir) {
+                self.enforced_directories.push(dir);
+            }
+        }
+        self
+    }
+}
+
 /// A task that can be executed during project generation
 #[async_trait]
 pub trait Task: Send + Sync {
@@ -72,8 +269,65 @@ pub trait Task: Send + Sync {
     /// IDs of tasks that must complete before this task can run
     fn dependencies(&self) -> &[String];
     
-    /// Execute the task
-    async fn execute(&self, context: &TaskContext) -> Result<(), String>;
+    /// Execute the task, returning data downstream tasks can read via
+    /// `TaskContext::dependency_outputs` -- `TaskOutput::default()` for a
+    /// task with nothing to hand off.
+    async fn execute(&self, context: &TaskContext) -> Result<TaskOutput, String>;
+
+    /// Attempts (including the first) allowed before a retryable failure
+    /// becomes permanent. Defaults to `DEFAULT_MAX_TASK_ATTEMPTS`; override
+    /// for a task that's unsafe or pointless to retry more than once.
+    fn max_attempts(&self) -> u32 {
+        DEFAULT_MAX_TASK_ATTEMPTS
+    }
+
+    /// Backoff schedule between retries of this task. Defaults to
+    /// `RetryPolicy::default()`; override for a task whose failures need a
+    /// different delay than the rest of the graph (e.g. a `FetchTask` hitting
+    /// a rate-limited host might want a longer base delay than a local file
+    /// operation).
+    fn retry_policy(&self) -> RetryPolicy {
+        RetryPolicy::default()
+    }
+
+    /// Whether a failure with this message is worth retrying. Defaults to
+    /// `true` (most failures here are transient: a flaky install, a locked
+    /// file); override to short-circuit retries for errors retrying can't
+    /// fix, like a missing module ID or invalid configuration.
+    fn is_retryable_error(&self, _error: &str) -> bool {
+        true
+    }
+
+    /// Undo this task's filesystem side effects. Called by `execute_all`,
+    /// newest-first, on every task that completed when a later task in the
+    /// same run fails terminally -- see `RollbackOutcome`. Defaults to a
+    /// no-op, which is correct for a task whose writes either land in the
+    /// run's staging directory (dropped entirely on failure, see
+    /// `TaskContext::staging_dir`) or aren't meaningfully reversible
+    /// (`PipelineStepTask`'s arbitrary shell command, `FetchTask`'s
+    /// content-addressed cache, which other runs may still be relying on).
+    async fn rollback(&self, _context: &TaskContext) -> Result<(), String> {
+        Ok(())
+    }
+
+    /// The parts of `TaskContext` that affect this task's output, as stable
+    /// strings. Folded into the task's cache key alongside the hashes of its
+    /// dependencies (see `cache::hash_task`), recorded as a record of what
+    /// ran with which inputs. Defaults to the whole project config, which is
+    /// always correct but invalidates more eagerly than a narrower override
+    /// needs to; override for a task whose output only depends on a slice
+    /// of it.
+    fn cache_inputs(&self, context: &TaskContext) -> Vec<String> {
+        vec![serde_json::to_string(&*context.config).unwrap_or_default()]
+    }
+
+    /// What this task can do for the given framework, before any project
+    /// configuration exists to run it against -- see `Capabilities`.
+    /// Defaults to the empty set; override for a task whose behavior
+    /// actually depends on the framework's config.
+    fn capabilities(&self, _framework: &Framework) -> Capabilities {
+        Capabilities::default()
+    }
 }
 
 /// A factory function for creating tasks
@@ -125,318 +379,357 @@ impl TaskRegistry {
         self.register("cleanup", |context| {
             Box::new(CleanupTask::new(context))
         });
+
+        self.register("lockfile", |_context| {
+            Box::new(LockfileTask::new())
+        });
     }
 }
 
+/// Default number of tasks `TaskExecutor::execute_all` will run at once.
+/// Override per project via `ProjectConfig::max_parallel_tasks`.
+pub const DEFAULT_MAX_PARALLEL_TASKS: usize = 4;
+
 /// Task executor that manages task execution with dependencies
 pub struct TaskExecutor {
     context: TaskContext,
-    tasks: HashMap<String, Box<dyn Task>>,
+    /// `Arc` rather than `Box` so a task can be cloned into an owned,
+    /// `'static` future handed to `tokio::task::JoinSet::spawn` --
+    /// `execute_all` spawns each task as an independent tokio task instead
+    /// of holding a borrow of `self` for the duration of its execution.
+    tasks: HashMap<String, Arc<dyn Task>>,
     state: Arc<Mutex<HashMap<String, TaskState>>>,
+    app_state: Arc<crate::state::AppState>,
+    /// Cache keys computed this run, keyed by task ID. Filled in as each
+    /// task is hashed, before it executes, so dependents can roll their
+    /// dependencies' fresh hashes into their own key -- see `cache::hash_task`.
+    computed_hashes: Arc<Mutex<HashMap<String, String>>>,
+    /// This run's background worker handle, for reporting progress and
+    /// checking pause/cancel between batches (see `worker::WorkerHandle`).
+    worker: Arc<WorkerHandle>,
+    /// Maximum number of tasks `execute_all` runs concurrently.
+    max_concurrency: usize,
 }
 
 impl TaskExecutor {
     /// Create a new task executor
-    pub fn new(context: TaskContext, tasks: Vec<Box<dyn Task>>) -> Self {
+    pub fn new(context: TaskContext, tasks: Vec<Box<dyn Task>>, app_state: Arc<crate::state::AppState>, worker: Arc<WorkerHandle>) -> Self {
         let mut task_map = HashMap::new();
         let mut state_map = HashMap::new();
-        
+
         for task in tasks {
             let id = task.id().to_string();
             state_map.insert(id.clone(), TaskState::Pending);
-            task_map.insert(id, task);
+            task_map.insert(id, Arc::from(task));
         }
-        
+
         Self {
             context,
             tasks: task_map,
             state: Arc::new(Mutex::new(state_map)),
+            app_state,
+            computed_hashes: Arc::new(Mutex::new(HashMap::new())),
+            worker,
+            max_concurrency: DEFAULT_MAX_PARALLEL_TASKS,
         }
     }
-    
-    /// Execute all tasks, respecting dependencies
-    pub async fn execute_all(&self) -> Result<Vec<TaskResult>, String> {
-        use futures::future::join_all;
+
+    /// Override how many tasks `execute_all` runs concurrently. See
+    /// `ProjectConfig::max_parallel_tasks`.
+    pub fn with_max_concurrency(mut self, limit: usize) -> Self {
+        self.max_concurrency = limit.max(1);
+        self
+    }
+
+    /// Execute all tasks, respecting dependencies. Validates the dependency
+    /// graph with Kahn's algorithm up front (returning a descriptive `Err`
+    /// naming every task still stuck in a cycle instead of stalling at
+    /// runtime), then streams tasks through a `JoinSet` as they become
+    /// runnable: each completion decrements its dependents' in-degree and
+    /// spawns any that reach zero immediately, so an unrelated slow task
+    /// never delays the rest of the graph. If a task fails terminally, every
+    /// task that had already completed is rolled back, newest first -- see
+    /// `ExecutionReport::rollback`.
+    pub async fn execute_all(&self) -> Result<ExecutionReport, String> {
         use tokio::sync::Semaphore;
-        use log::{debug, error, info, warn};
-        
+        use tokio::task::JoinSet;
+        use log::{debug, info, warn};
+
+        let semaphore = Arc::new(Semaphore::new(self.max_concurrency));
+
+        let total_tasks = self.tasks.len();
         let mut results = Vec::new();
-        let max_concurrent_tasks = 4; // Configurable
-        let semaphore = Arc::new(Semaphore::new(max_concurrent_tasks));
-        
-        // Find all root tasks (no dependencies)
-        let mut ready_tasks = self.find_ready_tasks().await;
-        info!("Initial ready tasks: {:?}", ready_tasks);
-        
-        let mut pending_tasks: HashMap<String, Vec<String>> = HashMap::new();
-        
-        // Build dependency map for non-ready tasks
+        // A dependency counts as satisfied once it's in here. `resume_generation`
+        // pre-filters already-completed tasks out of `self.tasks` entirely, so a
+        // dependency that was completed in a prior run never appears as a key of
+        // `self.tasks` either -- the in-degree count below treats that absence
+        // as satisfied too.
+        let mut completed: HashMap<String, TaskResult> = HashMap::new();
+        // Completed task IDs in finish order, so a compensating rollback
+        // after a terminal failure can walk them newest-first.
+        let mut completed_order: Vec<String> = Vec::new();
+        let mut join_set: JoinSet<TaskResult> = JoinSet::new();
+        let mut cancelled = false;
+
+        // Build the reverse-dependency map and an in-degree count per task up
+        // front, instead of rescanning every task's full dependency list on
+        // every completion to work out what just became runnable.
+        let mut in_degree: HashMap<String, usize> = self.tasks.keys().map(|id| (id.clone(), 0)).collect();
+        let mut dependents: HashMap<String, Vec<String>> = HashMap::new();
         for (id, task) in &self.tasks {
-            if !ready_tasks.contains(id) {
-                for dep in task.dependencies() {
-                    pending_tasks
-                        .entry(dep.to_string())
-                        .or_default()
-                        .push(id.clone());
+            for dep in task.dependencies() {
+                if self.tasks.contains_key(dep) {
+                    *in_degree.get_mut(id).unwrap() += 1;
+                    dependents.entry(dep.clone()).or_default().push(id.clone());
                 }
             }
         }
-        
-        info!("Dependency map: {:?}", pending_tasks);
-        
-        // Process tasks until all are complete or we can't make progress
-        let mut iteration_count = 0;
-        let mut completed_tasks = HashSet::new();
-        let mut failed_tasks = HashSet::new(); // Track failed tasks to prevent infinite retries
-        
-        while !ready_tasks.is_empty() {
-            iteration_count += 1;
-            info!("Starting iteration {} with {} ready tasks", iteration_count, ready_tasks.len());
-            debug!("Ready tasks: {:?}", ready_tasks);
-            
-            // Check for maximum iterations to prevent infinite loops
-            if iteration_count > 10 {
-                warn!("Reached maximum iterations (10), breaking to prevent infinite loop");
-                break;
+
+        // Validate the graph with Kahn's algorithm before spawning anything:
+        // repeatedly peel off zero-in-degree tasks, and if any remain once
+        // that stalls, they're all part of (or depend on) a cycle.
+        {
+            let mut remaining = in_degree.clone();
+            let mut frontier: Vec<String> = remaining.iter().filter(|(_, &d)| d == 0).map(|(id, _)| id.clone()).collect();
+            let mut resolved = 0usize;
+            while let Some(id) = frontier.pop() {
+                resolved += 1;
+                if let Some(deps) = dependents.get(&id) {
+                    for dependent in deps {
+                        let degree = remaining.get_mut(dependent).unwrap();
+                        *degree -= 1;
+                        if *degree == 0 {
+                            frontier.push(dependent.clone());
+                        }
+                    }
+                }
             }
-            
-            // Filter out previously failed tasks
-            ready_tasks.retain(|task_id| !failed_tasks.contains(task_id));
-            
-            if ready_tasks.is_empty() {
-                info!("No viable ready tasks remain after filtering out failed tasks");
-                break;
+            if resolved != self.tasks.len() {
+                let mut stuck: Vec<String> = remaining.iter().filter(|(_, &d)| d > 0).map(|(id, _)| id.clone()).collect();
+                stuck.sort();
+                return Err(format!("Dependency cycle detected among tasks: {:?}", stuck));
             }
-            
-            let task_futures = ready_tasks
-                .clone()
-                .into_iter()
-                .map(|id| {
-                    let task = self.tasks.get(&id).unwrap();
-                    let sem_permit = semaphore.clone().acquire_owned();
-                    let task_id = id.clone();
-                    let state = self.state.clone();
-                    let context = &self.context;
-                    
-                    async move {
-                        let _permit = sem_permit.await.unwrap();
-                        
+        }
+
+        // Seed the runnable queue with every zero-in-degree task; each
+        // completion below pushes its dependents onto it the instant their
+        // last dependency finishes, instead of at the next scan boundary.
+        let mut runnable_queue: Vec<String> = in_degree.iter().filter(|(_, &d)| d == 0).map(|(id, _)| id.clone()).collect();
+
+        loop {
+            if !cancelled {
+                // Natural checkpoint for the worker's control channel: wait out
+                // a pause here rather than mid-task, and stop spawning further
+                // tasks once cancelled (already-spawned tasks still finish, but
+                // no new ones start).
+                self.worker.wait_while_paused().await;
+                if self.worker.is_cancelled() {
+                    info!("Worker for project {} was cancelled, draining in-flight tasks and stopping", self.context.project_id);
+                    cancelled = true;
+                }
+            }
+
+            let runnable: Vec<String> = if cancelled {
+                Vec::new()
+            } else {
+                std::mem::take(&mut runnable_queue)
+            };
+
+            if !runnable.is_empty() {
+                debug!("Spawning {} newly runnable task(s): {:?}", runnable.len(), runnable);
+                self.worker.mark_task(Some(runnable.join(", "))).await;
+            }
+
+            for task_id in runnable {
+                let task = self.tasks.get(&task_id).unwrap().clone();
+                let sem_permit = semaphore.clone().acquire_owned();
+                let state = self.state.clone();
+                let mut context = self.context.clone();
+                // Every dependency is already in `completed` by the time a
+                // task is runnable, so its output is ready to snapshot here.
+                context.dependency_outputs = task
+                    .dependencies()
+                    .iter()
+                    .filter_map(|dep| completed.get(dep).map(|r| (dep.clone(), r.output.clone())))
+                    .collect();
+                let app_state = self.app_state.clone();
+                let retry_policy = task.retry_policy();
+                let max_attempts = task.max_attempts().max(1);
+                let computed_hashes = self.computed_hashes.clone();
+
+                join_set.spawn(async move {
+                    let _permit = sem_permit.await.unwrap();
+
+                    // Every dependency of this task is already in `completed`
+                    // (or was never part of this run), so its hash is already
+                    // in `computed_hashes` by now. Recorded on success below
+                    // (see `cache::store_cached_output`) for whatever reads
+                    // it later, but never used to skip `execute` outright --
+                    // every task here writes into this run's own fresh
+                    // `staging_dir` (see `TaskContext::staging_dir`), so
+                    // skipping `execute` on a hash match would leave that
+                    // directory missing this task's output even though it's
+                    // reported `Completed`.
+                    let hash = {
+                        let hashes = computed_hashes.lock().await;
+                        cache::hash_task(task.as_ref(), &context, &hashes)
+                    };
+
+                    let mut attempt: u32 = 1;
+                    loop {
                         // Update state to running
                         {
                             let mut state_map = state.lock().await;
                             state_map.insert(task_id.clone(), TaskState::Running);
-                            info!("Set task {} state to Running", task_id);
+                            info!("Set task {} state to Running (attempt {}/{})", task_id, attempt, max_attempts);
                         }
-                        
+
                         // Execute the task
-                        info!("Executing task: {}", task_id);
-                        let result = task.execute(context).await;
-                        
-                        // Update state based on result
-                        let (new_state, message, success) = match result {
-                            Ok(()) => (TaskState::Completed, format!("Task {} completed successfully", task.name()), true),
-                            Err(e) => (TaskState::Failed(e.clone()), e, false),
+                        info!("Executing task: {} (attempt {}/{})", task_id, attempt, max_attempts);
+                        let result = task.execute(&context).await;
+
+                        let (new_state, message, success, retryable, output) = match result {
+                            Ok(output) => (TaskState::Completed, format!("Task {} completed successfully", task.name()), true, true, output),
+                            Err(e) => {
+                                let retryable = task.is_retryable_error(&e);
+                                (TaskState::Failed(e.clone()), e, false, retryable, TaskOutput::default())
+                            }
                         };
-                        
+
+                        if !success && retryable && attempt < max_attempts {
+                            let next_attempt = attempt + 1;
+                            let delay_ms = retry_policy.delay_for(next_attempt);
+                            warn!(
+                                "Task {} failed on attempt {}/{}: {}. Retrying in {}ms",
+                                task_id, attempt, max_attempts, message, delay_ms
+                            );
+                            app_state.mark_task_retrying(&context.project_id, &task_id, next_attempt, delay_ms).await;
+                            tokio::time::sleep(tokio::time::Duration::from_millis(delay_ms)).await;
+                            attempt = next_attempt;
+                            continue;
+                        }
+
                         info!("Task {} finished with state: {:?}", task_id, new_state);
-                        
+
                         {
                             let mut state_map = state.lock().await;
                             state_map.insert(task_id.clone(), new_state.clone());
                             debug!("Updated task state in state map");
                         }
-                        
-                        TaskResult {
+
+                        if success {
+                            computed_hashes.lock().await.insert(task_id.clone(), hash.clone());
+                            if let Err(e) = cache::store_cached_output(&app_state, &hash, &message) {
+                                warn!("Failed to persist task cache entry for {}: {}", task_id, e);
+                            }
+                        }
+
+                        break TaskResult {
                             task_id,
                             success,
                             message,
-                        }
+                            retryable,
+                            skipped: false,
+                            output,
+                        };
                     }
-                })
-                .collect::<Vec<_>>();
-            
-            // Wait for all current tasks to complete
-            let batch_results = join_all(task_futures).await;
-            info!("Completed batch of {} tasks in iteration {}", batch_results.len(), iteration_count);
-            
-            // Mark completed tasks and add to results
-            for result in batch_results {
-                info!("Processing result for task {}: success={}", result.task_id, result.success);
-                if result.success {
-                    completed_tasks.insert(result.task_id.clone());
-                } else {
-                    failed_tasks.insert(result.task_id.clone());
-                    warn!("Task {} failed: {}", result.task_id, result.message);
+                });
+            }
+
+            if join_set.is_empty() {
+                let remaining = total_tasks.saturating_sub(results.len());
+                if remaining > 0 {
+                    // Expected whenever a failed task had dependents: they're
+                    // only unblocked `if result.success` above, so they stay
+                    // permanently stuck at a non-zero in-degree. The cycle
+                    // check up front only rules out a graph that can never
+                    // make progress at all, not this case.
+                    debug!(
+                        "Scheduler stopped for project {} with {} task(s) unreachable, likely downstream of an earlier task failure",
+                        self.context.project_id, remaining
+                    );
                 }
-                results.push(result.clone());
-                
-                // Check if any tasks are unlocked by this completion
-                if let Some(dependents) = pending_tasks.get(&result.task_id) {
-                    info!("Task {} has {} dependents", result.task_id, dependents.len());
-                    
-                    // For each dependent task, check if all its dependencies are now satisfied
-                    for dependent_id in dependents {
-                        debug!("Checking if task {} can now be executed", dependent_id);
-                        let dependent_task = self.tasks.get(dependent_id).unwrap();
-                        let deps_satisfied = dependent_task.dependencies().iter().all(|dep| {
-                            completed_tasks.contains(dep)
-                        });
-                        
-                        // If all dependencies are satisfied, add to ready tasks
-                        if deps_satisfied {
-                            info!("All dependencies for task {} are satisfied, adding to ready tasks", dependent_id);
-                            ready_tasks.push(dependent_id.clone());
-                        } else {
-                            debug!("Not all dependencies for task {} are satisfied yet", dependent_id);
+                break;
+            }
+
+            match join_set.join_next().await {
+                Some(Ok(result)) => {
+                    info!("Processing result for task {}: success={}", result.task_id, result.success);
+                    if result.success {
+                        // Unblock every dependent the instant its last
+                        // dependency finishes, rather than waiting for the
+                        // next scan of the whole task set.
+                        if !cancelled {
+                            if let Some(deps) = dependents.get(&result.task_id) {
+                                for dependent in deps {
+                                    if let Some(degree) = in_degree.get_mut(dependent) {
+                                        *degree -= 1;
+                                        if *degree == 0 {
+                                            runnable_queue.push(dependent.clone());
+                                        }
+                                    }
+                                }
+                            }
                         }
+                        completed.insert(result.task_id.clone(), result.clone());
+                        completed_order.push(result.task_id.clone());
+                    } else {
+                        warn!("Task {} failed: {}", result.task_id, result.message);
                     }
+
+                    let completed_count = (results.len() + 1) as u64;
+                    let progress = (completed_count as f64 / total_tasks as f64 * 90.0) as u8 + 5;
+                    self.app_state
+                        .update_progress_with_counts(&self.context.project_id, &result.message, progress, Some((completed_count, total_tasks as u64)))
+                        .await;
+                    results.push(result);
                 }
+                Some(Err(join_err)) => return Err(format!("Task execution panicked: {}", join_err)),
+                None => unreachable!("join_set.is_empty() was checked above"),
             }
-            
-            // Check if we can make progress
-            if ready_tasks.is_empty() && !self.can_make_progress().await {
-                break;
-            }
-            
-            // Remove already processed tasks from ready tasks to avoid duplicates
-            ready_tasks.retain(|id| !completed_tasks.contains(id));
-            debug!("After filtering, {} ready tasks remain", ready_tasks.len());
         }
-        
+
+        self.worker.mark_task(None).await;
+
         info!("Task execution completed with {} results", results.len());
-        Ok(results)
-    }
-    
-    /// Find tasks that are ready to be executed (all dependencies are satisfied)
-    async fn find_ready_tasks(&self) -> Vec<String> {
-        use log::{debug, info};
-        
-        // Get the state map to check task statuses
-        let state_map = self.state.lock().await;
-        let mut ready_tasks = Vec::new();
-        
-        // Check each task
-        for (id, task) in &self.tasks {
-            debug!("Checking if task {} is ready", id);
-            
-            // Skip tasks that are already completed or failed
-            if let Some(state) = state_map.get(id) {
-                match state {
-                    TaskState::Completed => {
-                        debug!("Task {} is already completed, skipping", id);
-                        continue;
-                    },
-                    TaskState::Failed(_) => {
-                        debug!("Task {} has failed, skipping", id);
-                        continue;
-                    },
-                    TaskState::Running => {
-                        debug!("Task {} is currently running, skipping", id);
-                        continue;
-                    },
-                    TaskState::Pending => {
-                        debug!("Task {} is pending", id);
-                        // Continue checking dependencies
-                    },
-                }
-            }
-            
-            // Check dependencies
-            let mut all_deps_satisfied = true;
-            for dep in task.dependencies() {
-                debug!("Checking dependency {} for task {}", dep, id);
-                
-                // Check if the dependency is satisfied
-                if let Some(dep_state) = state_map.get(dep) {
-                    match dep_state {
-                        TaskState::Completed => {
-                            debug!("Dependency {} is completed", dep);
-                            // This dependency is satisfied
-                        },
-                        _ => {
-                            debug!("Dependency {} is not completed (state: {:?})", dep, dep_state);
-                            all_deps_satisfied = false;
-                            break;
-                        },
+
+        // A terminal task failure leaves its dependents permanently
+        // unreachable (they never reach in-degree zero, so they're never in
+        // `completed`) -- compensate by unwinding whatever did complete.
+        let rollback = if !cancelled && results.iter().any(|r| !r.success) {
+            let mut failed_rollbacks = Vec::new();
+            for task_id in completed_order.iter().rev() {
+                if let Some(task) = self.tasks.get(task_id) {
+                    info!("Rolling back completed task: {}", task_id);
+                    if let Err(e) = task.rollback(&self.context).await {
+                        warn!("Rollback failed for task {}: {}", task_id, e);
+                        failed_rollbacks.push(task_id.clone());
                     }
-                } else {
-                    // Dependency not found in state map
-                    debug!("Dependency {} not found in state map", dep);
-                    all_deps_satisfied = false;
-                    break;
                 }
             }
-            
-            // If all dependencies are satisfied, this task is ready
-            if all_deps_satisfied {
-                info!("Task {} is ready for execution (all dependencies satisfied)", id);
-                ready_tasks.push(id.clone());
+            if failed_rollbacks.is_empty() {
+                RollbackOutcome::RolledBack
             } else {
-                debug!("Task {} is not ready (some dependencies not satisfied)", id);
+                RollbackOutcome::Incomplete(failed_rollbacks)
             }
-        }
-        
-        info!("Found {} ready tasks", ready_tasks.len());
-        ready_tasks
-    }
-    
-    /// Check if execution can continue or if we're stuck
-    async fn can_make_progress(&self) -> bool {
-        let state_map = self.state.lock().await;
-        
-        // Check if any task is ready to execute
-        let ready_tasks = self.find_ready_tasks().await;
-        if !ready_tasks.is_empty() {
-            return true;
-        }
-        
-        // Check if any task is currently running
-        if state_map.values().any(|state| matches!(state, TaskState::Running)) {
-            return true;
-        }
-        
-        // Check if all tasks are either completed or failed
-        let all_tasks_processed = state_map.values().all(|state| {
-            matches!(state, TaskState::Completed) || matches!(state, TaskState::Failed(_))
-        });
-        
-        if all_tasks_processed {
-            // If all tasks are processed, we're done (no progress needed)
-            return false;
-        }
-        
-        // Check if we have pending tasks with failed dependencies
-        let has_failed_dependencies = self.tasks.iter()
-            .any(|(id, task)| {
-                matches!(state_map.get(id), Some(TaskState::Pending)) && 
-                task.dependencies().iter().any(|dep| {
-                    matches!(state_map.get(dep), Some(TaskState::Failed(_)))
-                })
-            });
-            
-        if has_failed_dependencies {
-            // If we have tasks that can't run because of failed dependencies,
-            // that's not a cycle - it's a legitimate execution failure
-            return false;
-        }
-        
-        // If we have pending tasks but no ready tasks and no failed dependencies,
-        // we might have a cycle - can't make progress
-        let has_pending = state_map.values().any(|state| matches!(state, TaskState::Pending));
-        
-        if has_pending {
-            log::warn!("Can't make progress: have pending tasks but no ready tasks and no failed dependencies");
-            return false; // This suggests a circular dependency
-        }
-        
-        // Default case - we can't make progress if we reach here
-        false
+        } else {
+            RollbackOutcome::NotNeeded
+        };
+
+        Ok(ExecutionReport { results, rollback })
     }
-    
+
     /// Get the total number of tasks
     pub fn get_task_count(&self) -> usize {
         self.tasks.len()
     }
+
+    /// Cache keys computed this run for every task that ran (see
+    /// `cache::hash_task`), for the caller to persist alongside the
+    /// project's checkpoint.
+    pub async fn computed_hashes(&self) -> HashMap<String, String> {
+        self.computed_hashes.lock().await.clone()
+    }
     
     /// Get the current state of all tasks
     pub async fn get_states(&self) -> HashMap<String, TaskState> {
@@ -1,7 +1,6 @@
 //! Module installation task implementation
 
 use std::fs;
-use std::path::PathBuf;
 use tauri::Emitter;
 
 use async_trait::async_trait;
@@ -9,9 +8,9 @@ use log::{info, warn, debug};
 use tokio::time::{sleep, Duration};
 
 use crate::commands::framework::get_modules;
-use crate::commands::file::modify_file;
+use crate::commands::transform::apply_transform;
 use crate::commands::node_commands::execute_node_command;
-use super::{Task, TaskContext};
+use super::{Task, TaskContext, TaskOutput, ModuleTaskError};
 
 /// Task for installing a module
 pub struct ModuleTask {
@@ -76,15 +75,76 @@ impl Task for ModuleTask {
         &self.dependencies
     }
     
-    async fn execute(&self, context: &TaskContext) -> Result<(), String> {
+    async fn execute(&self, context: &TaskContext) -> Result<TaskOutput, String> {
+        self.run(context).await
+            .map(|_| TaskOutput::new().with("module_id", self.module_id.clone()))
+            .map_err(|e| e.to_string())
+    }
+
+    fn is_retryable_error(&self, error: &str) -> bool {
+        // A missing module ID is a configuration problem, not a transient
+        // one; retrying the same module ID won't make it exist.
+        !error.starts_with("module not found:")
+    }
+
+    /// Undo this module's install the same way an explicit removal would
+    /// (see `commands::module_apply::remove_module`): reverse its recorded
+    /// `AppliedOperation`s and run the package manager's remove command for
+    /// whatever it installed, then drop it from the modules lockfile.
+    async fn rollback(&self, context: &TaskContext) -> Result<(), String> {
+        // This task wrote into `staging_dir`, same as `execute` below, and
+        // rollback always runs before a successful run's staging dir would
+        // be promoted to `project_dir` -- so that's where its effects
+        // (and this lockfile) actually are.
+        let project_dir = context.staging_dir.to_path_buf();
+
+        let mut lockfile = match crate::commands::module_lockfile::ModulesLockfile::load(&project_dir) {
+            Ok(Some(lockfile)) => lockfile,
+            // Nothing was ever recorded as installed (e.g. this task failed
+            // before reaching the lockfile write) -- nothing to undo.
+            Ok(None) => return Ok(()),
+            Err(e) => return Err(e),
+        };
+
+        let all_modules = get_modules().await.map_err(|e| e.to_string())?;
+        crate::commands::module_apply::remove_module(
+            &context.app_handle,
+            &project_dir,
+            &mut lockfile,
+            &all_modules,
+            &self.module_id,
+        ).await
+    }
+
+    fn cache_inputs(&self, context: &TaskContext) -> Vec<String> {
+        // The module itself and which package manager resolves its install
+        // commands are what changes this task's output; framework changes
+        // already roll in through the framework task dependency's hash.
+        vec![
+            self.module_id.clone(),
+            serde_json::to_string(&context.config.package_manager).unwrap_or_default(),
+        ]
+    }
+}
+
+impl ModuleTask {
+    /// Does the actual install work, returning a typed `ModuleTaskError` so
+    /// callers further down (and eventually the UI) can tell a missing
+    /// module apart from a failed command or a denied file write instead of
+    /// matching on a flat string.
+    async fn run(&self, context: &TaskContext) -> Result<(), ModuleTaskError> {
         // Use only the needed context variables
         let app_handle = &context.app_handle;
-        let base_dir = &context.project_dir;
         let config = &context.config;
-        
-        // Create the full project path (base_dir/project_name)
-        let project_dir = base_dir.join(&config.name);
-        
+
+        // Write into the run's staging directory, same as `FrameworkTask`,
+        // rather than straight into `project_dir` -- otherwise a module's
+        // files would land at the real final path even when a later task in
+        // the same run fails, bypassing the staging dir's whole point (see
+        // `TaskContext::staging_dir`), and the eventual promote step would
+        // clobber them when merging the framework scaffold on top.
+        let project_dir = context.staging_dir.to_path_buf();
+
         // Log the actual directory we're working in
         info!("Working directory for module {}: {}", self.module_id, project_dir.display());
         app_handle.emit("log-message", format!("Working in directory: {}", project_dir.display())).unwrap();
@@ -99,7 +159,10 @@ impl Task for ModuleTask {
                 let error_msg = format!("Failed to create project directory: {}", e);
                 warn!("{}", error_msg);
                 app_handle.emit("log-message", &error_msg).unwrap();
-                return Err(error_msg);
+                return Err(ModuleTaskError::FileOp {
+                    path: project_dir.display().to_string(),
+                    reason: error_msg,
+                });
             }
             
             // Create a package.json file if it doesn't exist
@@ -132,10 +195,11 @@ impl Task for ModuleTask {
         }
         
         // Get module details
-        let all_modules = get_modules().await?;
+        let all_modules = get_modules().await
+            .map_err(|_| ModuleTaskError::ModuleNotFound(self.module_id.clone()))?;
         let module = all_modules.iter()
             .find(|m| m.id == self.module_id)
-            .ok_or_else(|| format!("Module not found: {}", self.module_id))?;
+            .ok_or_else(|| ModuleTaskError::ModuleNotFound(self.module_id.clone()))?;
         
         // Log the module installation
         info!("Setting up module: {}", module.name);
@@ -145,8 +209,8 @@ impl Task for ModuleTask {
         let package_json_path = project_dir.join("package.json");
         if !package_json_path.exists() && !module.installation.commands.is_empty() {
             let has_npm_commands = module.installation.commands.iter()
-                .any(|cmd| cmd.starts_with("npm") || cmd.starts_with("npx"));
-                
+                .any(|cmd| !matches!(cmd.package_manager, crate::commands::command_spec::PackageManager::None));
+
             if has_npm_commands {
                 info!("Creating package.json before npm operations");
                 app_handle.emit("log-message", "Creating package.json before npm operations").unwrap();
@@ -171,70 +235,113 @@ impl Task for ModuleTask {
             }
         }
         
+        // Resolve the package-manager backend once for this module, so every
+        // command below is translated into whichever manager the project is
+        // actually configured for (or auto-detected from its lockfile).
+        let backend = crate::commands::package_manager::resolve(
+            &project_dir,
+            config.package_manager.as_ref(),
+        );
+
         // Process each command
         for (i, cmd) in module.installation.commands.iter().enumerate() {
             // Update progress
             let progress_msg = format!("Running command {}/{}", i+1, module.installation.commands.len());
             info!("{}", progress_msg);
             app_handle.emit("task-progress", progress_msg).unwrap();
-            
+
+            let command_str = cmd.to_command_string_for(backend.as_ref());
+            let command_dir = match &cmd.cwd_relative {
+                Some(relative) => project_dir.join(relative),
+                None => project_dir.clone(),
+            };
+
+            // Skip installs whose requested packages are already satisfied,
+            // so re-running a module install is fast and idempotent.
+            let requested_packages = cmd.requested_packages();
+            if !requested_packages.is_empty() {
+                let inventory = crate::commands::package_inventory::PackageInventory::read(&command_dir);
+                if requested_packages.iter().all(|pkg| inventory.is_satisfied(pkg)) {
+                    let msg = format!("Skipping '{}': already satisfied", command_str);
+                    info!("{}", msg);
+                    app_handle.emit("log-message", &msg).unwrap();
+                    continue;
+                }
+            }
+
             // Execute the command using the new API
-            let command_log = format!("Executing: {}", cmd);
+            let command_log = format!("Executing: {}", command_str);
             app_handle.emit("log-message", &command_log).unwrap();
-            
+
+            let options = if cmd.env.is_empty() {
+                None
+            } else {
+                Some(crate::commands::node_commands::NodeCommandOptions {
+                    env_vars: Some(cmd.env.clone()),
+                    ..Default::default()
+                })
+            };
+
             let command_result = execute_node_command(
                 app_handle,
-                &project_dir,
-                cmd,
-                None
+                &command_dir,
+                &command_str,
+                options
             ).await;
-                
-            match command_result {
-                Ok(result) => {
-                    if !result.success {
-                        let error_msg = format!("Command failed: {}", result.stderr);
-                        warn!("{}", error_msg);
-                        app_handle.emit("log-message", &error_msg).unwrap();
-                        
-                        // If this is a critical npm/npx command, warn but continue
-                        if cmd.contains("npm install") || cmd.contains("npx") || cmd.contains("npm i") {
-                            let warning = "Critical command failed, but continuing with file operations";
-                            warn!("{}", warning);
-                            app_handle.emit("log-message", warning).unwrap();
-                        }
-                    } else {
-                        let success_msg = "Command completed successfully";
-                        debug!("{}", success_msg);
-                        app_handle.emit("log-message", success_msg).unwrap();
-                    }
+
+            let failure = match command_result {
+                Ok(result) if result.success => {
+                    let success_msg = "Command completed successfully";
+                    debug!("{}", success_msg);
+                    app_handle.emit("log-message", success_msg).unwrap();
+                    None
                 },
-                Err(e) => {
-                    let error_msg = format!("Command execution error: {}", e);
-                    warn!("{}", error_msg);
-                    app_handle.emit("log-message", &error_msg).unwrap();
-                    
-                    // If this is a critical npm/npx command, warn but continue
-                    if cmd.contains("npm install") || cmd.contains("npx") || cmd.contains("npm i") {
-                        let warning = "Critical command failed, but continuing with file operations";
-                        warn!("{}", warning);
-                        app_handle.emit("log-message", warning).unwrap();
-                    }
+                Ok(result) => Some((result.stderr, result.exit_code)),
+                Err(e) => Some((e, -1)),
+            };
+
+            if let Some((stderr, exit_code)) = failure {
+                let error_msg = format!("Command failed: {}", stderr);
+                warn!("{}", error_msg);
+                app_handle.emit("log-message", &error_msg).unwrap();
+
+                if cmd.critical && !cmd.allow_failure {
+                    let critical_msg = format!("Critical command '{}' failed, aborting module install", command_str);
+                    warn!("{}", critical_msg);
+                    app_handle.emit("log-message", &critical_msg).unwrap();
+                    return Err(ModuleTaskError::CommandFailed {
+                        cmd: command_str.clone(),
+                        stderr,
+                        exit_code,
+                    });
+                } else if cmd.critical {
+                    let warning = "Critical command failed, but continuing with file operations";
+                    warn!("{}", warning);
+                    app_handle.emit("log-message", warning).unwrap();
                 }
             }
-            
+
             // Add a delay between commands to ensure file system consistency
             sleep(Duration::from_millis(500)).await;
         }
         
-        // Process file operations
+        // Process file operations, journaling each touched file so a fatal
+        // failure can be rolled back instead of leaving a half-modified project.
+        let mut transaction = crate::tasks::FileTransaction::new();
+
+        // Mirrors `transaction`'s journal, but persisted into the modules
+        // lockfile below so a module removed long after this run can still
+        // reverse the operations it applied (see `ModulesLockfile::take_installed`).
+        let mut applied_ops: Vec<crate::commands::module_lockfile::AppliedOperation> = Vec::new();
+
         for (i, op) in module.installation.file_operations.iter().enumerate() {
             // Update progress
             let progress_msg = format!("Applying file operation {}/{}", i+1, module.installation.file_operations.len());
             info!("{}", progress_msg);
             app_handle.emit("task-progress", progress_msg).unwrap();
-            
+
             let file_path = project_dir.join(&op.path);
-            
+
             // Ensure parent directory exists
             if let Some(parent) = file_path.parent() {
                 if !parent.exists() {
@@ -246,62 +353,166 @@ impl Task for ModuleTask {
                     }
                 }
             }
-            
+
             // Handle different operation types
-            match op.operation.as_str() {
+            let op_result: Result<(), String> = match op.operation.as_str() {
                 "create" => {
                     // Create a new file
+                    transaction.record(&file_path);
+                    applied_ops.push(crate::commands::module_lockfile::AppliedOperation {
+                        path: op.path.clone(),
+                        prior_content: fs::read_to_string(&file_path).ok(),
+                    });
                     let content = op.content.as_str();
-                    if let Err(e) = fs::write(&file_path, content) {
-                        let error_msg = format!("Failed to create file '{}': {}", op.path, e);
-                        warn!("{}", error_msg);
-                        app_handle.emit("log-message", &error_msg).unwrap();
-                    } else {
-                        let success_msg = format!("Created file: {}", op.path);
-                        debug!("{}", success_msg);
-                        app_handle.emit("log-message", &success_msg).unwrap();
-                    }
+                    fs::write(&file_path, content)
+                        .map_err(|e| format!("Failed to create file '{}': {}", op.path, e))
+                        .map(|_| {
+                            let success_msg = format!("Created file: {}", op.path);
+                            debug!("{}", success_msg);
+                            app_handle.emit("log-message", &success_msg).unwrap();
+                        })
                 },
-                "modify" => {
-                    // Modify an existing file
+                "modify" | "regex" | "literal" => {
+                    // Modify an existing file. "modify" is the original,
+                    // still-supported kind; it now gets real regex semantics
+                    // via the transform engine instead of a literal
+                    // `content.replace`, which silently no-op'd on patterns
+                    // like DaisyUI's `plugins: \[.*\]`.
                     if !file_path.exists() {
                         let warning = format!("Cannot modify non-existent file: {}", op.path);
                         warn!("{}", warning);
                         app_handle.emit("log-message", &warning).unwrap();
                         continue;
                     }
-                    
+
                     // Check if pattern and replacement are available
                     if !op.pattern.is_empty() && !op.replacement.is_empty() {
-                        match modify_file(&file_path, &op.pattern, &op.replacement) {
-                            Ok(_) => {
-                                let success_msg = format!("Modified file: {}", op.path);
-                                debug!("{}", success_msg);
-                                app_handle.emit("log-message", &success_msg).unwrap();
-                            },
-                            Err(e) => {
-                                let error_msg = format!("Failed to modify file '{}': {}", op.path, e);
-                                warn!("{}", error_msg);
-                                app_handle.emit("log-message", &error_msg).unwrap();
-                            }
-                        }
+                        transaction.record(&file_path);
+                        applied_ops.push(crate::commands::module_lockfile::AppliedOperation {
+                            path: op.path.clone(),
+                            prior_content: fs::read_to_string(&file_path).ok(),
+                        });
+                        apply_transform(&file_path, op).map(|result| {
+                            let success_msg = if result.matched {
+                                format!("Modified file: {}", op.path)
+                            } else {
+                                format!("No changes needed in file (pattern not found): {}", op.path)
+                            };
+                            debug!("{}", success_msg);
+                            app_handle.emit("log-message", &success_msg).unwrap();
+                        })
                     } else {
                         let warning = "Missing pattern or replacement for file modification";
                         warn!("{}", warning);
                         app_handle.emit("log-message", warning).unwrap();
+                        Ok(())
+                    }
+                },
+                "modify_import" => {
+                    // Add or remove an import declaration -- AST-aware where
+                    // supported (see `command_runner::modify_import`), so a
+                    // module can toggle an import on or off without the
+                    // pattern/replacement plumbing "modify" needs.
+                    if !file_path.exists() {
+                        let warning = format!("Cannot modify imports in non-existent file: {}", op.path);
+                        warn!("{}", warning);
+                        app_handle.emit("log-message", &warning).unwrap();
+                        continue;
+                    }
+
+                    transaction.record(&file_path);
+                    applied_ops.push(crate::commands::module_lockfile::AppliedOperation {
+                        path: op.path.clone(),
+                        prior_content: fs::read_to_string(&file_path).ok(),
+                    });
+                    apply_transform(&file_path, op).map(|_| {
+                        let success_msg = format!("Modified imports in: {}", op.path);
+                        debug!("{}", success_msg);
+                        app_handle.emit("log-message", &success_msg).unwrap();
+                    })
+                },
+                "json-merge" | "json_merge" => {
+                    // Deep-merge a JSON fragment into the target file, so
+                    // multiple modules can contribute to shared files like
+                    // package.json without clobbering each other's edits.
+                    transaction.record(&file_path);
+                    applied_ops.push(crate::commands::module_lockfile::AppliedOperation {
+                        path: op.path.clone(),
+                        prior_content: fs::read_to_string(&file_path).ok(),
+                    });
+                    apply_transform(&file_path, op)
+                        .map_err(|e| format!("Failed to merge JSON into '{}': {}", op.path, e))
+                        .map(|_| {
+                            let success_msg = format!("Merged JSON into file: {}", op.path);
+                            debug!("{}", success_msg);
+                            app_handle.emit("log-message", &success_msg).unwrap();
+                        })
+                },
+                "insert_after" | "insert_before" => {
+                    if !file_path.exists() {
+                        let warning = format!("Cannot modify non-existent file: {}", op.path);
+                        warn!("{}", warning);
+                        app_handle.emit("log-message", &warning).unwrap();
+                        continue;
                     }
+
+                    transaction.record(&file_path);
+                    applied_ops.push(crate::commands::module_lockfile::AppliedOperation {
+                        path: op.path.clone(),
+                        prior_content: fs::read_to_string(&file_path).ok(),
+                    });
+                    apply_transform(&file_path, op).map(|result| {
+                        let success_msg = if result.matched {
+                            format!("Modified file: {}", op.path)
+                        } else {
+                            format!("No changes needed in file (anchor not found, or already present): {}", op.path)
+                        };
+                        debug!("{}", success_msg);
+                        app_handle.emit("log-message", &success_msg).unwrap();
+                    })
                 },
                 _ => {
                     let warning = format!("Unknown file operation: {}", op.operation);
                     warn!("{}", warning);
                     app_handle.emit("log-message", &warning).unwrap();
+                    Ok(())
+                }
+            };
+
+            if let Err(error_msg) = op_result {
+                warn!("{}", error_msg);
+                app_handle.emit("log-message", &error_msg).unwrap();
+
+                if context.rollback_on_failure {
+                    let rollback_msg = format!("Rolling back {} file operation(s) after failure", i + 1);
+                    warn!("{}", rollback_msg);
+                    app_handle.emit("log-message", &rollback_msg).unwrap();
+                    transaction.rollback();
+                    return Err(ModuleTaskError::FileOp {
+                        path: op.path.clone(),
+                        reason: error_msg,
+                    });
                 }
             }
-            
+
             // Add a delay between file operations to ensure consistency
             sleep(Duration::from_millis(200)).await;
         }
-        
+
+        // Record the install so `add_module_to_project` can later diff an
+        // incremental add against what's really in the project instead of
+        // trusting the caller's selection. A lockfile write failure doesn't
+        // fail the module itself -- the files and commands it exists to
+        // track already succeeded.
+        match crate::commands::module_lockfile::ModulesLockfile::load_or_init(&project_dir, &config.framework) {
+            Ok(mut lockfile) => {
+                if let Err(e) = lockfile.record_installed(&project_dir, &module.id, &module.version, applied_ops) {
+                    warn!("Failed to update modules lockfile for '{}': {}", module.id, e);
+                }
+            }
+            Err(e) => warn!("Failed to load modules lockfile for '{}': {}", module.id, e),
+        }
+
         Ok(())
     }
-} 
\ No newline at end of file
+}
\ No newline at end of file
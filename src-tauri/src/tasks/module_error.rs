@@ -0,0 +1,32 @@
+//! Typed error for module installation tasks.
+//!
+//! `Task::execute` still returns `Result<TaskOutput, String>` (the trait's contract
+//! across every task type), but `ModuleTask` builds up this richer, typed
+//! error internally for its fatal failure paths and converts it to a string
+//! only at that boundary, so the final message is always specific about
+//! what failed (which module, command, or file) instead of being assembled
+//! ad hoc at each call site.
+
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum ModuleTaskError {
+    #[error("module not found: {0}")]
+    ModuleNotFound(String),
+
+    #[error("command '{cmd}' failed (exit code {exit_code}): {stderr}")]
+    CommandFailed {
+        cmd: String,
+        stderr: String,
+        exit_code: i32,
+    },
+
+    #[error("file operation on '{path}' failed: {reason}")]
+    FileOp {
+        path: String,
+        reason: String,
+    },
+
+    #[error("failed to initialize package.json: {0}")]
+    PackageJsonInit(String),
+}
@@ -0,0 +1,77 @@
+//! Pipeline step task implementation
+//!
+//! Runs one named, declarable step (see `ProjectConfig::pipeline`) as a scoped
+//! node command, so the frontend can render a real progress graph via
+//! `ProjectStatusResponse::steps` instead of a single `progress: u8`.
+
+use async_trait::async_trait;
+use log::info;
+use tauri::Emitter;
+
+use crate::commands::node_commands::execute_node_command;
+use crate::commands::project::PipelineStep;
+use super::{Task, TaskContext, TaskOutput};
+
+pub struct PipelineStepTask {
+    id: String,
+    name: String,
+    dependencies: Vec<String>,
+    step: PipelineStep,
+}
+
+impl PipelineStepTask {
+    pub fn new(step: PipelineStep, dependencies: Vec<String>) -> Self {
+        Self {
+            id: format!("step:{}", step.id),
+            name: format!("Pipeline step: {}", step.id),
+            dependencies,
+            step,
+        }
+    }
+}
+
+#[async_trait]
+impl Task for PipelineStepTask {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn dependencies(&self) -> &[String] {
+        &self.dependencies
+    }
+
+    async fn execute(&self, context: &TaskContext) -> Result<TaskOutput, String> {
+        let app_handle = &context.app_handle;
+        // Write into the run's staging directory, same as every other task
+        // -- `context.project_dir` is already the full path (see
+        // `generation.rs`), so joining `config.name` onto it again pointed
+        // at a path that never exists.
+        let project_dir = context.staging_dir.to_path_buf();
+
+        info!("Running pipeline step '{}': {}", self.step.id, self.step.command);
+        app_handle
+            .emit("log-message", format!("Running pipeline step '{}': {}", self.step.id, self.step.command))
+            .map_err(|e| format!("Failed to emit log message: {}", e))?;
+
+        let options = if self.step.env.is_empty() {
+            None
+        } else {
+            Some(crate::commands::node_commands::NodeCommandOptions {
+                env_vars: Some(self.step.env.clone()),
+                ..Default::default()
+            })
+        };
+
+        let result = execute_node_command(app_handle, &project_dir, &self.step.command, options).await?;
+
+        if !result.success {
+            return Err(format!("Pipeline step '{}' failed: {}", self.step.id, result.stderr));
+        }
+
+        Ok(TaskOutput::new().with("stdout", result.stdout.clone()))
+    }
+}
@@ -0,0 +1,107 @@
+//! Declarative YAML recipe format for module/framework task graphs.
+//!
+//! `ProjectGenerator::create_tasks` otherwise assumes a fixed three-phase
+//! shape (one `FrameworkTask`, one `ModuleTask` per selected module, one
+//! `CleanupTask`). A module can instead ship a `recipes/<module_id>.yaml`
+//! file declaring extra tasks -- post-install steps or inter-module
+//! ordering like "run migrations after both db and auth" -- without
+//! `ProjectGenerator` knowing anything about them. A recipe task is run as
+//! a `PipelineStepTask`, the same scoped-node-command task `ProjectConfig::pipeline`
+//! steps use, so its `depends` entries are plain task ids (symbolic,
+//! e.g. `module:auth`) merged straight into the rest of the DAG; the
+//! existing cycle/missing-dependency validation in `create_tasks` runs over
+//! the merged graph unchanged.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use log::warn;
+use serde::{Deserialize, Serialize};
+
+use crate::commands::project::PipelineStep;
+use super::{PipelineStepTask, Task};
+
+/// One task declared by a recipe file.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RecipeTask {
+    /// Task id, unprefixed (becomes `step:{id}` once instantiated, same as
+    /// `ProjectConfig::pipeline` steps).
+    pub id: String,
+    /// Ids of tasks that must complete before this one, e.g. `module:auth`
+    /// or another recipe task's `step:{id}`. Falls back to the recipe's
+    /// default dependencies (see `Recipe::into_tasks`) when empty.
+    #[serde(default)]
+    pub depends: Vec<String>,
+    /// The scoped node command to run for this task (validated by `CommandScope`).
+    pub command: String,
+    /// Extra environment variables for this task.
+    #[serde(default)]
+    pub env: HashMap<String, String>,
+}
+
+/// A module or framework's declared task graph, loaded from YAML.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct Recipe {
+    #[serde(default)]
+    pub tasks: Vec<RecipeTask>,
+}
+
+impl Recipe {
+    /// Find and parse the recipe file for `module_id`, if one exists.
+    /// Searches the same bundled/user-template directories
+    /// `TemplateRegistry` merges framework/module definitions from, under a
+    /// sibling `recipes/` directory. A file that fails to parse is skipped
+    /// with a warning and the search moves on to the next directory, rather
+    /// than failing generation; `None` if no directory has a parsable file.
+    pub fn load_for_module(module_id: &str) -> Option<Self> {
+        let file_name = format!("{}.yaml", module_id);
+
+        // Same layering `TemplateRegistry` uses for framework/module
+        // definitions: check the user's own directory first so a user's
+        // recipe override actually takes effect and survives an app
+        // update, falling back to the bundled copy only if they don't have
+        // one.
+        let mut search_dirs = Vec::new();
+        if let Some(user_dir) = crate::commands::template_registry::user_template_dir() {
+            search_dirs.push(user_dir);
+        }
+        search_dirs.extend(crate::commands::template_registry::bundled_search_dirs());
+
+        search_dirs.into_iter().find_map(|dir| Self::load_file(&dir.join("recipes").join(&file_name)))
+    }
+
+    fn load_file(path: &PathBuf) -> Option<Self> {
+        let content = std::fs::read_to_string(path).ok()?;
+        match serde_yaml::from_str::<Recipe>(&content) {
+            Ok(recipe) => Some(recipe),
+            Err(e) => {
+                warn!("Failed to parse recipe file {}: {}", path.display(), e);
+                None
+            }
+        }
+    }
+
+    /// Instantiate every declared task as a `PipelineStepTask`, falling back
+    /// to `default_deps` for any task that doesn't declare its own `depends`.
+    pub fn into_tasks(self, default_deps: &[String]) -> Vec<Box<dyn Task>> {
+        self.tasks
+            .into_iter()
+            .map(|recipe_task| {
+                let dependencies = if recipe_task.depends.is_empty() {
+                    default_deps.to_vec()
+                } else {
+                    recipe_task.depends.clone()
+                };
+
+                let step = PipelineStep {
+                    id: recipe_task.id,
+                    command: recipe_task.command,
+                    depends_on: recipe_task.depends,
+                    env: recipe_task.env,
+                };
+
+                Box::new(PipelineStepTask::new(step, dependencies)) as Box<dyn Task>
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,53 @@
+//! Crate-wide, diagnostic-rich error type for task execution.
+//!
+//! `Task::execute` still returns `Result<TaskOutput, String>` (the trait's contract
+//! across every task type -- see `ModuleTaskError`'s doc comment for why).
+//! `TaskError` plays the same role `ModuleTaskError` plays for `ModuleTask`,
+//! but for `FrameworkTask` and `DirectoryTask`: a `miette::Diagnostic`
+//! carrying a stable error code and actionable `help` text, rendered into a
+//! single string (message, code, and help included) at the point each task
+//! converts it into its `Result<TaskOutput, String>`.
+
+use miette::Diagnostic;
+use thiserror::Error;
+
+#[derive(Debug, Error, Diagnostic)]
+pub enum TaskError {
+    #[error("framework '{id}' not found")]
+    #[diagnostic(
+        code(architech::task::framework_not_found),
+        help("Check that '{id}' matches a framework ID returned by `get_frameworks`.")
+    )]
+    FrameworkNotFound { id: String },
+
+    #[error("base directory does not exist: {path}")]
+    #[diagnostic(
+        code(architech::task::base_dir_missing),
+        help("Create '{path}' first, or point the project configuration's `path` field at an existing directory.")
+    )]
+    BaseDirMissing { path: String },
+
+    #[error("framework '{framework}' has no setup command configured")]
+    #[diagnostic(
+        code(architech::task::setup_command_missing),
+        help("Set the `setup_command` field in the project configuration for the '{framework}' framework.")
+    )]
+    SetupCommandMissing { framework: String },
+
+    #[error("filesystem operation on '{path}' failed")]
+    #[diagnostic(code(architech::task::filesystem_error))]
+    FilesystemError {
+        path: String,
+        #[source]
+        source: std::io::Error,
+    },
+}
+
+impl TaskError {
+    /// Render this error's full diagnostic -- message, code, and help text,
+    /// plus the source chain for `FilesystemError` -- into a single string,
+    /// for the boundary where tasks must still return `Result<TaskOutput, String>`.
+    pub fn render(self) -> String {
+        format!("{:?}", miette::Report::new(self))
+    }
+}
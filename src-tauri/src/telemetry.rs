@@ -0,0 +1,253 @@
+//! Opt-in crash and generation-failure telemetry via Sentry.
+//!
+//! Off by default on two independent axes: the `telemetry` build feature
+//! (a build without it carries no Sentry dependency at all) and a user
+//! setting persisted at `<app_data_dir>/telemetry.json`. Only when both are
+//! on does `init` actually install a Sentry client, a `log`-to-breadcrumb
+//! bridge, and a panic hook. `register_event_listeners`'s `Failed` arm
+//! calls `capture_generation_failure` unconditionally; with telemetry off
+//! that call is a no-op, so the event loop doesn't need its own feature
+//! gate.
+
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use tauri::{AppHandle, Manager};
+
+const SETTINGS_FILE_NAME: &str = "telemetry.json";
+
+/// User's telemetry opt-in, persisted alongside other app data so it
+/// survives restarts without depending on frontend-local storage.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TelemetrySettings {
+    #[serde(default)]
+    pub enabled: bool,
+}
+
+impl Default for TelemetrySettings {
+    fn default() -> Self {
+        Self { enabled: false }
+    }
+}
+
+impl TelemetrySettings {
+    fn path(app_handle: &AppHandle) -> Result<PathBuf, String> {
+        app_handle
+            .path()
+            .app_data_dir()
+            .map(|dir| dir.join(SETTINGS_FILE_NAME))
+            .map_err(|e| format!("Failed to resolve app data directory: {}", e))
+    }
+
+    pub fn load(app_handle: &AppHandle) -> Self {
+        Self::path(app_handle)
+            .ok()
+            .filter(|path| path.exists())
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, app_handle: &AppHandle) -> Result<(), String> {
+        let path = Self::path(app_handle)?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create '{}': {}", parent.display(), e))?;
+        }
+        let rendered = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize telemetry settings: {}", e))?;
+        std::fs::write(&path, rendered)
+            .map_err(|e| format!("Failed to write '{}': {}", path.display(), e))
+    }
+}
+
+/// Whether the user has opted in to crash/failure telemetry.
+#[tauri::command]
+pub fn get_telemetry_enabled(app_handle: AppHandle) -> bool {
+    TelemetrySettings::load(&app_handle).enabled
+}
+
+/// Set the user's telemetry opt-in. Takes effect on the next app start --
+/// the Sentry client is only initialized once, during `main()`.
+#[tauri::command]
+pub fn set_telemetry_enabled(app_handle: AppHandle, enabled: bool) -> Result<(), String> {
+    TelemetrySettings { enabled }.save(&app_handle)
+}
+
+#[cfg(feature = "telemetry")]
+mod backend {
+    use super::TelemetrySettings;
+    use sentry::protocol::{Breadcrumb, Value};
+    use tauri::AppHandle;
+
+    /// Recent context attached to a captured generation failure: the
+    /// failing project's per-task state/metadata and its last few log
+    /// lines, so a Sentry issue is diagnosable without reproducing the
+    /// failure locally.
+    struct FailureContext {
+        project_id: String,
+        error: String,
+        resumable: bool,
+        task_states: std::collections::HashMap<String, crate::tasks::TaskState>,
+        task_metadata: std::collections::HashMap<String, crate::state::GenerationTask>,
+        recent_logs: Vec<crate::state::LogEntry>,
+    }
+
+    /// How many of a project's most recent log entries to attach as
+    /// breadcrumbs on a captured failure, on top of whatever breadcrumbs
+    /// the global `log`-to-Sentry bridge already collected.
+    const FAILURE_LOG_BREADCRUMB_COUNT: usize = 50;
+
+    async fn build_failure_context(
+        app_state: &crate::state::AppState,
+        project_id: &str,
+        error: &str,
+        resumable: bool,
+    ) -> FailureContext {
+        let task_states = app_state.get_all_task_states(project_id).await;
+        let task_metadata = app_state.get_task_metadata(project_id).await;
+        let mut recent_logs = app_state.get_logs(project_id).await;
+        if recent_logs.len() > FAILURE_LOG_BREADCRUMB_COUNT {
+            let drop = recent_logs.len() - FAILURE_LOG_BREADCRUMB_COUNT;
+            recent_logs.drain(0..drop);
+        }
+
+        FailureContext {
+            project_id: project_id.to_string(),
+            error: error.to_string(),
+            resumable,
+            task_states,
+            task_metadata,
+            recent_logs,
+        }
+    }
+
+    /// DSN is supplied at runtime, not baked into the binary, so builds with
+    /// the `telemetry` feature still ship with telemetry inert until both
+    /// `ARCHITECH_SENTRY_DSN` is set and the user opts in.
+    const DSN_ENV_VAR: &str = "ARCHITECH_SENTRY_DSN";
+
+    /// Initialize the Sentry client and a panic hook, if the user has
+    /// opted in and a DSN is configured. Returns the guard the caller must
+    /// keep alive for the lifetime of the process (dropping it flushes
+    /// pending events).
+    pub fn init(app_handle: &AppHandle) -> Option<sentry::ClientInitGuard> {
+        if !TelemetrySettings::load(app_handle).enabled {
+            log::info!("Telemetry is disabled (user opt-out); skipping Sentry init");
+            return None;
+        }
+
+        let dsn = match std::env::var(DSN_ENV_VAR) {
+            Ok(dsn) if !dsn.is_empty() => dsn,
+            _ => {
+                log::info!("Telemetry is enabled but {} is not set; skipping Sentry init", DSN_ENV_VAR);
+                return None;
+            }
+        };
+
+        let guard = sentry::init((
+            dsn,
+            sentry::ClientOptions {
+                release: sentry::release_name!(),
+                attach_stacktrace: true,
+                // The panic integration is part of Sentry's default
+                // integrations, so a hard panic anywhere in the app is
+                // reported without a separate hook.
+                ..Default::default()
+            },
+        ));
+
+        // Bridge every `log::info!`/`log::warn!`/`log::error!` call into a
+        // Sentry breadcrumb trail, so a captured event carries the
+        // moments leading up to it, not just the event itself.
+        sentry_log::SentryLogger::with_dest(log::logger())
+            .filter(|md| match md.level() {
+                log::Level::Error => sentry_log::LogFilter::Event,
+                _ => sentry_log::LogFilter::Breadcrumb,
+            })
+            .install();
+
+        log::info!("Sentry telemetry initialized");
+        Some(guard)
+    }
+
+    /// Capture a structured Sentry event for a generation failure, with
+    /// the failing project's task states/metadata attached as context and
+    /// its recent logs attached as breadcrumbs.
+    pub async fn capture_generation_failure(
+        app_state: &crate::state::AppState,
+        project_id: &str,
+        error: &str,
+        resumable: bool,
+    ) {
+        if sentry::Hub::current().client().is_none() {
+            return;
+        }
+
+        let context = build_failure_context(app_state, project_id, error, resumable).await;
+        report(context);
+    }
+
+    fn report(context: FailureContext) {
+        sentry::with_scope(
+            |scope| {
+                scope.set_tag("project_id", &context.project_id);
+                scope.set_tag("resumable", context.resumable.to_string());
+                scope.set_extra(
+                    "task_states",
+                    Value::String(format!("{:?}", context.task_states)),
+                );
+                scope.set_extra(
+                    "task_metadata",
+                    Value::String(format!("{:?}", context.task_metadata)),
+                );
+
+                for entry in &context.recent_logs {
+                    scope.add_breadcrumb(Breadcrumb {
+                        category: entry.task_id.clone(),
+                        message: Some(entry.message.clone()),
+                        level: match entry.level {
+                            crate::state::LogLevel::Error => sentry::Level::Error,
+                            crate::state::LogLevel::Warn => sentry::Level::Warning,
+                            crate::state::LogLevel::Info => sentry::Level::Info,
+                            crate::state::LogLevel::Debug => sentry::Level::Debug,
+                        },
+                        ..Default::default()
+                    });
+                }
+            },
+            || {
+                sentry::capture_message(
+                    &format!("Generation failed for project {}: {}", context.project_id, context.error),
+                    sentry::Level::Error,
+                );
+            },
+        );
+    }
+}
+
+#[cfg(not(feature = "telemetry"))]
+mod backend {
+    use super::TelemetrySettings;
+    use tauri::AppHandle;
+
+    /// No-op client guard so `main()` doesn't need its own `cfg` gate
+    /// around the returned value.
+    pub struct NoopGuard;
+
+    pub fn init(app_handle: &AppHandle) -> Option<NoopGuard> {
+        if TelemetrySettings::load(app_handle).enabled {
+            log::info!("Telemetry was requested but this build was compiled without the 'telemetry' feature");
+        }
+        None
+    }
+
+    pub async fn capture_generation_failure(
+        _app_state: &crate::state::AppState,
+        _project_id: &str,
+        _error: &str,
+        _resumable: bool,
+    ) {
+    }
+}
+
+pub use backend::{capture_generation_failure, init};
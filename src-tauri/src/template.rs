@@ -0,0 +1,74 @@
+//! Handlebars-style `{{field}}` interpolation for `ProjectConfig` string
+//! values, resolved against the config itself.
+//!
+//! Module and framework tasks only ever saw the raw fields a user typed in
+//! -- there was no way to derive one field from another (a package name
+//! built from the project name, a connection string composed from a few
+//! answers). `render_config` runs every string value in the config through
+//! a handlebars template, with the full config (as JSON) available as
+//! substitution context, before task creation ever sees it.
+
+use handlebars::Handlebars;
+use serde_json::Value;
+use thiserror::Error;
+
+use crate::commands::project::ProjectConfig;
+
+#[derive(Debug, Error)]
+pub enum TemplateError {
+    #[error("invalid template in field '{field}': {reason}")]
+    InvalidTemplate { field: String, reason: String },
+}
+
+/// Render every string value in `config` as a handlebars template against
+/// `config` itself, returning the expanded config. A field with no `{{` in
+/// it round-trips unchanged. Errors name the dotted path of the offending
+/// field (e.g. `pipeline[0].command`), so an unknown variable or bad
+/// syntax is actionable instead of a bare parser message.
+pub fn render_config(config: &ProjectConfig) -> Result<ProjectConfig, TemplateError> {
+    let engine = Handlebars::new();
+    let context = serde_json::to_value(config).map_err(|e| TemplateError::InvalidTemplate {
+        field: "<config>".to_string(),
+        reason: e.to_string(),
+    })?;
+
+    let rendered = render_value(&engine, &context, &context, String::new())?;
+
+    serde_json::from_value(rendered).map_err(|e| TemplateError::InvalidTemplate {
+        field: "<config>".to_string(),
+        reason: e.to_string(),
+    })
+}
+
+fn render_value(
+    engine: &Handlebars,
+    value: &Value,
+    context: &Value,
+    field: String,
+) -> Result<Value, TemplateError> {
+    match value {
+        Value::String(s) if s.contains("{{") => {
+            let rendered = engine
+                .render_template(s, context)
+                .map_err(|e| TemplateError::InvalidTemplate { field: field.clone(), reason: e.to_string() })?;
+            Ok(Value::String(rendered))
+        }
+        Value::Array(items) => {
+            let rendered = items
+                .iter()
+                .enumerate()
+                .map(|(i, item)| render_value(engine, item, context, format!("{}[{}]", field, i)))
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(Value::Array(rendered))
+        }
+        Value::Object(map) => {
+            let mut rendered = serde_json::Map::with_capacity(map.len());
+            for (key, val) in map {
+                let nested = if field.is_empty() { key.clone() } else { format!("{}.{}", field, key) };
+                rendered.insert(key.clone(), render_value(engine, val, context, nested)?);
+            }
+            Ok(Value::Object(rendered))
+        }
+        other => Ok(other.clone()),
+    }
+}
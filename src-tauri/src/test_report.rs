@@ -0,0 +1,184 @@
+//! Structured test-result reporting.
+//!
+//! `CleanupTask` used to run the project's `test` script and only look at
+//! `result.success`, logging raw stderr on failure. This parses the two
+//! machine-readable test-output formats that show up in generated
+//! projects -- Jest's `--json` output and TAP -- into a small sequence of
+//! events: a `Plan` up front, one `Result` per test, then a `Summary`.
+//! Emitting these as `test-progress` events lets the frontend render a
+//! live test list instead of a wall of log text. When the output matches
+//! neither format, `parse_test_output` returns `None` and the caller falls
+//! back to the plain success/failure behavior.
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+
+/// The outcome of a single test.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case", tag = "status")]
+pub enum TestStatus {
+    Ok,
+    Failed { message: Option<String> },
+    Ignored,
+}
+
+/// One `test-progress` event, emitted in order: a single `Plan`, then one
+/// `Result` per test, then a single `Summary`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "event")]
+pub enum TestEvent {
+    /// Emitted once, before any `Result`.
+    Plan {
+        /// Total tests the run reported.
+        total: usize,
+        /// Tests excluded by a name filter, if the format reports it.
+        filtered: usize,
+    },
+    /// One per test, in the order the test output reported them.
+    Result {
+        name: String,
+        duration_ms: Option<u64>,
+        status: TestStatus,
+    },
+    /// Emitted once, after every `Result`.
+    Summary {
+        passed: usize,
+        failed: usize,
+        ignored: usize,
+        duration_ms: u64,
+    },
+}
+
+/// Emit `events` in order as `test-progress` events on `app_handle`.
+pub fn emit_events(app_handle: &AppHandle, events: &[TestEvent]) {
+    for event in events {
+        if let Err(e) = app_handle.emit("test-progress", event) {
+            log::warn!("Failed to emit test-progress event: {}", e);
+        }
+    }
+}
+
+/// Parse `stdout` as Jest `--json` output, then as TAP, returning the
+/// events it describes. `None` if it matches neither format.
+pub fn parse_test_output(stdout: &str) -> Option<Vec<TestEvent>> {
+    parse_jest_json(stdout).or_else(|| parse_tap(stdout))
+}
+
+/// Parse Jest's `--json` reporter output: a single JSON object with a
+/// `testResults` array of suites, each holding an `assertionResults` array
+/// of individual tests (`status` one of `passed`/`failed`/`pending`/`todo`).
+fn parse_jest_json(stdout: &str) -> Option<Vec<TestEvent>> {
+    let report: serde_json::Value = serde_json::from_str(stdout.trim()).ok()?;
+    let suites = report.get("testResults")?.as_array()?;
+
+    let total = report.get("numTotalTests").and_then(|v| v.as_u64()).unwrap_or(0) as usize;
+    let mut events = vec![TestEvent::Plan { total, filtered: 0 }];
+
+    let (mut passed, mut failed, mut ignored, mut duration_ms) = (0usize, 0usize, 0usize, 0u64);
+
+    for suite in suites {
+        let assertions = suite.get("assertionResults").and_then(|a| a.as_array())?;
+        for assertion in assertions {
+            let name = assertion
+                .get("fullName")
+                .or_else(|| assertion.get("title"))
+                .and_then(|v| v.as_str())
+                .unwrap_or("unknown test")
+                .to_string();
+            let test_duration_ms = assertion.get("duration").and_then(|v| v.as_u64());
+            if let Some(d) = test_duration_ms {
+                duration_ms += d;
+            }
+
+            let status = match assertion.get("status").and_then(|v| v.as_str()) {
+                Some("passed") => {
+                    passed += 1;
+                    TestStatus::Ok
+                }
+                Some("failed") => {
+                    failed += 1;
+                    let message = assertion
+                        .get("failureMessages")
+                        .and_then(|m| m.as_array())
+                        .and_then(|m| m.first())
+                        .and_then(|v| v.as_str())
+                        .map(|s| s.to_string());
+                    TestStatus::Failed { message }
+                }
+                Some("pending") | Some("skipped") | Some("todo") => {
+                    ignored += 1;
+                    TestStatus::Ignored
+                }
+                _ => continue,
+            };
+
+            events.push(TestEvent::Result { name, duration_ms: test_duration_ms, status });
+        }
+    }
+
+    events.push(TestEvent::Summary { passed, failed, ignored, duration_ms });
+    Some(events)
+}
+
+/// Parse TAP (Test Anything Protocol) output: a `1..N` plan line plus
+/// `ok`/`not ok [#] description [# SKIP|TODO reason]` result lines.
+fn parse_tap(stdout: &str) -> Option<Vec<TestEvent>> {
+    let mut plan_total: Option<usize> = None;
+    let mut results = Vec::new();
+    let (mut passed, mut failed, mut ignored) = (0usize, 0usize, 0usize);
+
+    for line in stdout.lines() {
+        let line = line.trim();
+
+        if let Some(range) = line.strip_prefix("1..") {
+            plan_total = range.trim().parse().ok();
+            continue;
+        }
+
+        let (is_failure, rest) = if let Some(rest) = line.strip_prefix("not ok") {
+            (true, rest)
+        } else if let Some(rest) = line.strip_prefix("ok") {
+            (false, rest)
+        } else {
+            continue;
+        };
+
+        // `rest` looks like " 1 - test name # SKIP reason" -- drop the
+        // leading test number, then split off any TAP directive.
+        let description = rest.trim_start().splitn(2, '-').last().unwrap_or(rest).trim();
+        let (name, directive) = match description.split_once('#') {
+            Some((name, directive)) => (name.trim(), Some(directive.trim().to_lowercase())),
+            None => (description, None),
+        };
+
+        let is_skipped = directive
+            .as_deref()
+            .is_some_and(|d| d.starts_with("skip") || d.starts_with("todo"));
+
+        let status = if is_skipped {
+            ignored += 1;
+            TestStatus::Ignored
+        } else if is_failure {
+            failed += 1;
+            TestStatus::Failed { message: None }
+        } else {
+            passed += 1;
+            TestStatus::Ok
+        };
+
+        results.push(TestEvent::Result {
+            name: name.to_string(),
+            duration_ms: None,
+            status,
+        });
+    }
+
+    if results.is_empty() {
+        return None;
+    }
+
+    let mut events = vec![TestEvent::Plan { total: plan_total.unwrap_or(results.len()), filtered: 0 }];
+    events.extend(results);
+    events.push(TestEvent::Summary { passed, failed, ignored, duration_ms: 0 });
+    Some(events)
+}
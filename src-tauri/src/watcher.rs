@@ -0,0 +1,209 @@
+//! Post-scaffold filesystem watcher.
+//!
+//! `DirectoryTask` starts one of these as soon as the enforced directories
+//! exist, recursively watching the staging directory tasks write into so
+//! the frontend gets live feedback as later tasks (`npm install`, `git
+//! init`, ...) mutate the tree -- today the only signal for that is the
+//! coarse `log-message` strings tasks emit. Raw filesystem events are
+//! coalesced over a debounce window and emitted as batched `fs-change`
+//! events instead, so a bulk operation that touches thousands of
+//! `node_modules` files doesn't flood the UI with one event per file.
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use log::{debug, warn};
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+use tokio::sync::{mpsc, Mutex, Notify};
+use tokio::time::Instant;
+
+/// A kind of filesystem change. A single coalesced `FsChangeEvent` can
+/// carry more than one for the same path when it mutated more than one way
+/// inside the debounce window (e.g. created then modified before the
+/// window flushed) -- modeled on the combinable change-kind sets
+/// filesystem-watching remote APIs report instead of a single tag per
+/// event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+    Created,
+    Modified,
+    Deleted,
+    Renamed,
+}
+
+/// One path's coalesced changes, emitted on the `fs-change` event channel.
+#[derive(Debug, Clone, Serialize)]
+pub struct FsChangeEvent {
+    /// Path relative to the watched root, forward-slash separated.
+    pub path: String,
+    pub kinds: Vec<ChangeKind>,
+}
+
+/// How long to wait after the last observed change to a path before
+/// flushing it as an event -- long enough that a bulk operation unpacking
+/// thousands of files collapses into one batch, short enough the UI still
+/// feels live.
+const DEBOUNCE_MS: u64 = 300;
+
+/// Paths ignored regardless of what's passed in to `watch` -- noisy or
+/// meaningless to show the user even while they're actively being written.
+fn default_ignored() -> HashSet<String> {
+    [".git", "node_modules"].into_iter().map(String::from).collect()
+}
+
+/// Handle to a running watcher. Dropping or calling `stop` tears down the
+/// background debounce task; the underlying OS watch is torn down when the
+/// `notify` watcher itself drops.
+pub struct WatcherHandle {
+    _watcher: RecommendedWatcher,
+    stop: Arc<Notify>,
+}
+
+impl WatcherHandle {
+    pub fn stop(&self) {
+        self.stop.notify_one();
+    }
+}
+
+/// Start recursively watching `root`, emitting debounced `fs-change` events
+/// to `app_handle` until the returned handle is stopped. Paths with any
+/// component in `ignored` (plus `.git` and `node_modules`, always) are
+/// never reported.
+pub fn watch(root: PathBuf, app_handle: AppHandle, ignored: HashSet<String>) -> Result<WatcherHandle, String> {
+    let mut ignored = ignored;
+    ignored.extend(default_ignored());
+
+    let (raw_tx, mut raw_rx) = mpsc::unbounded_channel::<notify::Event>();
+
+    let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| match res {
+        Ok(event) => {
+            let _ = raw_tx.send(event);
+        }
+        Err(e) => warn!("Filesystem watch error: {}", e),
+    })
+    .map_err(|e| format!("Failed to create filesystem watcher: {}", e))?;
+
+    watcher
+        .watch(&root, RecursiveMode::Recursive)
+        .map_err(|e| format!("Failed to watch {}: {}", root.display(), e))?;
+
+    let stop = Arc::new(Notify::new());
+    let stop_signal = stop.clone();
+
+    tokio::spawn(async move {
+        let mut pending: HashMap<String, Vec<ChangeKind>> = HashMap::new();
+        let mut deadline: Option<Instant> = None;
+
+        loop {
+            let debounce = async {
+                match deadline {
+                    Some(d) => tokio::time::sleep_until(d).await,
+                    None => std::future::pending::<()>().await,
+                }
+            };
+
+            tokio::select! {
+                _ = stop_signal.notified() => {
+                    debug!("Filesystem watcher for {} stopped", root.display());
+                    break;
+                }
+                event = raw_rx.recv() => {
+                    match event {
+                        Some(event) => {
+                            let kind = classify(&event.kind);
+                            for path in &event.paths {
+                                let Some(relative) = relative_path(&root, path) else { continue };
+                                if is_ignored(&relative, &ignored) {
+                                    continue;
+                                }
+                                let kinds = pending.entry(relative).or_default();
+                                if !kinds.contains(&kind) {
+                                    kinds.push(kind);
+                                }
+                            }
+                            deadline = Some(Instant::now() + Duration::from_millis(DEBOUNCE_MS));
+                        }
+                        // The watcher (and its sender) dropped.
+                        None => break,
+                    }
+                }
+                _ = debounce => {
+                    for (path, kinds) in pending.drain() {
+                        if let Err(e) = app_handle.emit("fs-change", FsChangeEvent { path, kinds }) {
+                            warn!("Failed to emit fs-change event: {}", e);
+                        }
+                    }
+                    deadline = None;
+                }
+            }
+        }
+    });
+
+    Ok(WatcherHandle { _watcher: watcher, stop })
+}
+
+fn relative_path(root: &Path, path: &Path) -> Option<String> {
+    path.strip_prefix(root)
+        .ok()
+        .map(|p| p.to_string_lossy().replace('\\', "/"))
+}
+
+fn is_ignored(relative: &str, ignored: &HashSet<String>) -> bool {
+    Path::new(relative)
+        .components()
+        .any(|c| ignored.contains(c.as_os_str().to_string_lossy().as_ref()))
+}
+
+fn classify(kind: &notify::EventKind) -> ChangeKind {
+    use notify::event::ModifyKind;
+    use notify::EventKind;
+
+    match kind {
+        EventKind::Create(_) => ChangeKind::Created,
+        EventKind::Modify(ModifyKind::Name(_)) => ChangeKind::Renamed,
+        EventKind::Modify(_) => ChangeKind::Modified,
+        EventKind::Remove(_) => ChangeKind::Deleted,
+        _ => ChangeKind::Modified,
+    }
+}
+
+/// Registry of every project currently being watched, so a task can start a
+/// watcher and the run that started it can stop it once the pipeline ends.
+/// Mirrors `crate::worker::WorkerManager`.
+pub struct WatcherRegistry {
+    watchers: Mutex<HashMap<String, Arc<WatcherHandle>>>,
+}
+
+impl WatcherRegistry {
+    pub fn new() -> Self {
+        Self {
+            watchers: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Start watching `root` for `project_id`, replacing any watcher
+    /// already registered for it.
+    pub async fn start(
+        &self,
+        project_id: &str,
+        root: PathBuf,
+        app_handle: AppHandle,
+        ignored: HashSet<String>,
+    ) -> Result<(), String> {
+        let handle = Arc::new(watch(root, app_handle, ignored)?);
+        self.watchers.lock().await.insert(project_id.to_string(), handle);
+        Ok(())
+    }
+
+    /// Stop and remove `project_id`'s watcher, if one is running.
+    pub async fn stop(&self, project_id: &str) {
+        if let Some(handle) = self.watchers.lock().await.remove(project_id) {
+            handle.stop();
+        }
+    }
+}
@@ -0,0 +1,338 @@
+//! Webhook/notifier subsystem forwarding `ProjectEvent`s to external HTTP
+//! endpoints.
+//!
+//! Mirrors moon's webhooks and build-o-tron's notifier: a dedicated
+//! subscriber task drains `AppState::subscribe()` and, for each event a
+//! registered endpoint's filter matches, hands it to that endpoint's own
+//! small bounded delivery queue. Each endpoint has a dedicated delivery
+//! worker that POSTs a signed JSON payload with retry-on-failure, so a slow
+//! or unreachable endpoint can never block generation itself -- a full
+//! queue just drops the event and logs it.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use hmac::{Hmac, Mac};
+use log::{debug, error, warn};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use tokio::sync::{mpsc, Mutex};
+use uuid::Uuid;
+
+use crate::state::{AppState, ProjectEvent};
+use crate::tasks::RetryPolicy;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Which `ProjectEvent` variants a webhook can subscribe to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookEvent {
+    Started,
+    Progress,
+    Completed,
+    Failed,
+    Cancelled,
+}
+
+impl WebhookEvent {
+    fn as_str(&self) -> &'static str {
+        match self {
+            WebhookEvent::Started => "started",
+            WebhookEvent::Progress => "progress",
+            WebhookEvent::Completed => "completed",
+            WebhookEvent::Failed => "failed",
+            WebhookEvent::Cancelled => "cancelled",
+        }
+    }
+}
+
+/// A registered notification endpoint.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookConfig {
+    pub id: String,
+    pub url: String,
+    /// Shared secret used to HMAC-SHA256 sign each delivery's body, sent in
+    /// the `X-Architech-Signature` header. `None` sends deliveries unsigned.
+    #[serde(default)]
+    pub secret: Option<String>,
+    /// The subset of events this endpoint wants delivered.
+    pub events: Vec<WebhookEvent>,
+}
+
+const WEBHOOKS_FILE: &str = "webhooks.json";
+
+/// Deliveries queued per endpoint before new ones are dropped (and logged)
+/// rather than letting a stuck endpoint back up generation.
+const DELIVERY_QUEUE_CAPACITY: usize = 32;
+
+/// Attempts (including the first) before a delivery is given up on.
+/// Matches `DEFAULT_MAX_TASK_ATTEMPTS`'s role for task retries.
+const WEBHOOK_MAX_ATTEMPTS: u32 = 3;
+
+struct DeliveryJob {
+    project_id: String,
+    event_name: &'static str,
+    payload: serde_json::Value,
+}
+
+/// Registry of configured webhook endpoints plus the delivery worker
+/// feeding each one.
+pub struct WebhookRegistry {
+    configs: Mutex<Vec<WebhookConfig>>,
+    senders: Mutex<HashMap<String, mpsc::Sender<DeliveryJob>>>,
+    client: Client,
+}
+
+impl WebhookRegistry {
+    pub fn new() -> Self {
+        Self {
+            configs: Mutex::new(Vec::new()),
+            senders: Mutex::new(HashMap::new()),
+            client: Client::new(),
+        }
+    }
+
+    fn config_path(app_dir: &Path) -> PathBuf {
+        app_dir.join(WEBHOOKS_FILE)
+    }
+
+    /// Load persisted configs from `app_dir`. Called once during
+    /// `AppState::initialize`; delivery workers for these are started
+    /// separately by `spawn_dispatcher`, once the caller holds the
+    /// `Arc<AppState>` a worker needs for logging delivery failures.
+    pub async fn load(&self, app_dir: &Path) -> Result<(), String> {
+        let path = Self::config_path(app_dir);
+        if !path.exists() {
+            return Ok(());
+        }
+
+        let content = std::fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read webhook config: {}", e))?;
+        let loaded: Vec<WebhookConfig> = serde_json::from_str(&content)
+            .map_err(|e| format!("Failed to parse webhook config: {}", e))?;
+
+        *self.configs.lock().await = loaded;
+        Ok(())
+    }
+
+    async fn save(&self, app_dir: &Path) -> Result<(), String> {
+        let configs = self.configs.lock().await.clone();
+        let content = serde_json::to_string_pretty(&configs)
+            .map_err(|e| format!("Failed to serialize webhook config: {}", e))?;
+        std::fs::write(Self::config_path(app_dir), content)
+            .map_err(|e| format!("Failed to write webhook config: {}", e))
+    }
+
+    /// Register a new endpoint, start its delivery worker, and persist it.
+    pub async fn add(
+        &self,
+        url: String,
+        secret: Option<String>,
+        events: Vec<WebhookEvent>,
+        app_state: &Arc<AppState>,
+    ) -> Result<WebhookConfig, String> {
+        let config = WebhookConfig {
+            id: Uuid::new_v4().to_string(),
+            url,
+            secret,
+            events,
+        };
+
+        self.start_delivery_worker(config.clone(), app_state.clone()).await;
+        self.configs.lock().await.push(config.clone());
+        self.save(&app_state.get_app_data_dir()?).await?;
+
+        Ok(config)
+    }
+
+    /// Unregister an endpoint, stopping its delivery worker once its queue
+    /// drains (dropping the sender closes the channel).
+    pub async fn remove(&self, id: &str, app_state: &Arc<AppState>) -> Result<(), String> {
+        self.configs.lock().await.retain(|c| c.id != id);
+        self.senders.lock().await.remove(id);
+        self.save(&app_state.get_app_data_dir()?).await
+    }
+
+    pub async fn list(&self) -> Vec<WebhookConfig> {
+        self.configs.lock().await.clone()
+    }
+
+    async fn start_delivery_worker(&self, config: WebhookConfig, app_state: Arc<AppState>) {
+        let (tx, mut rx) = mpsc::channel::<DeliveryJob>(DELIVERY_QUEUE_CAPACITY);
+        self.senders.lock().await.insert(config.id.clone(), tx);
+
+        let client = self.client.clone();
+        tokio::spawn(async move {
+            while let Some(job) = rx.recv().await {
+                deliver(&client, &config, job, &app_state).await;
+            }
+        });
+    }
+
+    /// Queue `event` for delivery to every endpoint whose filter includes
+    /// it. An endpoint with a full queue drops the event (and logs it)
+    /// rather than blocking the dispatcher, which must keep draining the
+    /// project-event broadcast channel for every other project.
+    async fn dispatch(&self, app_state: &Arc<AppState>, project_id: &str, kind: WebhookEvent, payload: serde_json::Value) {
+        let targets: Vec<(String, mpsc::Sender<DeliveryJob>)> = {
+            let configs = self.configs.lock().await;
+            let senders = self.senders.lock().await;
+            configs
+                .iter()
+                .filter(|c| c.events.contains(&kind))
+                .filter_map(|c| senders.get(&c.id).map(|tx| (c.id.clone(), tx.clone())))
+                .collect()
+        };
+
+        for (id, tx) in targets {
+            let job = DeliveryJob {
+                project_id: project_id.to_string(),
+                event_name: kind.as_str(),
+                payload: payload.clone(),
+            };
+
+            if tx.try_send(job).is_err() {
+                let message = format!("Webhook {} delivery queue full, dropped a {} notification", id, kind.as_str());
+                warn!("{}", message);
+                app_state.add_log(project_id, &message).await;
+            }
+        }
+    }
+}
+
+impl Default for WebhookRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Maps a `ProjectEvent` to the webhook filter it satisfies and the JSON
+/// body to deliver, or `None` for events this subsystem doesn't forward
+/// (task-level events are too chatty for external endpoints).
+fn event_payload(event: &ProjectEvent) -> Option<(String, WebhookEvent, serde_json::Value)> {
+    match event {
+        ProjectEvent::Started { project_id } => Some((
+            project_id.clone(),
+            WebhookEvent::Started,
+            serde_json::json!({ "project_id": project_id }),
+        )),
+        ProjectEvent::Progress { project_id, step, progress, task_counts } => Some((
+            project_id.clone(),
+            WebhookEvent::Progress,
+            serde_json::json!({
+                "project_id": project_id,
+                "step": step,
+                "progress": progress,
+                "completed_tasks": task_counts.map(|(completed, _)| completed),
+                "total_tasks": task_counts.map(|(_, total)| total),
+            }),
+        )),
+        ProjectEvent::Completed { project_id, path } => Some((
+            project_id.clone(),
+            WebhookEvent::Completed,
+            serde_json::json!({ "project_id": project_id, "path": path }),
+        )),
+        ProjectEvent::Failed { project_id, error, resumable } => Some((
+            project_id.clone(),
+            WebhookEvent::Failed,
+            serde_json::json!({ "project_id": project_id, "error": error, "resumable": resumable }),
+        )),
+        ProjectEvent::Cancelled { project_id } => Some((
+            project_id.clone(),
+            WebhookEvent::Cancelled,
+            serde_json::json!({ "project_id": project_id }),
+        )),
+        _ => None,
+    }
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// HMAC-SHA256 accepts a key of any length, so this never fails in practice.
+fn sign(secret: &str, body: &[u8]) -> String {
+    let mut mac = HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC-SHA256 accepts keys of any length");
+    mac.update(body);
+    to_hex(&mac.finalize().into_bytes())
+}
+
+async fn deliver(client: &Client, config: &WebhookConfig, job: DeliveryJob, app_state: &Arc<AppState>) {
+    let body = serde_json::json!({
+        "event": job.event_name,
+        "project_id": job.project_id,
+        "data": job.payload,
+    });
+
+    let body_bytes = match serde_json::to_vec(&body) {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            error!("Failed to serialize webhook payload for {}: {}", config.url, e);
+            return;
+        }
+    };
+
+    let retry_policy = RetryPolicy::default();
+    let mut attempt: u32 = 1;
+
+    loop {
+        let mut request = client.post(&config.url).header("Content-Type", "application/json");
+        if let Some(secret) = &config.secret {
+            request = request.header("X-Architech-Signature", sign(secret, &body_bytes));
+        }
+
+        match request.body(body_bytes.clone()).send().await {
+            Ok(response) if response.status().is_success() => {
+                debug!("Delivered {} webhook to {}", job.event_name, config.url);
+                return;
+            }
+            Ok(response) => {
+                warn!(
+                    "Webhook {} responded with status {} (attempt {}/{})",
+                    config.url, response.status(), attempt, WEBHOOK_MAX_ATTEMPTS
+                );
+            }
+            Err(e) => {
+                warn!(
+                    "Webhook delivery to {} failed (attempt {}/{}): {}",
+                    config.url, attempt, WEBHOOK_MAX_ATTEMPTS, e
+                );
+            }
+        }
+
+        if attempt >= WEBHOOK_MAX_ATTEMPTS {
+            let message = format!(
+                "Webhook {} unreachable after {} attempts, giving up on a {} notification",
+                config.url, attempt, job.event_name
+            );
+            error!("{}", message);
+            app_state.add_log(&job.project_id, &message).await;
+            return;
+        }
+
+        tokio::time::sleep(tokio::time::Duration::from_millis(retry_policy.delay_for(attempt + 1))).await;
+        attempt += 1;
+    }
+}
+
+/// Start delivery workers for every webhook loaded from disk, then spawn
+/// the subscriber task that forwards matching `ProjectEvent`s to them for
+/// the lifetime of the app.
+pub async fn spawn_dispatcher(app_state: Arc<AppState>) {
+    for config in app_state.webhooks.list().await {
+        app_state.webhooks.start_delivery_worker(config, app_state.clone()).await;
+    }
+
+    let mut rx = app_state.subscribe();
+    let dispatch_state = app_state.clone();
+    tokio::spawn(async move {
+        while let Ok(event) = rx.recv().await {
+            if let Some((project_id, kind, payload)) = event_payload(&event) {
+                dispatch_state.webhooks.dispatch(&dispatch_state, &project_id, kind, payload).await;
+            }
+        }
+    });
+}
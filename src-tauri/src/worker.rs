@@ -0,0 +1,236 @@
+//! Background worker registry for generation runs.
+//!
+//! Mirrors Garage's background task manager: each in-flight generation gets
+//! a `WorkerHandle` tracking its live status, the task it's currently
+//! running, and a control channel the UI can use to pause, resume, or
+//! cancel it without tearing down the whole process. `AppState` holds the
+//! one `WorkerManager` for the app; `generation::execute_tasks` registers a
+//! worker at the start of each run and removes it once the run ends.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::{mpsc, Mutex, Notify};
+
+/// How a worker presents itself to the UI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WorkerStatus {
+    /// Currently running a batch of tasks.
+    Active,
+    /// Alive but between batches.
+    Idle,
+    /// Paused by a `WorkerAction::Pause`; resumes on `Resume`.
+    Paused,
+    /// Hasn't reported progress within the watchdog timeout; presumed stuck.
+    Dead,
+}
+
+/// A control message sent to a running worker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WorkerAction {
+    Start,
+    Pause,
+    Resume,
+    Cancel,
+}
+
+/// A snapshot of a worker's state, as exposed to the frontend.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkerInfo {
+    pub project_id: String,
+    pub status: WorkerStatus,
+    /// The task ID(s) currently occupying the executor's batch, if any.
+    pub current_task: Option<String>,
+    /// Unix timestamp (seconds) of the last progress update.
+    pub last_progress_at: u64,
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Handle to a single project's background worker, shared between the task
+/// executor (which reports progress and checks for pause/cancel between
+/// batches) and the control-message listener spawned alongside it (which
+/// applies `Start`/`Pause`/`Resume`/`Cancel` actions as they arrive).
+pub struct WorkerHandle {
+    project_id: String,
+    tx: mpsc::Sender<WorkerAction>,
+    paused: AtomicBool,
+    cancelled: AtomicBool,
+    last_progress_at: AtomicU64,
+    current_task: Mutex<Option<String>>,
+    status: Mutex<WorkerStatus>,
+    resume_notify: Notify,
+}
+
+impl WorkerHandle {
+    fn new(project_id: String, tx: mpsc::Sender<WorkerAction>) -> Self {
+        Self {
+            project_id,
+            tx,
+            paused: AtomicBool::new(false),
+            cancelled: AtomicBool::new(false),
+            last_progress_at: AtomicU64::new(now_secs()),
+            current_task: Mutex::new(None),
+            status: Mutex::new(WorkerStatus::Active),
+            resume_notify: Notify::new(),
+        }
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::SeqCst)
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Seconds since the last progress update, for the dead-worker watchdog.
+    pub fn seconds_since_progress(&self) -> u64 {
+        now_secs().saturating_sub(self.last_progress_at.load(Ordering::SeqCst))
+    }
+
+    /// Blocks the caller while paused, waking as soon as `Resume` or
+    /// `Cancel` is applied. Returns immediately if not currently paused.
+    pub async fn wait_while_paused(&self) {
+        while self.is_paused() && !self.is_cancelled() {
+            self.resume_notify.notified().await;
+        }
+    }
+
+    /// Records that `task_id` is now running (or `None` between batches),
+    /// refreshing the progress timestamp so the watchdog doesn't treat this
+    /// worker as stuck.
+    pub async fn mark_task(&self, task_id: Option<String>) {
+        *self.current_task.lock().await = task_id;
+        self.last_progress_at.store(now_secs(), Ordering::SeqCst);
+
+        if !self.is_paused() && !self.is_cancelled() {
+            *self.status.lock().await = WorkerStatus::Active;
+        }
+    }
+
+    /// Flags this worker `Dead`. Called by the watchdog once
+    /// `seconds_since_progress` exceeds the configured timeout.
+    pub async fn mark_dead(&self) {
+        *self.status.lock().await = WorkerStatus::Dead;
+    }
+
+    /// Applies a control action to this worker's live state. Called by the
+    /// listener task spawned alongside the worker as actions arrive on its
+    /// channel, never directly by command handlers (see `WorkerManager::control`).
+    pub(crate) async fn apply(&self, action: WorkerAction) {
+        match action {
+            WorkerAction::Start | WorkerAction::Resume => {
+                self.paused.store(false, Ordering::SeqCst);
+                *self.status.lock().await = WorkerStatus::Active;
+                self.resume_notify.notify_waiters();
+            }
+            WorkerAction::Pause => {
+                self.paused.store(true, Ordering::SeqCst);
+                *self.status.lock().await = WorkerStatus::Paused;
+            }
+            WorkerAction::Cancel => {
+                self.cancelled.store(true, Ordering::SeqCst);
+                // Wake a paused waiter so it observes the cancellation
+                // instead of blocking forever.
+                self.resume_notify.notify_waiters();
+            }
+        }
+    }
+
+    pub async fn snapshot(&self) -> WorkerInfo {
+        WorkerInfo {
+            project_id: self.project_id.clone(),
+            status: *self.status.lock().await,
+            current_task: self.current_task.lock().await.clone(),
+            last_progress_at: self.last_progress_at.load(Ordering::SeqCst),
+        }
+    }
+
+    /// Sends `action` to this worker's control-message listener.
+    pub async fn control(&self, action: WorkerAction) -> Result<(), String> {
+        self.tx.send(action).await.map_err(|_| "Worker is no longer running".to_string())
+    }
+}
+
+/// Capacity of a worker's control channel -- generous for a handful of
+/// UI-driven actions, never a high-throughput path.
+const WORKER_ACTION_CHANNEL_CAPACITY: usize = 8;
+
+/// Default time a worker can go without reporting progress before the
+/// watchdog gives up on it and marks it `Dead`.
+pub const DEFAULT_DEAD_WORKER_TIMEOUT_SECS: u64 = 300;
+
+/// Registry of every project currently running a background worker.
+pub struct WorkerManager {
+    workers: Mutex<HashMap<String, Arc<WorkerHandle>>>,
+    dead_worker_timeout_secs: u64,
+}
+
+impl WorkerManager {
+    pub fn new() -> Self {
+        Self {
+            workers: Mutex::new(HashMap::new()),
+            dead_worker_timeout_secs: DEFAULT_DEAD_WORKER_TIMEOUT_SECS,
+        }
+    }
+
+    pub fn dead_worker_timeout_secs(&self) -> u64 {
+        self.dead_worker_timeout_secs
+    }
+
+    /// Registers a new worker for `project_id`, returning its handle and the
+    /// receiving half of its control channel for the caller to listen on.
+    pub async fn register(&self, project_id: &str) -> (Arc<WorkerHandle>, mpsc::Receiver<WorkerAction>) {
+        let (tx, rx) = mpsc::channel(WORKER_ACTION_CHANNEL_CAPACITY);
+        let handle = Arc::new(WorkerHandle::new(project_id.to_string(), tx));
+
+        let mut workers = self.workers.lock().await;
+        workers.insert(project_id.to_string(), handle.clone());
+
+        (handle, rx)
+    }
+
+    /// Removes a project's worker once its run has ended (successfully,
+    /// failed, or cancelled).
+    pub async fn remove(&self, project_id: &str) {
+        self.workers.lock().await.remove(project_id);
+    }
+
+    pub async fn get(&self, project_id: &str) -> Option<Arc<WorkerHandle>> {
+        self.workers.lock().await.get(project_id).cloned()
+    }
+
+    /// Snapshots every currently registered worker.
+    pub async fn list(&self) -> Vec<WorkerInfo> {
+        let workers = self.workers.lock().await.values().cloned().collect::<Vec<_>>();
+        let mut infos = Vec::with_capacity(workers.len());
+        for worker in workers {
+            infos.push(worker.snapshot().await);
+        }
+        infos
+    }
+
+    /// Sends `action` to `project_id`'s worker, if one is currently running.
+    pub async fn control(&self, project_id: &str, action: WorkerAction) -> Result<(), String> {
+        let handle = self.get(project_id).await
+            .ok_or_else(|| format!("No running worker for project {}", project_id))?;
+        handle.control(action).await
+    }
+}
+
+impl Default for WorkerManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}